@@ -31,6 +31,29 @@ impl AiClient {
 		}
 	}
 
+	/// Checks that the provider settings are present and the service
+	/// account key is readable. Does not call the provider.
+	pub fn check_config(&self) -> Result<()> {
+		for (name, value) in [
+			("project", &self.project),
+			("location", &self.location),
+			("model", &self.model),
+		] {
+			if value.trim().is_empty() {
+				return Err(Error::NotConfigured(format!("missing {name}")));
+			}
+		}
+
+		let key_path = self.access_token.key_path();
+		if !std::path::Path::new(key_path).is_file() {
+			return Err(Error::NotConfigured(format!(
+				"credentials file not found: {key_path}"
+			)));
+		}
+
+		Ok(())
+	}
+
 	/// Tries to acquire generation lock for session_id.
 	/// If successful, returns GenerationGuard; if not, returns None.
 	pub fn try_acquire_generation(
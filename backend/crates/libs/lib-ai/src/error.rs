@@ -15,4 +15,7 @@ pub enum Error {
 
 	#[error("Stream error: {0}")]
 	Stream(#[from] reqwest_streams::error::StreamBodyError),
+
+	#[error("AI provider is not configured: {0}")]
+	NotConfigured(String),
 }
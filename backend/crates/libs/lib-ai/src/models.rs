@@ -4,6 +4,14 @@ use serde::{Deserialize, Serialize};
 pub struct ChatRequest {
 	pub system_instruction: Option<SystemInstruction>,
 	pub contents: Vec<Content>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub generation_config: Option<GenerationConfig>,
+}
+
+/// Sampling settings, the provider defaults apply when absent.
+#[derive(Debug, Serialize, Clone, Copy)]
+pub struct GenerationConfig {
+	pub temperature: f32,
 }
 
 #[derive(Debug, Serialize)]
@@ -18,13 +18,17 @@ impl TokenManager {
 		}
 	}
 
+	pub fn key_path(&self) -> &str {
+		&self.key_path
+	}
+
 	pub async fn get_token(&self) -> Result<String> {
 		{
 			let token_guard = self.token.read().await;
-			if let Some((tok, exp)) = &*token_guard {
-				if Instant::now() < *exp {
-					return Ok(tok.clone());
-				}
+			if let Some((tok, exp)) = &*token_guard
+				&& Instant::now() < *exp
+			{
+				return Ok(tok.clone());
 			}
 		}
 
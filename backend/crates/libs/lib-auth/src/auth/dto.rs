@@ -1,4 +1,5 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 #[derive(Debug, Deserialize)]
 pub struct RegisterRequest {
@@ -11,3 +12,40 @@ pub struct LoginRequest {
 	pub username: String,
 	pub password: String,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct RefreshRequest {
+	pub refresh_token: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct LogoutRequest {
+	/// Refresh token of this device, revoked together with the access token.
+	pub refresh_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChangePasswordRequest {
+	pub old_password: String,
+	pub new_password: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpgradeGuestRequest {
+	pub username: String,
+	pub password: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccountDto {
+	pub user_id: Uuid,
+	pub username: String,
+	pub is_guest: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuthTokens {
+	pub token: String,
+	pub refresh_token: String,
+	pub user_id: Uuid,
+}
@@ -1,5 +1,5 @@
 use axum::{
-	http::StatusCode,
+	http::{HeaderValue, StatusCode, header::RETRY_AFTER},
 	response::{IntoResponse, Response},
 };
 use lib_core::client_error::ClientError;
@@ -24,6 +24,32 @@ pub enum AuthError {
 	VerifyError(String),
 	#[error("JWT creation error: {0}")]
 	JwtError(String),
+	#[error("Invalid username: {0}")]
+	InvalidUsername(String),
+	#[error("Weak password: {0}")]
+	WeakPassword(String),
+	#[error("Old password does not match")]
+	WrongPassword,
+	#[error("Invalid username or password")]
+	InvalidCredentials,
+	#[error("Guest accounts have no password")]
+	GuestAccount,
+	#[error("Account is not a guest")]
+	NotGuest,
+	#[error(transparent)]
+	RateLimited(#[from] lib_rate_limit::error::Error),
+}
+
+impl From<diesel::result::Error> for AuthError {
+	fn from(ex: diesel::result::Error) -> Self {
+		AuthError::DbError(ex.to_string())
+	}
+}
+
+impl From<lib_core::model::error::Error> for AuthError {
+	fn from(ex: lib_core::model::error::Error) -> Self {
+		AuthError::DbError(ex.to_string())
+	}
 }
 
 impl AuthError {
@@ -41,6 +67,35 @@ impl AuthError {
 				StatusCode::BAD_REQUEST,
 				ClientError::AUTHENTICATION_FAILED("User exists".to_string()),
 			),
+			AuthError::InvalidUsername(detail) | AuthError::WeakPassword(detail) => {
+				(
+					StatusCode::BAD_REQUEST,
+					ClientError::VALIDATION_FAILED(detail.clone()),
+				)
+			}
+			AuthError::InvalidCredentials => (
+				StatusCode::UNAUTHORIZED,
+				ClientError::AUTHENTICATION_FAILED(
+					"Invalid username or password".to_string(),
+				),
+			),
+			AuthError::RateLimited(err) => err.client_status_and_error(),
+			AuthError::GuestAccount => (
+				StatusCode::BAD_REQUEST,
+				ClientError::VALIDATION_FAILED(
+					"Guest accounts must be upgraded first".to_string(),
+				),
+			),
+			AuthError::NotGuest => (
+				StatusCode::BAD_REQUEST,
+				ClientError::VALIDATION_FAILED("Account is not a guest".to_string()),
+			),
+			AuthError::WrongPassword => (
+				StatusCode::BAD_REQUEST,
+				ClientError::VALIDATION_FAILED(
+					"Old password does not match".to_string(),
+				),
+			),
 			AuthError::DbError(_) => (
 				StatusCode::INTERNAL_SERVER_ERROR,
 				ClientError::INTERNAL_SERVER_ERROR,
@@ -67,6 +122,11 @@ impl IntoResponse for AuthError {
 
 		let mut response = StatusCode::INTERNAL_SERVER_ERROR.into_response();
 
+		if let AuthError::RateLimited(err) = &self {
+			response
+				.headers_mut()
+				.insert(RETRY_AFTER, HeaderValue::from(err.retry_after_secs()));
+		}
 		response.extensions_mut().insert(self);
 
 		response
@@ -1,26 +1,40 @@
-use chrono::{Duration, Utc};
-use jsonwebtoken::{EncodingKey, Header};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation};
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
 	pub sub: Uuid,
 	pub exp: usize,
+	/// Token id, checked against the revocation list.
+	pub jti: Uuid,
+}
+
+impl Claims {
+	pub fn expires_at(&self) -> NaiveDateTime {
+		DateTime::from_timestamp(self.exp as i64, 0)
+			.unwrap_or_default()
+			.naive_utc()
+	}
 }
 
 pub fn create_jwt(
 	user_id: Uuid,
 	secret: &[u8],
+	ttl: Duration,
 ) -> Result<String, jsonwebtoken::errors::Error> {
 	let expiration = Utc::now()
-		.checked_add_signed(Duration::hours(1))
+		.checked_add_signed(ttl)
 		.expect("valid timestamp")
 		.timestamp() as usize;
 
 	let claims = Claims {
 		sub: user_id,
 		exp: expiration,
+		jti: Uuid::new_v4(),
 	};
 
 	jsonwebtoken::encode(
@@ -29,3 +43,28 @@ pub fn create_jwt(
 		&EncodingKey::from_secret(secret),
 	)
 }
+
+pub fn decode_jwt(
+	token: &str,
+	secret: &[u8],
+) -> Result<Claims, jsonwebtoken::errors::Error> {
+	jsonwebtoken::decode::<Claims>(
+		token,
+		&DecodingKey::from_secret(secret),
+		&Validation::default(),
+	)
+	.map(|data| data.claims)
+}
+
+/// Random opaque refresh token, returned to the client once.
+pub fn generate_refresh_token() -> String {
+	let mut bytes = [0u8; 32];
+	rand::thread_rng().fill_bytes(&mut bytes);
+
+	hex::encode(bytes)
+}
+
+/// Refresh tokens are high-entropy, so a plain SHA-256 is enough to store them.
+pub fn hash_refresh_token(token: &str) -> String {
+	hex::encode(Sha256::digest(token.as_bytes()))
+}
@@ -2,3 +2,4 @@ pub mod dto;
 pub mod error;
 pub mod jwt;
 pub mod service;
+pub mod validation;
@@ -1,14 +1,30 @@
 use std::sync::Arc;
 
 use bcrypt::{hash, verify};
+use chrono::{Duration, Utc};
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use diesel::{Connection, PgConnection};
+use lib_core::config::core_config;
 use lib_core::model::base::BasicDbOps;
+use rand::Rng;
+use uuid::Uuid;
 
-use crate::auth::dto::{LoginRequest, RegisterRequest};
+use crate::auth::dto::{
+	AccountDto, AuthTokens, ChangePasswordRequest, LoginRequest, LogoutRequest,
+	RefreshRequest, RegisterRequest, UpgradeGuestRequest,
+};
 use crate::auth::error::{AuthError, Result};
-use crate::auth::jwt::create_jwt;
+use crate::auth::jwt::{
+	create_jwt, decode_jwt, generate_refresh_token, hash_refresh_token,
+};
+use crate::auth::validation::{validate_password, validate_username};
+use crate::tokens::model::{NewRefreshToken, RefreshToken, RevokedToken};
 use crate::users::model::{NewUser, User};
 use lib_core::model::ModelManager;
 
+/// Attempts at picking a free generated guest name.
+const GUEST_NAME_ATTEMPTS: usize = 5;
+
 #[derive(Clone)]
 pub struct AuthService {
 	pub mm: Arc<ModelManager>,
@@ -16,56 +32,303 @@ pub struct AuthService {
 }
 
 impl AuthService {
-	pub fn register(&self, input: RegisterRequest) -> Result<(String, String)> {
-		let mut db = self.mm.db();
+	pub async fn register(&self, input: RegisterRequest) -> Result<AuthTokens> {
+		let jwt_secret = self.jwt_secret.clone();
 
-		// Check if username already exists
-		let existing = User::find_by_username(&mut db, &input.username)
-			.map_err(|e| AuthError::DbError(e.to_string()))?;
+		validate_username(&input.username)?;
+		validate_password(&input.password)?;
 
-		if existing.is_some() {
-			return Err(AuthError::UserExists);
-		}
+		// Db access and hashing both block, keep them off the async workers
+		self.mm
+			.run_blocking(move |db| {
+				// Check if username already exists, ignoring case
+				let existing = User::find_by_username(db, &input.username)
+					.map_err(|e| AuthError::DbError(e.to_string()))?;
 
-		// Hash password
-		let password_hash = hash(&input.password, 10)
-			.map_err(|e| AuthError::HashError(e.to_string()))?;
+				if existing.is_some() {
+					return Err(AuthError::UserExists);
+				}
 
-		let new_user = NewUser {
-			username: &input.username,
-			password_hash: &password_hash,
-		};
+				// Hash password
+				let password_hash = hash(&input.password, 10)
+					.map_err(|e| AuthError::HashError(e.to_string()))?;
 
-		let user = User::create(&mut db, new_user)
-			.map_err(|e| AuthError::DbError(e.to_string()))?;
+				let new_user = NewUser {
+					username: &input.username,
+					password_hash: Some(&password_hash),
+					is_guest: false,
+				};
 
-		// Generate token
-		let token = create_jwt(user.id, self.jwt_secret.as_bytes())
-			.map_err(|e| AuthError::JwtError(e.to_string()))?;
+				// The lower(username) index still catches concurrent registrations
+				let user = User::create(db, new_user)
+					.map_err(unique_violation_as_user_exists)?;
 
-		Ok((token, user.id.to_string()))
+				// Generate tokens
+				issue_tokens(db, user.id, &jwt_secret)
+			})
+			.await
 	}
 
-	pub fn login(&self, input: LoginRequest) -> Result<(String, String)> {
-		// Find user by username
-		let mut db = self.mm.db();
+	pub async fn login(&self, input: LoginRequest) -> Result<AuthTokens> {
+		let jwt_secret = self.jwt_secret.clone();
 
-		let user = User::find_by_username(&mut db, &input.username)
-			.map_err(|e| AuthError::DbError(e.to_string()))?
-			.ok_or(AuthError::UserNotFound)?;
+		self.mm
+			.run_blocking(move |db| {
+				// Unknown user and wrong password look the same to the client
+				let user = User::find_by_username(db, &input.username)
+					.map_err(|e| AuthError::DbError(e.to_string()))?
+					.ok_or(AuthError::InvalidCredentials)?;
 
-		// Verify password
-		let verified = verify(&input.password, &user.password_hash)
-			.map_err(|e| AuthError::VerifyError(e.to_string()))?;
+				// Guests have no password to log in with
+				let password_hash = user
+					.password_hash
+					.as_deref()
+					.ok_or(AuthError::InvalidCredentials)?;
 
-		if !verified {
-			return Err(AuthError::InvalidToken);
-		}
+				// Verify password
+				let verified = verify(&input.password, password_hash)
+					.map_err(|e| AuthError::VerifyError(e.to_string()))?;
+
+				if !verified {
+					return Err(AuthError::InvalidCredentials);
+				}
+
+				// Generate tokens
+				issue_tokens(db, user.id, &jwt_secret)
+			})
+			.await
+	}
+
+	/// Creates a guest user with a generated name and no password.
+	/// Guests get regular tokens, they only lack a way to log in again.
+	pub async fn guest(&self) -> Result<AuthTokens> {
+		let jwt_secret = self.jwt_secret.clone();
+
+		self.mm
+			.run_blocking(move |db| {
+				let mut attempts = 0;
+
+				let user = loop {
+					attempts += 1;
+					let username =
+						format!("guest-{:08x}", rand::thread_rng().r#gen::<u32>());
+
+					let new_user = NewUser {
+						username: &username,
+						password_hash: None,
+						is_guest: true,
+					};
+
+					match User::create(db, new_user)
+						.map_err(unique_violation_as_user_exists)
+					{
+						Err(AuthError::UserExists)
+							if attempts < GUEST_NAME_ATTEMPTS =>
+						{
+							continue;
+						}
+						res => break res?,
+					}
+				};
+
+				issue_tokens(db, user.id, &jwt_secret)
+			})
+			.await
+	}
+
+	/// Gives a guest a username and password. The user id is kept,
+	/// so players, messages and stories stay attached to the account.
+	pub async fn upgrade_guest(
+		&self,
+		user_id: Uuid,
+		input: UpgradeGuestRequest,
+	) -> Result<AccountDto> {
+		validate_username(&input.username)?;
+		validate_password(&input.password)?;
+
+		self.mm
+			.run_blocking(move |db| {
+				let user = User::get(db, user_id)?;
+				if !user.is_guest {
+					return Err(AuthError::NotGuest);
+				}
+
+				let existing = User::find_by_username(db, &input.username)?;
+				if existing.is_some_and(|other| other.id != user_id) {
+					return Err(AuthError::UserExists);
+				}
+
+				let password_hash = hash(&input.password, 10)
+					.map_err(|e| AuthError::HashError(e.to_string()))?;
+
+				let user = User::upgrade_guest(
+					db,
+					user_id,
+					&input.username,
+					&password_hash,
+				)
+				.map_err(|e| match e {
+					DieselError::NotFound => AuthError::NotGuest,
+					e => unique_violation_as_user_exists(e),
+				})?;
+
+				Ok(AccountDto {
+					user_id: user.id,
+					username: user.username,
+					is_guest: user.is_guest,
+				})
+			})
+			.await
+	}
+
+	/// Replaces the password after checking the old one.
+	/// Refresh tokens of the user are revoked, so other devices must log in again.
+	pub async fn change_password(
+		&self,
+		user_id: Uuid,
+		input: ChangePasswordRequest,
+	) -> Result<()> {
+		validate_password(&input.new_password)?;
+
+		self.mm
+			.run_blocking(move |db| {
+				let user = User::get(db, user_id)
+					.map_err(|e| AuthError::DbError(e.to_string()))?;
+
+				let old_hash = user
+					.password_hash
+					.as_deref()
+					.ok_or(AuthError::GuestAccount)?;
+
+				let verified = verify(&input.old_password, old_hash)
+					.map_err(|e| AuthError::VerifyError(e.to_string()))?;
 
-		// Generate token
-		let token = create_jwt(user.id, self.jwt_secret.as_bytes())
-			.map_err(|e| AuthError::JwtError(e.to_string()))?;
+				if !verified {
+					return Err(AuthError::WrongPassword);
+				}
 
-		Ok((token, user.id.to_string()))
+				let password_hash = hash(&input.new_password, 10)
+					.map_err(|e| AuthError::HashError(e.to_string()))?;
+
+				db.transaction::<_, AuthError, _>(|db| {
+					User::update_password_hash(db, user_id, &password_hash)?;
+					RefreshToken::revoke_all_for_user(db, user_id)?;
+
+					Ok(())
+				})
+			})
+			.await
+	}
+
+	/// Exchanges a refresh token for a new token pair.
+	/// The presented refresh token is rotated: it can only be used once.
+	pub async fn refresh(&self, input: RefreshRequest) -> Result<AuthTokens> {
+		let jwt_secret = self.jwt_secret.clone();
+		let token_hash = hash_refresh_token(&input.refresh_token);
+
+		self.mm
+			.run_blocking(move |db| {
+				let stored = RefreshToken::find_by_hash(db, &token_hash)?
+					.ok_or(AuthError::InvalidToken)?;
+
+				// A rotated token coming back means it leaked,
+				// so cut every session of that user
+				if stored.revoked_at.is_some() {
+					RefreshToken::revoke_all_for_user(db, stored.user_id)?;
+					return Err(AuthError::InvalidToken);
+				}
+
+				if stored.expires_at < Utc::now().naive_utc() {
+					return Err(AuthError::InvalidToken);
+				}
+
+				db.transaction::<_, AuthError, _>(|db| {
+					if !RefreshToken::revoke(db, stored.id)? {
+						return Err(AuthError::InvalidToken);
+					}
+
+					issue_tokens(db, stored.user_id, &jwt_secret)
+				})
+			})
+			.await
+	}
+
+	/// Revokes the access token (by `jti`) and, if given,
+	/// the refresh token of the same user.
+	pub async fn logout(
+		&self,
+		access_token: &str,
+		input: LogoutRequest,
+	) -> Result<()> {
+		let claims = decode_jwt(access_token, self.jwt_secret.as_bytes())
+			.map_err(|_| AuthError::InvalidToken)?;
+		let refresh_hash = input.refresh_token.as_deref().map(hash_refresh_token);
+
+		self.mm
+			.run_blocking(move |db| {
+				db.transaction::<_, AuthError, _>(|db| {
+					RevokedToken::revoke(
+						db,
+						claims.jti,
+						claims.sub,
+						claims.expires_at(),
+					)?;
+
+					if let Some(refresh_hash) = refresh_hash
+						&& let Some(stored) =
+							RefreshToken::find_by_hash(db, &refresh_hash)?
+						&& stored.user_id == claims.sub
+					{
+						RefreshToken::revoke(db, stored.id)?;
+					}
+
+					Ok(())
+				})
+			})
+			.await
+	}
+}
+
+fn unique_violation_as_user_exists(e: DieselError) -> AuthError {
+	match e {
+		DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+			AuthError::UserExists
+		}
+		e => AuthError::DbError(e.to_string()),
 	}
 }
+
+/// Creates an access token and stores the hash of a fresh refresh token.
+fn issue_tokens(
+	db: &mut PgConnection,
+	user_id: Uuid,
+	jwt_secret: &str,
+) -> Result<AuthTokens> {
+	let config = core_config();
+
+	let token = create_jwt(
+		user_id,
+		jwt_secret.as_bytes(),
+		Duration::minutes(config.JWT_TTL_MINUTES),
+	)
+	.map_err(|e| AuthError::JwtError(e.to_string()))?;
+
+	let refresh_token = generate_refresh_token();
+	let token_hash = hash_refresh_token(&refresh_token);
+
+	RefreshToken::create(
+		db,
+		NewRefreshToken {
+			user_id,
+			token_hash: &token_hash,
+			expires_at: Utc::now().naive_utc()
+				+ Duration::days(config.REFRESH_TOKEN_TTL_DAYS),
+		},
+	)?;
+
+	Ok(AuthTokens {
+		token,
+		refresh_token,
+		user_id,
+	})
+}
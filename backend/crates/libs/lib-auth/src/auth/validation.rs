@@ -0,0 +1,101 @@
+use lib_core::config::core_config;
+
+use crate::auth::error::{AuthError, Result};
+
+/// Checks length and charset of a username against the account policy.
+pub fn validate_username(username: &str) -> Result<()> {
+	UsernamePolicy::from_config().check(username)
+}
+
+/// Checks minimum length and character class mix of a password.
+pub fn validate_password(password: &str) -> Result<()> {
+	PasswordPolicy::from_config().check(password)
+}
+
+/// Length bounds and charset of usernames.
+#[derive(Debug, Clone)]
+pub struct UsernamePolicy {
+	pub min_len: usize,
+	pub max_len: usize,
+	/// Characters allowed besides ASCII letters and digits.
+	pub extra_chars: String,
+}
+
+impl UsernamePolicy {
+	pub fn from_config() -> Self {
+		let config = core_config();
+		Self {
+			min_len: config.USERNAME_MIN_LEN,
+			max_len: config.USERNAME_MAX_LEN,
+			extra_chars: config.USERNAME_EXTRA_CHARS.clone(),
+		}
+	}
+
+	pub fn check(&self, username: &str) -> Result<()> {
+		let len = username.chars().count();
+
+		if len < self.min_len || len > self.max_len {
+			return Err(AuthError::InvalidUsername(format!(
+				"Username must be between {} and {} characters",
+				self.min_len, self.max_len
+			)));
+		}
+
+		let allowed =
+			|c: char| c.is_ascii_alphanumeric() || self.extra_chars.contains(c);
+		if !username.chars().all(allowed) {
+			return Err(AuthError::InvalidUsername(format!(
+				"Username may only contain letters, digits and '{}'",
+				self.extra_chars
+			)));
+		}
+
+		Ok(())
+	}
+}
+
+/// Minimum length and character class mix of passwords.
+#[derive(Debug, Clone)]
+pub struct PasswordPolicy {
+	pub min_len: usize,
+	/// How many of lowercase, uppercase, digit and symbol to mix.
+	pub min_char_classes: usize,
+}
+
+impl PasswordPolicy {
+	pub fn from_config() -> Self {
+		let config = core_config();
+		Self {
+			min_len: config.PASSWORD_MIN_LEN,
+			min_char_classes: config.PASSWORD_MIN_CHAR_CLASSES,
+		}
+	}
+
+	pub fn check(&self, password: &str) -> Result<()> {
+		if password.chars().count() < self.min_len {
+			return Err(AuthError::WeakPassword(format!(
+				"Password must be at least {} characters",
+				self.min_len
+			)));
+		}
+
+		let classes = [
+			password.chars().any(|c| c.is_lowercase()),
+			password.chars().any(|c| c.is_uppercase()),
+			password.chars().any(|c| c.is_ascii_digit()),
+			password.chars().any(|c| !c.is_alphanumeric()),
+		]
+		.into_iter()
+		.filter(|present| *present)
+		.count();
+
+		if classes < self.min_char_classes {
+			return Err(AuthError::WeakPassword(format!(
+				"Password must mix at least {} of: lowercase, uppercase, digits, symbols",
+				self.min_char_classes
+			)));
+		}
+
+		Ok(())
+	}
+}
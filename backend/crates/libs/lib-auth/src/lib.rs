@@ -1,3 +1,4 @@
 pub mod auth;
 pub mod router;
+pub mod tokens;
 pub mod users;
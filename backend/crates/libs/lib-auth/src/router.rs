@@ -1,14 +1,25 @@
 // src/auth/router.rs
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use axum::{
-	Extension, Json, Router, http::StatusCode, response::IntoResponse, routing::post,
+	Extension, Json, Router,
+	http::{HeaderMap, StatusCode},
+	response::IntoResponse,
+	routing::post,
+};
+use lib_core::{config::core_config, ctx::Ctx, model::ModelManager};
+use lib_rate_limit::{
+	client_ip::ClientIp,
+	layer::{RateLimitKey, RateLimitLayer},
+	limiter::RateLimiter,
+	throttle::LoginThrottle,
 };
-use lib_core::model::ModelManager;
-use serde_json::json;
 
 use crate::auth::{
-	dto::{LoginRequest, RegisterRequest},
+	dto::{
+		ChangePasswordRequest, LoginRequest, LogoutRequest, RefreshRequest,
+		RegisterRequest, UpgradeGuestRequest,
+	},
 	error::AuthError,
 	service::AuthService,
 };
@@ -19,9 +30,36 @@ pub fn auth_router(mm: Arc<ModelManager>, jwt_secret: String) -> Router {
 		jwt_secret: jwt_secret.clone(),
 	};
 
+	let config = core_config();
+	let login_throttle = Arc::new(LoginThrottle::new(
+		config.LOGIN_FREE_ATTEMPTS,
+		Duration::from_secs(config.LOGIN_LOCKOUT_BASE_SECS),
+		Duration::from_secs(config.LOGIN_LOCKOUT_MAX_SECS),
+		Duration::from_secs(config.LOGIN_FAILURE_WINDOW_SECS),
+	));
+	let ip_limit = RateLimitLayer::new(
+		RateLimiter::per_minute(config.RATE_LIMIT_AUTH_PER_MINUTE),
+		RateLimitKey::ClientIp,
+	);
+
 	Router::new()
 		.route("/register", post(register_handler))
 		.route("/login", post(login_handler))
+		.route("/guest", post(guest_handler))
+		.route("/refresh", post(refresh_handler))
+		.route("/logout", post(logout_handler))
+		.route_layer(ip_limit)
+		.layer(Extension(auth_service))
+		.layer(Extension(login_throttle))
+}
+
+/// Routes acting on the logged in user, mount behind the auth middleware.
+pub fn account_router(mm: Arc<ModelManager>, jwt_secret: String) -> Router {
+	let auth_service = AuthService { mm, jwt_secret };
+
+	Router::new()
+		.route("/account/password", post(change_password_handler))
+		.route("/account/upgrade", post(upgrade_guest_handler))
 		.layer(Extension(auth_service))
 }
 
@@ -29,24 +67,88 @@ async fn register_handler(
 	Extension(auth_service): Extension<AuthService>,
 	Json(payload): Json<RegisterRequest>,
 ) -> Result<impl IntoResponse, AuthError> {
-	let (token, user_id) = auth_service.register(payload)?;
+	let tokens = auth_service.register(payload).await?;
 
-	Ok((
-		StatusCode::OK,
-		Json(json!({ "token": token, "user_id": user_id })),
-	)
-		.into_response())
+	Ok((StatusCode::OK, Json(tokens)).into_response())
 }
 
 async fn login_handler(
 	Extension(auth_service): Extension<AuthService>,
+	Extension(login_throttle): Extension<Arc<LoginThrottle>>,
+	client_ip: ClientIp,
 	Json(payload): Json<LoginRequest>,
 ) -> Result<impl IntoResponse, AuthError> {
-	let (token, user_id) = auth_service.login(payload)?;
+	// Usernames are keyed per client, so failures from elsewhere can't lock
+	// an account out
+	let ip_key = client_ip.key();
+	let user_key = format!("{ip_key}:user:{}", payload.username.to_lowercase());
+	let keys = [ip_key.as_str(), user_key.as_str()];
+
+	login_throttle.check(&keys)?;
+
+	match auth_service.login(payload).await {
+		Ok(tokens) => {
+			login_throttle.record_success(&[user_key.as_str()]);
+			Ok((StatusCode::OK, Json(tokens)).into_response())
+		}
+		Err(AuthError::InvalidCredentials) => {
+			login_throttle.record_failure(&keys);
+			Err(AuthError::InvalidCredentials)
+		}
+		Err(err) => Err(err),
+	}
+}
+
+async fn guest_handler(
+	Extension(auth_service): Extension<AuthService>,
+) -> Result<impl IntoResponse, AuthError> {
+	let tokens = auth_service.guest().await?;
+
+	Ok((StatusCode::OK, Json(tokens)).into_response())
+}
+
+async fn refresh_handler(
+	Extension(auth_service): Extension<AuthService>,
+	Json(payload): Json<RefreshRequest>,
+) -> Result<impl IntoResponse, AuthError> {
+	let tokens = auth_service.refresh(payload).await?;
+
+	Ok((StatusCode::OK, Json(tokens)).into_response())
+}
+
+async fn logout_handler(
+	Extension(auth_service): Extension<AuthService>,
+	headers: HeaderMap,
+	payload: Option<Json<LogoutRequest>>,
+) -> Result<impl IntoResponse, AuthError> {
+	let access_token = headers
+		.get("authorization")
+		.and_then(|hv| hv.to_str().ok())
+		.and_then(|s| s.strip_prefix("Bearer "))
+		.ok_or(AuthError::InvalidToken)?;
+
+	let Json(payload) = payload.unwrap_or_default();
+	auth_service.logout(access_token, payload).await?;
+
+	Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+async fn change_password_handler(
+	Extension(auth_service): Extension<AuthService>,
+	ctx: Ctx,
+	Json(payload): Json<ChangePasswordRequest>,
+) -> Result<impl IntoResponse, AuthError> {
+	auth_service.change_password(ctx.user_id(), payload).await?;
+
+	Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+async fn upgrade_guest_handler(
+	Extension(auth_service): Extension<AuthService>,
+	ctx: Ctx,
+	Json(payload): Json<UpgradeGuestRequest>,
+) -> Result<impl IntoResponse, AuthError> {
+	let account = auth_service.upgrade_guest(ctx.user_id(), payload).await?;
 
-	Ok((
-		StatusCode::OK,
-		Json(json!({ "token": token, "user_id": user_id })),
-	)
-		.into_response())
+	Ok((StatusCode::OK, Json(account)).into_response())
 }
@@ -0,0 +1,90 @@
+use chrono::{NaiveDateTime, Utc};
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use lib_core::model::schema::{refresh_tokens, revoked_tokens};
+use uuid::Uuid;
+
+use crate::tokens::model::{NewRefreshToken, RefreshToken, RevokedToken};
+
+impl RefreshToken {
+	pub fn create(
+		conn: &mut PgConnection,
+		item: NewRefreshToken,
+	) -> QueryResult<Self> {
+		diesel::insert_into(refresh_tokens::table)
+			.values(item)
+			.get_result(conn)
+	}
+
+	pub fn find_by_hash(
+		conn: &mut PgConnection,
+		token_hash: &str,
+	) -> QueryResult<Option<Self>> {
+		refresh_tokens::table
+			.filter(refresh_tokens::token_hash.eq(token_hash))
+			.first(conn)
+			.optional()
+	}
+
+	/// Marks the token as revoked, returns false if it already was.
+	pub fn revoke(conn: &mut PgConnection, id: Uuid) -> QueryResult<bool> {
+		let updated = diesel::update(
+			refresh_tokens::table
+				.find(id)
+				.filter(refresh_tokens::revoked_at.is_null()),
+		)
+		.set(refresh_tokens::revoked_at.eq(Utc::now().naive_utc()))
+		.execute(conn)?;
+
+		Ok(updated == 1)
+	}
+
+	/// Revokes every active refresh token of the user.
+	pub fn revoke_all_for_user(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+	) -> QueryResult<usize> {
+		diesel::update(
+			refresh_tokens::table
+				.filter(refresh_tokens::user_id.eq(user_id))
+				.filter(refresh_tokens::revoked_at.is_null()),
+		)
+		.set(refresh_tokens::revoked_at.eq(Utc::now().naive_utc()))
+		.execute(conn)
+	}
+}
+
+impl RevokedToken {
+	/// Adds the `jti` to the revocation list and drops expired entries,
+	/// which can no longer pass validation anyway.
+	pub fn revoke(
+		conn: &mut PgConnection,
+		jti: Uuid,
+		user_id: Uuid,
+		expires_at: NaiveDateTime,
+	) -> QueryResult<()> {
+		let now = Utc::now().naive_utc();
+
+		diesel::delete(
+			revoked_tokens::table.filter(revoked_tokens::expires_at.lt(now)),
+		)
+		.execute(conn)?;
+
+		diesel::insert_into(revoked_tokens::table)
+			.values(RevokedToken {
+				jti,
+				user_id,
+				expires_at,
+				revoked_at: now,
+			})
+			.on_conflict_do_nothing()
+			.execute(conn)?;
+
+		Ok(())
+	}
+
+	pub fn is_revoked(conn: &mut PgConnection, jti: Uuid) -> QueryResult<bool> {
+		diesel::select(diesel::dsl::exists(revoked_tokens::table.find(jti)))
+			.get_result(conn)
+	}
+}
@@ -0,0 +1,35 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::{Insertable, Queryable};
+use lib_core::model::schema::{refresh_tokens, revoked_tokens};
+use uuid::Uuid;
+
+/// Long-lived token exchanged for new access tokens.
+/// Only the SHA-256 hash of the token is stored.
+#[derive(Debug, Queryable)]
+#[diesel(table_name = refresh_tokens)]
+pub struct RefreshToken {
+	pub id: Uuid,
+	pub user_id: Uuid,
+	pub token_hash: String,
+	pub expires_at: NaiveDateTime,
+	pub revoked_at: Option<NaiveDateTime>,
+	pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = refresh_tokens)]
+pub struct NewRefreshToken<'a> {
+	pub user_id: Uuid,
+	pub token_hash: &'a str,
+	pub expires_at: NaiveDateTime,
+}
+
+/// Access token revoked before its expiry, looked up by `jti`.
+#[derive(Debug, Queryable, Insertable)]
+#[diesel(table_name = revoked_tokens)]
+pub struct RevokedToken {
+	pub jti: Uuid,
+	pub user_id: Uuid,
+	pub expires_at: NaiveDateTime,
+	pub revoked_at: NaiveDateTime,
+}
@@ -5,6 +5,8 @@ use lib_core::model::base::BasicDbOps;
 use lib_core::model::schema::users;
 use uuid::Uuid;
 
+define_sql_function!(fn lower(x: diesel::sql_types::Text) -> diesel::sql_types::Text);
+
 use crate::users::model::NewUser;
 use crate::users::model::User;
 
@@ -49,13 +51,40 @@ impl BasicDbOps for User {
 }
 
 impl User {
+	/// Case-insensitive lookup, usernames are unique regardless of case.
 	pub fn find_by_username(
 		db: &mut PgConnection,
 		username: &str,
 	) -> Result<Option<Self>, diesel::result::Error> {
 		users::table
-			.filter(users::username.eq(username))
+			.filter(lower(users::username).eq(username.to_lowercase()))
 			.first::<Self>(db)
 			.optional()
 	}
+
+	/// Turns a guest into a full account, the id (and history) stays the same.
+	pub fn upgrade_guest(
+		db: &mut PgConnection,
+		id: Uuid,
+		username: &str,
+		password_hash: &str,
+	) -> QueryResult<Self> {
+		diesel::update(users::table.find(id).filter(users::is_guest.eq(true)))
+			.set((
+				users::username.eq(username),
+				users::password_hash.eq(password_hash),
+				users::is_guest.eq(false),
+			))
+			.get_result(db)
+	}
+
+	pub fn update_password_hash(
+		db: &mut PgConnection,
+		id: Uuid,
+		password_hash: &str,
+	) -> QueryResult<usize> {
+		diesel::update(users::table.find(id))
+			.set(users::password_hash.eq(password_hash))
+			.execute(db)
+	}
 }
@@ -8,13 +8,16 @@ use uuid::Uuid;
 pub struct User {
 	pub id: Uuid,
 	pub username: String,
-	pub password_hash: String,
+	/// `None` for guests.
+	pub password_hash: Option<String>,
 	pub created_at: NaiveDateTime,
+	pub is_guest: bool,
 }
 
 #[derive(Debug, Insertable, Clone)]
 #[diesel(table_name = users)]
 pub struct NewUser<'a> {
 	pub username: &'a str,
-	pub password_hash: &'a str,
+	pub password_hash: Option<&'a str>,
+	pub is_guest: bool,
 }
@@ -8,5 +8,8 @@ use serde_with::serde_as;
 pub enum ClientError {
 	INTERNAL_SERVER_ERROR,
 	AUTHENTICATION_FAILED(String),
+	VALIDATION_FAILED(String),
+	FORBIDDEN(String),
+	TOO_MANY_REQUESTS(String),
 	GAME_ERROR(String),
 }
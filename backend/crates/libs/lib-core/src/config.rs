@@ -1,4 +1,4 @@
-use lib_utils::envs::get_env;
+use lib_utils::envs::{get_env, get_env_opt, get_env_parse_opt, get_env_parse_or};
 use std::sync::OnceLock;
 
 pub fn core_config() -> &'static CoreConfig {
@@ -16,9 +16,52 @@ pub struct CoreConfig {
 	// -- Db
 	pub DB_URL_BASE: String,
 	pub DB_URL: String,
+	pub DB_READ_URL: Option<String>,
+	pub DB_POOL_MAX_SIZE: u32,
+	pub DB_POOL_MIN_IDLE: Option<u32>,
+	pub DB_CONNECTION_TIMEOUT_SECS: u64,
+	pub DB_STATEMENT_TIMEOUT_MS: Option<u64>,
+	pub RUN_MIGRATIONS: bool,
 
 	// -- JWT
 	pub JWT_SECRET: String,
+	pub JWT_TTL_MINUTES: i64,
+	pub REFRESH_TOKEN_TTL_DAYS: i64,
+
+	// -- Account policy
+	pub USERNAME_MIN_LEN: usize,
+	pub USERNAME_MAX_LEN: usize,
+	/// Characters allowed in usernames besides ASCII letters and digits.
+	pub USERNAME_EXTRA_CHARS: String,
+	pub PASSWORD_MIN_LEN: usize,
+	/// How many of lowercase, uppercase, digit and symbol a password must mix.
+	pub PASSWORD_MIN_CHAR_CLASSES: usize,
+
+	// -- Profiles
+	/// Directory uploaded avatars are stored in.
+	pub AVATAR_DIR: String,
+	pub AVATAR_MAX_BYTES: usize,
+
+	// -- Game
+	/// How long players may vote on story variants before the tally.
+	pub VOTING_TIMEOUT_SECS: u64,
+
+	// -- Sharing
+	/// Base of public links handed out to users, e.g. share links.
+	pub PUBLIC_BASE_URL: String,
+
+	// -- Rate limiting
+	/// Failed logins per ip / username before lockouts start.
+	pub LOGIN_FREE_ATTEMPTS: u32,
+	/// First lockout, doubled on every further failure.
+	pub LOGIN_LOCKOUT_BASE_SECS: u64,
+	pub LOGIN_LOCKOUT_MAX_SECS: u64,
+	/// Failures older than this are forgotten.
+	pub LOGIN_FAILURE_WINDOW_SECS: u64,
+	pub RATE_LIMIT_AUTH_PER_MINUTE: u32,
+	pub RATE_LIMIT_MUTATIONS_PER_MINUTE: u32,
+	/// Take the client ip from `X-Forwarded-For`, only behind a trusted proxy.
+	pub RATE_LIMIT_TRUST_PROXY: bool,
 
 	// -- AI
 	pub GCP_APPLICATION_CREDENTIALS: String,
@@ -31,7 +74,42 @@ impl CoreConfig {
 	fn load_from_env() -> lib_utils::envs::Result<CoreConfig> {
 		let db_url_base = get_env("DATABASE_URL_BASE")?;
 		let db_url = get_env("DATABASE_URL")?;
+		let db_read_url = get_env_opt("DATABASE_READ_URL");
+		let db_pool_max_size = get_env_parse_or("DB_POOL_MAX_SIZE", 10)?;
+		let db_pool_min_idle = get_env_parse_opt("DB_POOL_MIN_IDLE")?;
+		let db_connection_timeout_secs =
+			get_env_parse_or("DB_CONNECTION_TIMEOUT_SECS", 30)?;
+		let db_statement_timeout_ms = get_env_parse_opt("DB_STATEMENT_TIMEOUT_MS")?;
+		let run_migrations = get_env_parse_or("RUN_MIGRATIONS", true)?;
+		let username_min_len = get_env_parse_or("USERNAME_MIN_LEN", 3)?;
+		let username_max_len = get_env_parse_or("USERNAME_MAX_LEN", 32)?;
+		let username_extra_chars =
+			get_env_opt("USERNAME_EXTRA_CHARS").unwrap_or_else(|| "_-.".to_string());
+		let password_min_len = get_env_parse_or("PASSWORD_MIN_LEN", 8)?;
+		let password_min_char_classes =
+			get_env_parse_or("PASSWORD_MIN_CHAR_CLASSES", 2)?;
+		let avatar_dir = get_env_opt("AVATAR_DIR")
+			.unwrap_or_else(|| "./data/avatars".to_string());
+		let avatar_max_bytes = get_env_parse_or("AVATAR_MAX_BYTES", 1024 * 1024)?;
+		let voting_timeout_secs = get_env_parse_or("VOTING_TIMEOUT_SECS", 120)?;
+		let public_base_url = get_env_opt("PUBLIC_BASE_URL")
+			.unwrap_or_else(|| "http://localhost:3000".to_string());
+		let login_free_attempts = get_env_parse_or("LOGIN_FREE_ATTEMPTS", 5)?;
+		let login_lockout_base_secs =
+			get_env_parse_or("LOGIN_LOCKOUT_BASE_SECS", 30)?;
+		let login_lockout_max_secs =
+			get_env_parse_or("LOGIN_LOCKOUT_MAX_SECS", 3600)?;
+		let login_failure_window_secs =
+			get_env_parse_or("LOGIN_FAILURE_WINDOW_SECS", 900)?;
+		let rate_limit_auth_per_minute =
+			get_env_parse_or("RATE_LIMIT_AUTH_PER_MINUTE", 30)?;
+		let rate_limit_mutations_per_minute =
+			get_env_parse_or("RATE_LIMIT_MUTATIONS_PER_MINUTE", 120)?;
+		let rate_limit_trust_proxy =
+			get_env_parse_or("RATE_LIMIT_TRUST_PROXY", false)?;
 		let jwt_secret = get_env("JWT_SECRET")?;
+		let jwt_ttl_minutes = get_env_parse_or("JWT_TTL_MINUTES", 60)?;
+		let refresh_token_ttl_days = get_env_parse_or("REFRESH_TOKEN_TTL_DAYS", 30)?;
 		let gcp_application_credentials = get_env("GCP_APPLICATION_CREDENTIALS")?;
 		let gcp_project_id = get_env("GCP_PROJECT_ID")?;
 		let gcp_location = get_env("GCP_LOCATION")?;
@@ -41,8 +119,37 @@ impl CoreConfig {
 			// -- Db
 			DB_URL_BASE: db_url_base.clone(),
 			DB_URL: db_url.clone(),
+			DB_READ_URL: db_read_url,
+			DB_POOL_MAX_SIZE: db_pool_max_size,
+			DB_POOL_MIN_IDLE: db_pool_min_idle,
+			DB_CONNECTION_TIMEOUT_SECS: db_connection_timeout_secs,
+			DB_STATEMENT_TIMEOUT_MS: db_statement_timeout_ms,
+			RUN_MIGRATIONS: run_migrations,
 			// -- JWT
 			JWT_SECRET: jwt_secret.clone(),
+			JWT_TTL_MINUTES: jwt_ttl_minutes,
+			REFRESH_TOKEN_TTL_DAYS: refresh_token_ttl_days,
+			// -- Account policy
+			USERNAME_MIN_LEN: username_min_len,
+			USERNAME_MAX_LEN: username_max_len,
+			USERNAME_EXTRA_CHARS: username_extra_chars,
+			PASSWORD_MIN_LEN: password_min_len,
+			PASSWORD_MIN_CHAR_CLASSES: password_min_char_classes,
+			// -- Profiles
+			AVATAR_DIR: avatar_dir,
+			AVATAR_MAX_BYTES: avatar_max_bytes,
+			// -- Game
+			VOTING_TIMEOUT_SECS: voting_timeout_secs,
+			// -- Sharing
+			PUBLIC_BASE_URL: public_base_url.trim_end_matches('/').to_string(),
+			// -- Rate limiting
+			LOGIN_FREE_ATTEMPTS: login_free_attempts,
+			LOGIN_LOCKOUT_BASE_SECS: login_lockout_base_secs,
+			LOGIN_LOCKOUT_MAX_SECS: login_lockout_max_secs,
+			LOGIN_FAILURE_WINDOW_SECS: login_failure_window_secs,
+			RATE_LIMIT_AUTH_PER_MINUTE: rate_limit_auth_per_minute,
+			RATE_LIMIT_MUTATIONS_PER_MINUTE: rate_limit_mutations_per_minute,
+			RATE_LIMIT_TRUST_PROXY: rate_limit_trust_proxy,
 			// -- AI
 			GCP_APPLICATION_CREDENTIALS: gcp_application_credentials.clone(),
 			GCP_PROJECT_ID: gcp_project_id.clone(),
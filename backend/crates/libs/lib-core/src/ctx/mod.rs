@@ -31,7 +31,7 @@ impl Ctx {
 
 impl Ctx {
 	pub fn user_id(&self) -> Uuid {
-		self.user_id.clone()
+		self.user_id
 	}
 
 	pub fn username(&self) -> String {
@@ -66,6 +66,14 @@ pub enum CtxExtError {
 	DbError(String),
 	#[error("Token validation failed")]
 	FailValidate,
+	#[error("Token has been revoked")]
+	TokenRevoked,
+}
+
+impl From<crate::model::error::Error> for CtxExtError {
+	fn from(ex: crate::model::error::Error) -> Self {
+		CtxExtError::DbError(ex.to_string())
+	}
 }
 
 impl CtxExtError {
@@ -83,6 +91,12 @@ impl CtxExtError {
 					"Token not found in request or invalid".to_string(),
 				),
 			),
+			CtxExtError::TokenRevoked => (
+				StatusCode::UNAUTHORIZED,
+				ClientError::AUTHENTICATION_FAILED(
+					"Token has been revoked".to_string(),
+				),
+			),
 			CtxExtError::CtxCreateFail(_) => (
 				StatusCode::INTERNAL_SERVER_ERROR,
 				ClientError::INTERNAL_SERVER_ERROR,
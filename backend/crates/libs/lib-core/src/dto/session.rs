@@ -5,6 +5,19 @@ use uuid::Uuid;
 #[derive(Queryable, Debug, Serialize, Deserialize, Clone)]
 pub struct UserInSessionDto {
 	pub user_id: Uuid,
+	pub display_name: String,
 	pub is_ready: bool,
 	pub is_host: bool,
+	pub seat: i32,
+	pub is_bot: bool,
+}
+
+/// A player's secret objective once the judge looked at the story.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ObjectiveScoreDto {
+	pub user_id: Uuid,
+	pub display_name: String,
+	pub objective: String,
+	pub met: bool,
+	pub points: i32,
 }
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::model::schema_enums::ReactionKind;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReactionCountDto {
+	pub kind: ReactionKind,
+	pub count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StoryVariantDto {
+	pub variant_id: Uuid,
+	pub variant_index: i32,
+	pub style: String,
+	pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VariantVotesDto {
+	pub variant_id: Uuid,
+	pub votes: i64,
+}
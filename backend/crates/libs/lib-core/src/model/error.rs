@@ -1,16 +1,19 @@
 use crate::model::store;
-use derive_more::From;
 use serde::Serialize;
 use serde_with::{DisplayFromStr, serde_as};
 
 pub type Result<T> = core::result::Result<T, Error>;
 
 #[serde_as]
-#[derive(Debug, Serialize, From)]
+#[derive(Debug, Serialize, thiserror::Error)]
 pub enum Error {
-	#[from]
-	Store(store::Error),
+	#[error(transparent)]
+	Store(#[from] store::Error),
 
-	#[from]
-	Diesel(#[serde_as(as = "DisplayFromStr")] diesel::result::Error),
+	#[error("Database error: {0}")]
+	Diesel(
+		#[from]
+		#[serde_as(as = "DisplayFromStr")]
+		diesel::result::Error,
+	),
 }
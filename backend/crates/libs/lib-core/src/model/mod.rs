@@ -5,26 +5,114 @@ pub mod schema_enums;
 
 pub mod store;
 
-use self::error::Result;
+use diesel::PgConnection;
+use serde::Serialize;
+use tracing::Span;
+
+use self::error::{Error, Result};
 use crate::model::store::{
-	DbPool, DbPooledConn, drop_test_db, new_db_pool, new_test_db_pool,
+	DbPool, DbPooledConn, PoolStats, drop_test_db, new_db_pool, new_read_db_pool,
+	new_test_db_pool,
 };
 
 #[derive(Clone)]
 pub struct ModelManager {
 	db_pool: DbPool,
+	/// Optional read replica used by read-only endpoints.
+	read_db_pool: Option<DbPool>,
+}
+
+/// Connection usage of the primary pool and of the replica, if any.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelManagerStats {
+	pub primary: PoolStats,
+	pub replica: Option<PoolStats>,
 }
 
 impl ModelManager {
 	/// Creates a ModelManager with the production database
 	pub async fn new() -> Result<Self> {
 		let pool = new_db_pool().await?;
-		Ok(ModelManager { db_pool: pool })
+		let read_pool = new_read_db_pool().await?;
+		Ok(ModelManager {
+			db_pool: pool,
+			read_db_pool: read_pool,
+		})
 	}
 
-	/// Gets a pooled connection from the database
-	pub fn db(&self) -> DbPooledConn {
-		self.db_pool.get().expect("Failed to get pooled connection")
+	/// Gets a pooled connection from the database.
+	/// Fails once the pool's connection timeout elapses instead of panicking.
+	pub fn db(&self) -> Result<DbPooledConn> {
+		self.db_pool
+			.get()
+			.map_err(|ex| store::Error::FailToGetConnection(ex.to_string()).into())
+	}
+
+	/// Gets a pooled connection from the read replica,
+	/// falling back to the primary when no replica is configured.
+	pub fn db_read(&self) -> Result<DbPooledConn> {
+		self.read_db_pool
+			.as_ref()
+			.unwrap_or(&self.db_pool)
+			.get()
+			.map_err(|ex| store::Error::FailToGetConnection(ex.to_string()).into())
+	}
+
+	/// Runs blocking Diesel work on tokio's blocking thread pool,
+	/// so async handlers never block a runtime worker thread.
+	pub async fn run_blocking<F, T, E>(&self, f: F) -> core::result::Result<T, E>
+	where
+		F: FnOnce(&mut PgConnection) -> core::result::Result<T, E> + Send + 'static,
+		T: Send + 'static,
+		E: From<Error> + Send + 'static,
+	{
+		self.spawn_db_task(ModelManager::db, f).await
+	}
+
+	/// Same as [`ModelManager::run_blocking`], but on a read replica connection.
+	/// Only for read-only work that tolerates replication lag.
+	pub async fn run_blocking_read<F, T, E>(
+		&self,
+		f: F,
+	) -> core::result::Result<T, E>
+	where
+		F: FnOnce(&mut PgConnection) -> core::result::Result<T, E> + Send + 'static,
+		T: Send + 'static,
+		E: From<Error> + Send + 'static,
+	{
+		self.spawn_db_task(ModelManager::db_read, f).await
+	}
+
+	async fn spawn_db_task<F, T, E>(
+		&self,
+		get_conn: fn(&ModelManager) -> Result<DbPooledConn>,
+		f: F,
+	) -> core::result::Result<T, E>
+	where
+		F: FnOnce(&mut PgConnection) -> core::result::Result<T, E> + Send + 'static,
+		T: Send + 'static,
+		E: From<Error> + Send + 'static,
+	{
+		let mm = self.clone();
+		let span = Span::current();
+
+		tokio::task::spawn_blocking(move || {
+			let _entered = span.enter();
+			let mut conn = get_conn(&mm)?;
+			f(&mut conn)
+		})
+		.await
+		.map_err(|ex| {
+			Error::from(store::Error::BlockingTaskFailed(ex.to_string()))
+		})?
+	}
+
+	/// Current connection usage of the pools.
+	pub fn stats(&self) -> ModelManagerStats {
+		ModelManagerStats {
+			primary: PoolStats::of(&self.db_pool),
+			replica: self.read_db_pool.as_ref().map(PoolStats::of),
+		}
 	}
 }
 
@@ -44,6 +132,14 @@ impl TestModelManager {
 		})
 	}
 
+	/// A ModelManager on the test database, for code that takes one
+	pub fn model_manager(&self) -> ModelManager {
+		ModelManager {
+			db_pool: self.db_pool.clone(),
+			read_db_pool: None,
+		}
+	}
+
 	/// Gets a pooled connection from the test database
 	pub fn db(&self) -> DbPooledConn {
 		self.db_pool.get().expect("Failed to get pooled connection")
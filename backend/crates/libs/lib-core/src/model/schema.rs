@@ -1,20 +1,69 @@
 // @generated automatically by Diesel CLI.
 
 pub mod sql_types {
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "bot_difficulty"))]
+    pub struct BotDifficulty;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "narrator_mode"))]
+    pub struct NarratorMode;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "play_mode"))]
+    pub struct PlayMode;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "reaction_kind"))]
+    pub struct ReactionKind;
+
     #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "session_status"))]
     pub struct SessionStatus;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "turn_order_mode"))]
+    pub struct TurnOrderMode;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "visibility_mode"))]
+    pub struct VisibilityMode;
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::BotDifficulty;
+
+    bots (user_id) {
+        user_id -> Uuid,
+        persona -> Text,
+        difficulty -> BotDifficulty,
+        created_at -> Timestamp,
+    }
 }
 
 diesel::table! {
     messages (id) {
         id -> Uuid,
         session_id -> Uuid,
-        user_id -> Uuid,
+        user_id -> Nullable<Uuid>,
         content -> Text,
         round -> Int4,
         turn_order -> Int4,
         created_at -> Timestamp,
+        thread_id -> Nullable<Uuid>,
+        used_suggestion -> Bool,
+    }
+}
+
+diesel::table! {
+    player_objectives (session_id, user_id) {
+        session_id -> Uuid,
+        user_id -> Uuid,
+        objective -> Text,
+        met -> Nullable<Bool>,
+        points -> Int4,
+        created_at -> Timestamp,
     }
 }
 
@@ -25,12 +74,47 @@ diesel::table! {
         joined_at -> Timestamp,
         is_ready -> Bool,
         is_host -> Bool,
+        seat -> Int4,
+    }
+}
+
+diesel::table! {
+    profiles (user_id) {
+        user_id -> Uuid,
+        display_name -> Nullable<Text>,
+        avatar_url -> Nullable<Text>,
+        bio -> Nullable<Text>,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    refresh_tokens (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        token_hash -> Text,
+        expires_at -> Timestamp,
+        revoked_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    revoked_tokens (jti) {
+        jti -> Uuid,
+        user_id -> Uuid,
+        expires_at -> Timestamp,
+        revoked_at -> Timestamp,
     }
 }
 
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::SessionStatus;
+    use super::sql_types::VisibilityMode;
+    use super::sql_types::PlayMode;
+    use super::sql_types::TurnOrderMode;
+    use super::sql_types::NarratorMode;
 
     sessions (id) {
         id -> Uuid,
@@ -40,6 +124,20 @@ diesel::table! {
         max_rounds -> Int4,
         current_round -> Int4,
         created_at -> Timestamp,
+        version -> Int8,
+        story_variants -> Int4,
+        visibility -> VisibilityMode,
+        visibility_words -> Int4,
+        play_mode -> PlayMode,
+        turn_order -> TurnOrderMode,
+        bot_backfill -> Bool,
+        narrator -> NarratorMode,
+        objectives -> Bool,
+        undo_window_secs -> Int4,
+        pending_message_id -> Nullable<Uuid>,
+        voting_ends_at -> Nullable<Timestamp>,
+        narration_ends_at -> Nullable<Timestamp>,
+        pending_until -> Nullable<Timestamp>,
     }
 }
 
@@ -49,6 +147,91 @@ diesel::table! {
         session_id -> Uuid,
         content -> Text,
         created_at -> Timestamp,
+        is_public -> Bool,
+        thread_id -> Nullable<Uuid>,
+    }
+}
+
+diesel::table! {
+    story_ratings (story_id, user_id) {
+        story_id -> Uuid,
+        user_id -> Uuid,
+        stars -> Int2,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::ReactionKind;
+
+    story_reactions (story_id, user_id, kind) {
+        story_id -> Uuid,
+        user_id -> Uuid,
+        kind -> ReactionKind,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    story_shares (id) {
+        id -> Uuid,
+        story_id -> Uuid,
+        created_by -> Uuid,
+        token -> Text,
+        expires_at -> Nullable<Timestamp>,
+        revoked_at -> Nullable<Timestamp>,
+        view_count -> Int8,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    story_variant_votes (session_id, user_id) {
+        session_id -> Uuid,
+        user_id -> Uuid,
+        variant_id -> Uuid,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    story_variants (id) {
+        id -> Uuid,
+        session_id -> Uuid,
+        variant_index -> Int4,
+        style -> Text,
+        temperature -> Float4,
+        content -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    suggestions (id) {
+        id -> Uuid,
+        session_id -> Uuid,
+        user_id -> Uuid,
+        round -> Int4,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    threads (id) {
+        id -> Uuid,
+        session_id -> Uuid,
+        thread_index -> Int4,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    user_achievements (user_id, achievement_id) {
+        user_id -> Uuid,
+        achievement_id -> Text,
+        unlocked_at -> Timestamp,
     }
 }
 
@@ -56,21 +239,57 @@ diesel::table! {
     users (id) {
         id -> Uuid,
         username -> Text,
-        password_hash -> Text,
+        password_hash -> Nullable<Text>,
         created_at -> Timestamp,
+        is_guest -> Bool,
     }
 }
 
+diesel::joinable!(bots -> users (user_id));
 diesel::joinable!(messages -> sessions (session_id));
+diesel::joinable!(messages -> threads (thread_id));
 diesel::joinable!(messages -> users (user_id));
+diesel::joinable!(player_objectives -> sessions (session_id));
+diesel::joinable!(player_objectives -> users (user_id));
 diesel::joinable!(players -> sessions (session_id));
 diesel::joinable!(players -> users (user_id));
+diesel::joinable!(profiles -> users (user_id));
+diesel::joinable!(refresh_tokens -> users (user_id));
+diesel::joinable!(revoked_tokens -> users (user_id));
 diesel::joinable!(stories -> sessions (session_id));
+diesel::joinable!(stories -> threads (thread_id));
+diesel::joinable!(story_ratings -> stories (story_id));
+diesel::joinable!(story_ratings -> users (user_id));
+diesel::joinable!(story_reactions -> stories (story_id));
+diesel::joinable!(story_reactions -> users (user_id));
+diesel::joinable!(story_shares -> stories (story_id));
+diesel::joinable!(story_shares -> users (created_by));
+diesel::joinable!(story_variant_votes -> sessions (session_id));
+diesel::joinable!(story_variant_votes -> story_variants (variant_id));
+diesel::joinable!(story_variant_votes -> users (user_id));
+diesel::joinable!(story_variants -> sessions (session_id));
+diesel::joinable!(suggestions -> sessions (session_id));
+diesel::joinable!(suggestions -> users (user_id));
+diesel::joinable!(threads -> sessions (session_id));
+diesel::joinable!(user_achievements -> users (user_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    bots,
     messages,
+    player_objectives,
     players,
+    profiles,
+    refresh_tokens,
+    revoked_tokens,
     sessions,
     stories,
+    story_ratings,
+    story_reactions,
+    story_shares,
+    story_variant_votes,
+    story_variants,
+    suggestions,
+    threads,
+    user_achievements,
     users,
 );
@@ -1,5 +1,5 @@
 use diesel_derive_enum::DbEnum;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, DbEnum, Clone, PartialEq, Serialize)]
 #[ExistingTypePath = "crate::model::schema::sql_types::SessionStatus"]
@@ -10,6 +10,147 @@ pub enum SessionStatus {
 	Started,
 	#[db_rename = "waiting_for_story_generation"]
 	WaitingForStoryGeneration,
+	#[db_rename = "voting"]
+	Voting,
 	#[db_rename = "finished"]
 	Finished,
 }
+
+impl SessionStatus {
+	pub const ALL: [SessionStatus; 5] = [
+		SessionStatus::Waiting,
+		SessionStatus::Started,
+		SessionStatus::WaitingForStoryGeneration,
+		SessionStatus::Voting,
+		SessionStatus::Finished,
+	];
+
+	/// Database name of the status, also used as a metrics label.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			SessionStatus::Waiting => "waiting",
+			SessionStatus::Started => "started",
+			SessionStatus::WaitingForStoryGeneration => {
+				"waiting_for_story_generation"
+			}
+			SessionStatus::Voting => "voting",
+			SessionStatus::Finished => "finished",
+		}
+	}
+}
+
+#[derive(
+	Debug, DbEnum, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize,
+)]
+#[ExistingTypePath = "crate::model::schema::sql_types::ReactionKind"]
+#[serde(rename_all = "snake_case")]
+pub enum ReactionKind {
+	#[db_rename = "like"]
+	Like,
+	#[db_rename = "love"]
+	Love,
+	#[db_rename = "laugh"]
+	Laugh,
+	#[db_rename = "wow"]
+	Wow,
+	#[db_rename = "sad"]
+	Sad,
+}
+
+/// How much of the story so far the player on turn gets to see.
+#[derive(
+	Debug, DbEnum, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize,
+)]
+#[ExistingTypePath = "crate::model::schema::sql_types::VisibilityMode"]
+#[serde(rename_all = "snake_case")]
+pub enum VisibilityMode {
+	/// The previous player's full message.
+	#[default]
+	#[db_rename = "previous"]
+	Previous,
+	/// The last `visibility_words` words of the previous message.
+	#[db_rename = "last_words"]
+	LastWords,
+	#[db_rename = "last_sentence"]
+	LastSentence,
+	/// Every message so far.
+	#[db_rename = "open"]
+	Open,
+	/// Nothing at all.
+	#[db_rename = "blind"]
+	Blind,
+}
+
+#[derive(
+	Debug, DbEnum, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize,
+)]
+#[ExistingTypePath = "crate::model::schema::sql_types::PlayMode"]
+#[serde(rename_all = "snake_case")]
+pub enum PlayMode {
+	/// One chain, players take turns.
+	#[default]
+	#[db_rename = "linear"]
+	Linear,
+	/// One thread per player, everyone writes each round and the threads
+	/// rotate between players.
+	#[db_rename = "rotating"]
+	Rotating,
+}
+
+/// Who writes first and next, players write in seat order.
+#[derive(
+	Debug, DbEnum, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize,
+)]
+#[ExistingTypePath = "crate::model::schema::sql_types::TurnOrderMode"]
+#[serde(rename_all = "snake_case")]
+pub enum TurnOrderMode {
+	/// Seats follow the order players joined in.
+	#[default]
+	#[db_rename = "join_order"]
+	JoinOrder,
+	/// Seats are shuffled when the game starts.
+	#[db_rename = "shuffled"]
+	Shuffled,
+	/// Each round starts one seat further.
+	#[db_rename = "rotating_starter"]
+	RotatingStarter,
+	/// The host arranges the seats in the lobby.
+	#[db_rename = "manual"]
+	Manual,
+}
+
+/// How well an AI bot player writes.
+#[derive(
+	Debug, DbEnum, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize,
+)]
+#[ExistingTypePath = "crate::model::schema::sql_types::BotDifficulty"]
+#[serde(rename_all = "snake_case")]
+pub enum BotDifficulty {
+	/// Short and simple lines.
+	#[db_rename = "easy"]
+	Easy,
+	#[default]
+	#[db_rename = "normal"]
+	Normal,
+	/// Longer lines that build on earlier details.
+	#[db_rename = "hard"]
+	Hard,
+}
+
+/// Whether an AI narrator bridges the rounds of a linear session.
+#[derive(
+	Debug, DbEnum, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize,
+)]
+#[ExistingTypePath = "crate::model::schema::sql_types::NarratorMode"]
+#[serde(rename_all = "snake_case")]
+pub enum NarratorMode {
+	#[default]
+	#[db_rename = "off"]
+	Off,
+	/// A short paragraph leading from one round into the next.
+	#[db_rename = "bridge"]
+	Bridge,
+	/// Like `Bridge`, each paragraph also adds a plot twist.
+	#[db_rename = "twists"]
+	Twists,
+}
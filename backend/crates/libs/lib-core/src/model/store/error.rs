@@ -2,9 +2,16 @@ use serde::Serialize;
 
 pub type Result<T> = core::result::Result<T, Error>;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, thiserror::Error)]
 pub enum Error {
+	#[error("Failed to create pool: {0}")]
 	FailToCreatePool(String),
+	#[error("Failed to connect: {0}")]
 	FailToConnect(String),
+	#[error("Migration error: {0}")]
 	MigrationError(String),
+	#[error("Failed to get pooled connection: {0}")]
+	FailToGetConnection(String),
+	#[error("Blocking db task failed: {0}")]
+	BlockingTaskFailed(String),
 }
@@ -0,0 +1,89 @@
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+/// Everything an export needs, independent of the database models.
+#[derive(Debug, Clone)]
+pub struct StoryDocument {
+	pub story_id: Uuid,
+	pub theme: String,
+	/// Display names, in order of first contribution.
+	pub contributors: Vec<String>,
+	pub content: String,
+	pub created_at: NaiveDateTime,
+}
+
+impl StoryDocument {
+	/// Theme as a single line, usable as a title.
+	pub fn title(&self) -> String {
+		let title = self.theme.split_whitespace().collect::<Vec<_>>().join(" ");
+		if title.is_empty() {
+			"Untitled story".to_string()
+		} else {
+			title
+		}
+	}
+
+	/// "Ann", "Ann and Bob", "Ann, Bob and Cid".
+	pub fn byline(&self) -> String {
+		match self.contributors.as_slice() {
+			[] => String::new(),
+			[only] => only.clone(),
+			[rest @ .., last] => format!("{} and {last}", rest.join(", ")),
+		}
+	}
+
+	/// Story text split into paragraphs on blank lines.
+	pub fn paragraphs(&self) -> Vec<String> {
+		self.content
+			.split("\n\n")
+			.map(|paragraph| {
+				paragraph
+					.lines()
+					.map(str::trim)
+					.collect::<Vec<_>>()
+					.join(" ")
+			})
+			.filter(|paragraph| !paragraph.is_empty())
+			.collect()
+	}
+
+	/// `Content-Disposition` for downloads: an ASCII file name,
+	/// plus the title as an RFC 5987 UTF-8 file name for clients that support it.
+	pub fn content_disposition(&self, extension: &str) -> String {
+		let mut encoded = String::new();
+		for byte in format!("{}.{extension}", self.title()).bytes() {
+			if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+				encoded.push(byte as char);
+			} else {
+				encoded.push_str(&format!("%{byte:02X}"));
+			}
+		}
+
+		format!(
+			"attachment; filename=\"{}\"; filename*=UTF-8''{encoded}",
+			self.file_name(extension)
+		)
+	}
+
+	/// ASCII file name for downloads, e.g. `the-lost-key.pdf`.
+	pub fn file_name(&self, extension: &str) -> String {
+		let mut slug = String::new();
+		for ch in self.theme.chars() {
+			if ch.is_ascii_alphanumeric() {
+				slug.push(ch.to_ascii_lowercase());
+			} else if !slug.is_empty() && !slug.ends_with('-') {
+				slug.push('-');
+			}
+			if slug.len() >= 60 {
+				break;
+			}
+		}
+		let slug = slug.trim_end_matches('-');
+
+		if slug.is_empty() {
+			format!("story.{extension}")
+		} else {
+			format!("{slug}.{extension}")
+		}
+	}
+}
@@ -0,0 +1,92 @@
+use std::io::{Cursor, Write};
+
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+	document::StoryDocument,
+	error::Result,
+	html::{body, escape},
+};
+
+const CONTAINER_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>
+"#;
+
+/// Minimal EPUB 3: one chapter plus the navigation document.
+pub fn render(document: &StoryDocument) -> Result<Vec<u8>> {
+	let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+	let deflated =
+		SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+	// The mimetype entry must come first and be stored uncompressed
+	zip.start_file(
+		"mimetype",
+		SimpleFileOptions::default().compression_method(CompressionMethod::Stored),
+	)?;
+	zip.write_all(b"application/epub+zip")?;
+
+	zip.start_file("META-INF/container.xml", deflated)?;
+	zip.write_all(CONTAINER_XML.as_bytes())?;
+
+	zip.start_file("OEBPS/content.opf", deflated)?;
+	zip.write_all(package(document).as_bytes())?;
+
+	zip.start_file("OEBPS/nav.xhtml", deflated)?;
+	zip.write_all(xhtml("Contents", &nav(document)).as_bytes())?;
+
+	zip.start_file("OEBPS/story.xhtml", deflated)?;
+	zip.write_all(xhtml(&document.title(), &body(document)).as_bytes())?;
+
+	Ok(zip.finish()?.into_inner())
+}
+
+fn package(document: &StoryDocument) -> String {
+	let creators: String = document
+		.contributors
+		.iter()
+		.map(|name| format!("    <dc:creator>{}</dc:creator>\n", escape(name)))
+		.collect();
+
+	format!(
+		r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="story-id">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <dc:identifier id="story-id">urn:uuid:{id}</dc:identifier>
+    <dc:title>{title}</dc:title>
+    <dc:language>en</dc:language>
+{creators}    <meta property="dcterms:modified">{modified}</meta>
+  </metadata>
+  <manifest>
+    <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+    <item id="story" href="story.xhtml" media-type="application/xhtml+xml"/>
+  </manifest>
+  <spine>
+    <itemref idref="story"/>
+  </spine>
+</package>
+"#,
+		id = document.story_id,
+		title = escape(&document.title()),
+		modified = document.created_at.format("%Y-%m-%dT%H:%M:%SZ"),
+	)
+}
+
+fn nav(document: &StoryDocument) -> String {
+	format!(
+		"<nav epub:type=\"toc\">\n<ol>\n<li><a href=\"story.xhtml\">{}</a></li>\n</ol>\n</nav>\n",
+		escape(&document.title())
+	)
+}
+
+fn xhtml(title: &str, body: &str) -> String {
+	format!(
+		"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!DOCTYPE html>\n\
+		 <html xmlns=\"http://www.w3.org/1999/xhtml\" xmlns:epub=\"http://www.idpf.org/2007/ops\">\n\
+		 <head><title>{}</title></head>\n<body>\n{body}</body>\n</html>\n",
+		escape(title)
+	)
+}
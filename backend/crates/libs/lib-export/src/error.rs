@@ -0,0 +1,18 @@
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Error)]
+pub enum Error {
+	#[error("Unknown export format: {0}")]
+	UnknownFormat(String),
+
+	#[error("Zip error: {0}")]
+	Zip(#[from] zip::result::ZipError),
+
+	#[error("Io error: {0}")]
+	Io(#[from] std::io::Error),
+
+	#[error("Pdf error: {0}")]
+	Pdf(#[from] printpdf::Error),
+}
@@ -0,0 +1,45 @@
+use std::str::FromStr;
+
+use crate::error::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+	Markdown,
+	Html,
+	Epub,
+	Pdf,
+}
+
+impl ExportFormat {
+	pub fn content_type(&self) -> &'static str {
+		match self {
+			ExportFormat::Markdown => "text/markdown; charset=utf-8",
+			ExportFormat::Html => "text/html; charset=utf-8",
+			ExportFormat::Epub => "application/epub+zip",
+			ExportFormat::Pdf => "application/pdf",
+		}
+	}
+
+	pub fn extension(&self) -> &'static str {
+		match self {
+			ExportFormat::Markdown => "md",
+			ExportFormat::Html => "html",
+			ExportFormat::Epub => "epub",
+			ExportFormat::Pdf => "pdf",
+		}
+	}
+}
+
+impl FromStr for ExportFormat {
+	type Err = Error;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		match value.to_ascii_lowercase().as_str() {
+			"md" | "markdown" => Ok(ExportFormat::Markdown),
+			"html" => Ok(ExportFormat::Html),
+			"epub" => Ok(ExportFormat::Epub),
+			"pdf" => Ok(ExportFormat::Pdf),
+			other => Err(Error::UnknownFormat(other.to_string())),
+		}
+	}
+}
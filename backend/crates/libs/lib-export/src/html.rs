@@ -0,0 +1,57 @@
+use crate::document::StoryDocument;
+
+const STYLE: &str = "body{max-width:40em;margin:2em auto;padding:0 1em;\
+font-family:Georgia,serif;line-height:1.6;color:#222}\
+h1{margin-bottom:0.2em}.byline,.date{color:#666;font-style:italic;margin:0}";
+
+pub fn render(document: &StoryDocument) -> String {
+	render_with_head(document, "")
+}
+
+/// Standalone page with extra `<head>` markup, e.g. Open Graph meta tags.
+/// `head` is inserted as is, so it must already be escaped.
+pub fn render_with_head(document: &StoryDocument, head: &str) -> String {
+	let title = escape(&document.title());
+
+	format!(
+		"<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+		 <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+		 <title>{title}</title>\n{head}<style>{STYLE}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+		body(document)
+	)
+}
+
+/// Title, byline and paragraphs; shared with the EPUB chapter.
+pub(crate) fn body(document: &StoryDocument) -> String {
+	let mut out = format!("<h1>{}</h1>\n", escape(&document.title()));
+
+	let byline = document.byline();
+	if !byline.is_empty() {
+		out.push_str(&format!("<p class=\"byline\">By {}</p>\n", escape(&byline)));
+	}
+	out.push_str(&format!(
+		"<p class=\"date\">{}</p>\n<hr/>\n",
+		document.created_at.format("%Y-%m-%d")
+	));
+
+	for paragraph in document.paragraphs() {
+		out.push_str(&format!("<p>{}</p>\n", escape(&paragraph)));
+	}
+
+	out
+}
+
+pub fn escape(text: &str) -> String {
+	let mut out = String::with_capacity(text.len());
+	for ch in text.chars() {
+		match ch {
+			'&' => out.push_str("&amp;"),
+			'<' => out.push_str("&lt;"),
+			'>' => out.push_str("&gt;"),
+			'"' => out.push_str("&quot;"),
+			'\'' => out.push_str("&#39;"),
+			_ => out.push(ch),
+		}
+	}
+	out
+}
@@ -0,0 +1,19 @@
+pub mod document;
+mod epub;
+pub mod error;
+pub mod format;
+pub mod html;
+mod markdown;
+mod pdf;
+
+use crate::{document::StoryDocument, error::Result, format::ExportFormat};
+
+/// Renders the story into the bytes of a file in the given format.
+pub fn render(document: &StoryDocument, format: ExportFormat) -> Result<Vec<u8>> {
+	match format {
+		ExportFormat::Markdown => Ok(markdown::render(document).into_bytes()),
+		ExportFormat::Html => Ok(html::render(document).into_bytes()),
+		ExportFormat::Epub => epub::render(document),
+		ExportFormat::Pdf => pdf::render(document),
+	}
+}
@@ -0,0 +1,33 @@
+use crate::document::StoryDocument;
+
+pub fn render(document: &StoryDocument) -> String {
+	let mut out = format!("# {}\n\n", escape(&document.title()));
+
+	let byline = document.byline();
+	if !byline.is_empty() {
+		out.push_str(&format!("*By {}*\n\n", escape(&byline)));
+	}
+	out.push_str(&format!(
+		"*{}*\n\n---\n\n",
+		document.created_at.format("%Y-%m-%d")
+	));
+
+	for paragraph in document.paragraphs() {
+		out.push_str(&paragraph);
+		out.push_str("\n\n");
+	}
+
+	out
+}
+
+/// Escapes the characters that would change the meaning of a heading or byline.
+fn escape(text: &str) -> String {
+	let mut out = String::with_capacity(text.len());
+	for ch in text.chars() {
+		if matches!(ch, '\\' | '*' | '_' | '`' | '#' | '[' | ']' | '<' | '>') {
+			out.push('\\');
+		}
+		out.push(ch);
+	}
+	out
+}
@@ -0,0 +1,117 @@
+use printpdf::{BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfLayerReference};
+
+use crate::{document::StoryDocument, error::Result};
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 20.0;
+const TITLE_SIZE: f32 = 20.0;
+const TEXT_SIZE: f32 = 11.0;
+const LINE_HEIGHT: f32 = 6.0;
+/// Rough character capacity of a line of body text in Times at 11pt.
+const CHARS_PER_LINE: usize = 95;
+const TITLE_CHARS_PER_LINE: usize = 50;
+
+/// A4 pages in the standard PDF fonts, so no font files are needed.
+/// Those fonts only cover Windows-1252; other characters are dropped.
+pub fn render(document: &StoryDocument) -> Result<Vec<u8>> {
+	let title = document.title();
+	let (doc, page, layer) =
+		PdfDocument::new(&title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+	let regular = doc.add_builtin_font(BuiltinFont::TimesRoman)?;
+	let bold = doc.add_builtin_font(BuiltinFont::TimesBold)?;
+	let italic = doc.add_builtin_font(BuiltinFont::TimesItalic)?;
+
+	let mut writer = PageWriter {
+		layer: doc.get_page(page).get_layer(layer),
+		y: PAGE_HEIGHT - MARGIN,
+	};
+	let mut new_page = || {
+		let (page, layer) = doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+		doc.get_page(page).get_layer(layer)
+	};
+
+	for line in wrap(&title, TITLE_CHARS_PER_LINE) {
+		writer.line(&line, TITLE_SIZE, &bold, LINE_HEIGHT * 1.8, &mut new_page);
+	}
+	writer.skip(LINE_HEIGHT / 2.0);
+
+	let byline = document.byline();
+	if !byline.is_empty() {
+		for line in wrap(&format!("By {byline}"), CHARS_PER_LINE) {
+			writer.line(&line, TEXT_SIZE, &italic, LINE_HEIGHT, &mut new_page);
+		}
+	}
+	let date = document.created_at.format("%Y-%m-%d").to_string();
+	writer.line(&date, TEXT_SIZE, &italic, LINE_HEIGHT, &mut new_page);
+	writer.skip(LINE_HEIGHT);
+
+	for paragraph in document.paragraphs() {
+		for line in wrap(&paragraph, CHARS_PER_LINE) {
+			writer.line(&line, TEXT_SIZE, &regular, LINE_HEIGHT, &mut new_page);
+		}
+		writer.skip(LINE_HEIGHT / 2.0);
+	}
+
+	Ok(doc.save_to_bytes()?)
+}
+
+struct PageWriter {
+	layer: PdfLayerReference,
+	/// Baseline of the next line, from the bottom of the page.
+	y: f32,
+}
+
+impl PageWriter {
+	fn line(
+		&mut self,
+		text: &str,
+		size: f32,
+		font: &IndirectFontRef,
+		height: f32,
+		new_page: &mut impl FnMut() -> PdfLayerReference,
+	) {
+		if self.y - height < MARGIN {
+			self.layer = new_page();
+			self.y = PAGE_HEIGHT - MARGIN;
+		}
+		self.y -= height;
+		self.layer
+			.use_text(text, size, Mm(MARGIN), Mm(self.y), font);
+	}
+
+	fn skip(&mut self, height: f32) {
+		self.y -= height;
+	}
+}
+
+/// Greedy word wrap by character count; overlong words are split.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+	let mut lines = Vec::new();
+	let mut line = String::new();
+
+	for word in text.split_whitespace() {
+		let mut word: Vec<char> = word.chars().collect();
+		while word.len() > width {
+			if !line.is_empty() {
+				lines.push(std::mem::take(&mut line));
+			}
+			lines.push(word.drain(..width).collect());
+		}
+		let word: String = word.into_iter().collect();
+
+		let line_len = line.chars().count();
+		if line_len > 0 && line_len + 1 + word.chars().count() > width {
+			lines.push(std::mem::take(&mut line));
+		}
+		if !line.is_empty() {
+			line.push(' ');
+		}
+		line.push_str(&word);
+	}
+	if !line.is_empty() {
+		lines.push(line);
+	}
+
+	lines
+}
@@ -1,3 +1,7 @@
+use lib_core::dto::{
+	session::ObjectiveScoreDto,
+	story::{StoryVariantDto, VariantVotesDto},
+};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -5,19 +9,135 @@ use uuid::Uuid;
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum GameEvent {
 	GameStarted,
-	NewTurn { user_id: Uuid },
-	PlayerLeft { user_id: Uuid },
+	NewTurn {
+		user_id: Uuid,
+		display_name: String,
+	},
+	PlayerLeft {
+		user_id: Uuid,
+		display_name: String,
+	},
 	GameFinished,
-	PlayerJoined { user_id: Uuid },
-	PlayerReady { user_id: Uuid, ready: bool },
-	LastPlayerMessage { content: String },
-	Error { message: String },
+	PlayerJoined {
+		user_id: Uuid,
+		display_name: String,
+	},
+	PlayerReady {
+		user_id: Uuid,
+		display_name: String,
+		ready: bool,
+	},
+	/// The host's seating, first seat first.
+	SeatsArranged {
+		user_ids: Vec<Uuid>,
+	},
+	LastPlayerMessage {
+		content: String,
+	},
+	/// The player on turn wrote, the turn moves on once the undo window
+	/// closed unless they take the line back.
+	MessagePending {
+		user_id: Uuid,
+		undo_window_secs: i32,
+	},
+	MessageRetracted {
+		user_id: Uuid,
+	},
+	PlayerPassed {
+		user_id: Uuid,
+		display_name: String,
+	},
+	/// Sent to the player on turn who asked to be inspired.
+	SuggestionChunk {
+		seq: u64,
+		chunk: String,
+	},
+	SuggestionsComplete {
+		suggestions: Vec<String>,
+	},
+	/// Sent privately to each player when a competitive game starts.
+	SecretObjective {
+		objective: String,
+	},
+	/// The judged objectives once the story is told, best score first.
+	Scoreboard {
+		scores: Vec<ObjectiveScoreDto>,
+	},
+	/// Sent privately to the player who unlocked it.
+	AchievementUnlocked {
+		achievement_id: String,
+		title: String,
+		description: String,
+	},
+	Error {
+		message: String,
+	},
 	SessionDeleted,
 
+	// Rotating sessions
+	/// Everyone writes this round, each player gets a `ThreadTurn`.
+	RoundStarted {
+		round: i32,
+	},
+	/// Sent to one player: the thread they write on and what they may see.
+	ThreadTurn {
+		thread_id: Uuid,
+		thread_index: i32,
+		content: String,
+	},
+	ThreadSubmitted {
+		user_id: Uuid,
+		display_name: String,
+	},
+
 	// Events that depend on ai
 	WaitingForStoryGeneration,
-	StoryChunk { seq: u64, chunk: String },
-	StoryComplete { story_id: Uuid, full_text: String },
+	StoryChunk {
+		seq: u64,
+		chunk: String,
+	},
+	StoryComplete {
+		story_id: Uuid,
+		full_text: String,
+	},
+	/// Chunk of a rotating session's thread, threads are told one by one.
+	ThreadStoryChunk {
+		thread_id: Uuid,
+		seq: u64,
+		chunk: String,
+	},
+	ThreadStoryComplete {
+		thread_id: Uuid,
+		story_id: Uuid,
+		full_text: String,
+	},
+	/// Chunk of the narrator's interlude after `round`, the next player's
+	/// turn follows once it's complete.
+	NarratorChunk {
+		round: i32,
+		seq: u64,
+		chunk: String,
+	},
+	NarratorComplete {
+		round: i32,
+		content: String,
+	},
+	/// Chunk of one of several variants, generated one after another.
+	VariantChunk {
+		variant_index: i32,
+		seq: u64,
+		chunk: String,
+	},
+	VotingStarted {
+		variants: Vec<StoryVariantDto>,
+		timeout_secs: u64,
+	},
+	/// The winner becomes the session's story, `StoryComplete` follows.
+	VotingResult {
+		winner_variant_id: Uuid,
+		story_id: Uuid,
+		votes: Vec<VariantVotesDto>,
+	},
 }
 
 pub struct GameEventReceiver {
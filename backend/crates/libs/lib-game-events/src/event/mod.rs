@@ -1,2 +1,14 @@
+use serde::{Deserialize, Serialize};
+
 pub mod game;
 pub mod session;
+pub mod story;
+
+/// Event stamped with the session `version` it was produced at,
+/// so clients can tell whether their snapshot is stale.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct VersionedEvent<E> {
+	pub version: i64,
+	#[serde(flatten)]
+	pub event: E,
+}
@@ -0,0 +1,19 @@
+use lib_core::dto::story::ReactionCountDto;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Community activity on finished stories, for feed observers.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StoryEvent {
+	ReactionsUpdated {
+		story_id: Uuid,
+		reactions: Vec<ReactionCountDto>,
+	},
+
+	RatingUpdated {
+		story_id: Uuid,
+		rating_count: i64,
+		rating_avg: Option<f64>,
+	},
+}
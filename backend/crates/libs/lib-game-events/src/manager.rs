@@ -3,17 +3,31 @@ use tokio::sync::broadcast;
 use uuid::Uuid;
 
 use crate::event::{
+	VersionedEvent,
 	game::{GameEvent, GameEventReceiver},
 	session::SessionEvent,
+	story::StoryEvent,
 };
 
+type GameEventSender = broadcast::Sender<VersionedEvent<GameEvent>>;
+type SessionEventSender = broadcast::Sender<VersionedEvent<SessionEvent>>;
+
 #[derive(Clone)]
 pub struct GameEventsManager {
 	/// player_senders: session_id -> (user_id -> sender)
-	player_senders: DashMap<Uuid, DashMap<Uuid, broadcast::Sender<GameEvent>>>,
+	player_senders: DashMap<Uuid, DashMap<Uuid, GameEventSender>>,
 
 	/// session_senders: user_id -> sender for session-level observers
-	session_senders: DashMap<Uuid, broadcast::Sender<SessionEvent>>,
+	session_senders: DashMap<Uuid, SessionEventSender>,
+
+	/// story_sender: shared by all story feed observers
+	story_sender: broadcast::Sender<StoryEvent>,
+}
+
+impl Default for GameEventsManager {
+	fn default() -> Self {
+		Self::new()
+	}
 }
 
 impl GameEventsManager {
@@ -21,6 +35,7 @@ impl GameEventsManager {
 		Self {
 			player_senders: DashMap::new(),
 			session_senders: DashMap::new(),
+			story_sender: broadcast::channel(100).0,
 		}
 	}
 
@@ -33,11 +48,8 @@ impl GameEventsManager {
 		&self,
 		session_id: Uuid,
 		user_id: Uuid,
-	) -> broadcast::Receiver<GameEvent> {
-		let player_map = self
-			.player_senders
-			.entry(session_id)
-			.or_insert_with(DashMap::new);
+	) -> broadcast::Receiver<VersionedEvent<GameEvent>> {
+		let player_map = self.player_senders.entry(session_id).or_default();
 
 		let sender = player_map
 			.entry(user_id)
@@ -49,7 +61,7 @@ impl GameEventsManager {
 	pub fn subscribe_user_to_observe_sessions_list(
 		&self,
 		user_id: Uuid,
-	) -> broadcast::Receiver<SessionEvent> {
+	) -> broadcast::Receiver<VersionedEvent<SessionEvent>> {
 		let sender = self
 			.session_senders
 			.entry(user_id)
@@ -57,19 +69,28 @@ impl GameEventsManager {
 		sender.subscribe()
 	}
 
+	/// Subscribe as story feed observer — receive `StoryEvent`.
+	pub fn subscribe_to_story_events(&self) -> broadcast::Receiver<StoryEvent> {
+		self.story_sender.subscribe()
+	}
+
 	/// Send `GameEvent` either to all players in session, or to a specific player (if receiver is specified).
+	/// `version` is the session version the event was produced at.
 	pub fn send_game_event(
 		&self,
 		session_id: Uuid,
 		game_event_receiver: Option<GameEventReceiver>,
+		version: i64,
 		event: GameEvent,
 	) {
+		let event = VersionedEvent { version, event };
+
 		match game_event_receiver {
 			Some(receiver) => {
-				if let Some(player_map) = self.player_senders.get(&session_id) {
-					if let Some(sender) = player_map.get(&receiver.user_id) {
-						let _ = sender.send(event);
-					}
+				if let Some(player_map) = self.player_senders.get(&session_id)
+					&& let Some(sender) = player_map.get(&receiver.user_id)
+				{
+					let _ = sender.send(event);
 				}
 			}
 			None => {
@@ -84,9 +105,17 @@ impl GameEventsManager {
 	}
 
 	/// Send `SessionEvent` to all session observers.
-	pub fn send_session_event(&self, event: SessionEvent) {
+	/// `version` is the version of the session the event is about.
+	pub fn send_session_event(&self, version: i64, event: SessionEvent) {
+		let event = VersionedEvent { version, event };
+
 		for sender in self.session_senders.iter() {
 			let _ = sender.send(event.clone());
 		}
 	}
+
+	/// Send `StoryEvent` to all story feed observers.
+	pub fn send_story_event(&self, event: StoryEvent) {
+		let _ = self.story_sender.send(event);
+	}
 }
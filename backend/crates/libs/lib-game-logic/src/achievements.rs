@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use diesel::PgConnection;
+use lib_core::model::{ModelManager, base::BasicDbOps};
+use lib_game_events::{
+	event::game::{GameEvent, GameEventReceiver},
+	manager::GameEventsManager,
+};
+use lib_players::{bots::model::Bot, model::Player};
+use lib_profiles::achievements::model::UserAchievement;
+use lib_sessions::model::Session;
+use tracing::{Instrument, Span, warn};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+
+/// Unlocks what the session's players earned and tells each of them.
+/// Achievements never hold up the game, failures are only logged.
+pub(crate) fn check_achievements(
+	conn: &mut PgConnection,
+	events: &GameEventsManager,
+	session_id: Uuid,
+	version: i64,
+) {
+	if let Err(e) = unlock_for_session(conn, events, session_id, version) {
+		warn!("Failed to check achievements: {:?}", e);
+	}
+}
+
+/// `check_achievements` for async tasks, on the blocking thread pool.
+pub(crate) fn spawn_achievements_check(
+	session_id: Uuid,
+	version: i64,
+	model_manager: Arc<ModelManager>,
+	events: Arc<GameEventsManager>,
+) {
+	let span = Span::current();
+
+	tokio::spawn(
+		async move {
+			let checked = model_manager
+				.run_blocking(move |conn| {
+					check_achievements(conn, &events, session_id, version);
+					Ok::<_, Error>(())
+				})
+				.await;
+
+			if let Err(e) = checked {
+				warn!("Failed to check achievements: {:?}", e);
+			}
+		}
+		.instrument(span),
+	);
+}
+
+/// Unlocks what the user earned outside of a game, e.g. by rating
+/// stories, and tells them on the channel of `session_id`.
+/// Failures are only logged, like for `check_achievements`.
+pub(crate) fn check_user_achievements(
+	conn: &mut PgConnection,
+	events: &GameEventsManager,
+	session_id: Uuid,
+	user_id: Uuid,
+) {
+	let unlocked = Session::get(conn, session_id)
+		.map_err(Error::from)
+		.and_then(|session| {
+			unlock_for_user(conn, events, session_id, session.version, user_id)
+		});
+	if let Err(e) = unlocked {
+		warn!("Failed to check achievements: {:?}", e);
+	}
+}
+
+fn unlock_for_session(
+	conn: &mut PgConnection,
+	events: &GameEventsManager,
+	session_id: Uuid,
+	version: i64,
+) -> Result<()> {
+	// Bots don't collect achievements
+	let bots = Bot::ids_in_session(conn, session_id)?;
+
+	for player in Player::list_by_session(conn, session_id)? {
+		if !bots.contains(&player.user_id) {
+			unlock_for_user(conn, events, session_id, version, player.user_id)?;
+		}
+	}
+
+	Ok(())
+}
+
+fn unlock_for_user(
+	conn: &mut PgConnection,
+	events: &GameEventsManager,
+	session_id: Uuid,
+	version: i64,
+	user_id: Uuid,
+) -> Result<()> {
+	for achievement in UserAchievement::unlock_earned(conn, user_id)? {
+		events.send_game_event(
+			session_id,
+			Some(GameEventReceiver { user_id }),
+			version,
+			GameEvent::AchievementUnlocked {
+				achievement_id: achievement.id.to_string(),
+				title: achievement.title.to_string(),
+				description: achievement.description.to_string(),
+			},
+		);
+	}
+
+	Ok(())
+}
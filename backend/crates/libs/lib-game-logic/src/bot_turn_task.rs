@@ -0,0 +1,114 @@
+use lib_ai::{client::AiClient, models::GenerationConfig};
+use lib_core::model::schema_enums::BotDifficulty;
+use lib_players::bots::model::Bot;
+use rand::seq::SliceRandom;
+use tracing::{Instrument, Span, info_span, warn};
+use uuid::Uuid;
+
+use crate::{
+	engine::GameEngine,
+	story_generation_task::{chat_request, stream_chunks},
+};
+
+const BOT_INSTRUCTION: &str = "You are a player in a collaborative storytelling game. \
+	Continue the story with your next line and reply with that line only.";
+
+/// Persona of a bot taking over the seat of a player who left.
+pub(crate) const STAND_IN_PERSONA: &str = "A steady stand-in who keeps the story on course in the spirit of the \
+	 player whose seat they took.";
+
+pub const MAX_BOT_NAME_CHARS: usize = 40;
+pub const MAX_BOT_PERSONA_CHARS: usize = 300;
+
+/// Longest line a bot submits, in characters.
+const MAX_BOT_LINE_CHARS: usize = 500;
+
+/// Written when generation fails, so the game never waits on a bot.
+const FALLBACK_LINES: [&str; 4] = [
+	"For a long moment, nothing happened at all.",
+	"Somewhere nearby, a door creaked open.",
+	"A sudden gust of wind carried the moment away.",
+	"And then, quite unexpectedly, everything changed.",
+];
+
+/// Spawn an async task that writes the bot's line from the `excerpt` it may
+/// see and submits it as the bot's turn.
+/// Runs in a `bot_turn` span, child of the caller's (request) span.
+pub(crate) fn spawn_bot_turn_task(
+	engine: GameEngine,
+	session_id: Uuid,
+	theme: String,
+	bot: Bot,
+	excerpt: String,
+) {
+	let span = info_span!("bot_turn", %session_id, bot_id = %bot.user_id);
+
+	tokio::spawn(
+		async move {
+			let line = write_line(&engine.ai_client, &theme, &bot, &excerpt).await;
+
+			let span = Span::current();
+			let bot_id = bot.user_id;
+			let submitted = tokio::task::spawn_blocking(move || {
+				span.in_scope(|| {
+					engine.submit_message(session_id, bot_id, &line, None)
+				})
+			})
+			.await;
+
+			match submitted {
+				Ok(Ok(())) => {}
+				// The bot left or the game moved on meanwhile
+				Ok(Err(e)) => warn!("Bot turn was not accepted: {:?}", e),
+				Err(e) => warn!("Bot turn task failed: {:?}", e),
+			}
+		}
+		.instrument(span),
+	);
+}
+
+/// Generates the bot's next line, or a fallback line when generation fails.
+async fn write_line(
+	ai_client: &AiClient,
+	theme: &str,
+	bot: &Bot,
+	excerpt: &str,
+) -> String {
+	let (style, temperature) = match bot.difficulty {
+		BotDifficulty::Easy => ("Write one short, simple sentence.", 0.5),
+		BotDifficulty::Normal => ("Write one or two sentences.", 0.8),
+		BotDifficulty::Hard => (
+			"Write two or three vivid sentences that pick up earlier details \
+			 and leave a hook for the next player.",
+			1.0,
+		),
+	};
+	let instruction = format!("{BOT_INSTRUCTION} {style}\nPersona: {}", bot.persona);
+
+	let prompt = if excerpt.trim().is_empty() {
+		format!("Theme: {theme}\nNothing is visible to you, write on.")
+	} else {
+		format!("Theme: {theme}\nWhat you can see of the story:\n{excerpt}")
+	};
+
+	let chat_req =
+		chat_request(instruction, prompt, Some(GenerationConfig { temperature }));
+
+	match stream_chunks(ai_client, chat_req, |_, _| {}).await {
+		Ok(line) if !line.trim().is_empty() => {
+			line.trim().chars().take(MAX_BOT_LINE_CHARS).collect()
+		}
+		other => {
+			if let Err(e) = other {
+				warn!("Bot generation failed: {:?}", e);
+			}
+			metrics::counter!("bot_turn_fallbacks_total").increment(1);
+
+			FALLBACK_LINES
+				.choose(&mut rand::thread_rng())
+				.copied()
+				.unwrap_or_default()
+				.to_string()
+		}
+	}
+}
@@ -1,18 +1,51 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
+use chrono::{NaiveDateTime, Utc};
+use diesel::{Connection, OptionalExtension, PgConnection};
 use lib_ai::client::AiClient;
 use lib_core::{
-	dto::session::UserInSessionDto,
-	model::{ModelManager, base::BasicDbOps, schema_enums::SessionStatus},
+	dto::session::{ObjectiveScoreDto, UserInSessionDto},
+	model::{
+		ModelManager,
+		base::BasicDbOps,
+		schema_enums::{
+			BotDifficulty, NarratorMode, PlayMode, SessionStatus, TurnOrderMode,
+			VisibilityMode,
+		},
+	},
+};
+use lib_messages::{
+	model::{Message, NARRATION_TURN_ORDER, NewMessage},
+	suggestions::model::{NewSuggestion, Suggestion},
+	threads::model::Thread,
 };
-use lib_messages::model::{Message, NewMessage};
-use lib_players::model::{NewPlayer, Player, PlayerId};
+use lib_players::{
+	bots::model::Bot,
+	model::{NewPlayer, Player, PlayerId},
+	objectives::model::PlayerObjective,
+};
+use lib_profiles::model::Profile;
 use lib_sessions::model::{NewSession, Session};
+use lib_stories::variants::model::{StoryVariant, StoryVariantVote};
+use rand::seq::SliceRandom;
+use tracing::{Span, warn};
 use uuid::Uuid;
 
 use crate::{
+	achievements::{check_achievements, check_user_achievements},
+	bot_turn_task::{
+		MAX_BOT_NAME_CHARS, MAX_BOT_PERSONA_CHARS, STAND_IN_PERSONA,
+		spawn_bot_turn_task,
+	},
 	error::{Error, Result},
-	story_generation_task::spawn_story_generation_task,
+	judge_task::{assign_objectives, scoreboard, spawn_judge_task},
+	narrator_task::{NARRATION_TIMEOUT_SECS, spawn_narrator_task},
+	story_generation_task::{MAX_STORY_VARIANTS, spawn_story_generation_task},
+	suggestion_task::{MAX_SUGGESTIONS_PER_TURN, spawn_suggestion_task},
+	thread_stories_task::spawn_thread_stories_task,
+	turn_release_task::{MAX_UNDO_WINDOW_SECS, spawn_turn_release_task},
+	visibility::{self, MAX_VISIBILITY_WORDS},
+	voting::{finish_voting, spawn_voting_timeout},
 };
 use lib_game_events::{
 	event::{
@@ -29,6 +62,34 @@ pub struct GameEngine {
 	pub ai_client: Arc<AiClient>,
 }
 
+/// Host-chosen rules of a new session.
+pub struct SessionSettings {
+	pub max_rounds: i32,
+	/// Endings generated for the vote, 1 skips the vote.
+	pub story_variants: i32,
+	pub visibility: VisibilityMode,
+	/// Words shown in `VisibilityMode::LastWords`.
+	pub visibility_words: i32,
+	pub play_mode: PlayMode,
+	pub turn_order: TurnOrderMode,
+	/// A bot takes over the seat of a player leaving mid-game.
+	pub bot_backfill: bool,
+	/// Interludes between rounds, linear sessions only.
+	pub narrator: NarratorMode,
+	/// Secret objectives judged once the story is told.
+	pub objectives: bool,
+	/// Seconds a line can be taken back, linear sessions only.
+	pub undo_window_secs: i32,
+}
+
+/// Outcome of a player leaving, computed inside the leave transaction.
+enum LeaveOutcome {
+	SessionDeleted,
+	GameFinished(Session),
+	TurnPassed(Session),
+	Left(i64),
+}
+
 impl GameEngine {
 	pub fn new(
 		model_manager: Arc<ModelManager>,
@@ -42,44 +103,153 @@ impl GameEngine {
 		}
 	}
 
+	/// Event channels, for features that broadcast outside of a game.
+	pub fn game_events_manager(&self) -> &Arc<GameEventsManager> {
+		&self.game_events_manager
+	}
+
+	/// Runs engine operations on the blocking thread pool.
+	/// The engine talks to Postgres synchronously, so async handlers
+	/// call it through here instead of directly.
+	/// The caller's span (e.g. the request span) stays current inside `f`.
+	pub async fn run<T, F>(self: &Arc<Self>, f: F) -> Result<T>
+	where
+		F: FnOnce(&GameEngine) -> Result<T> + Send + 'static,
+		T: Send + 'static,
+	{
+		let engine = self.clone();
+		let span = Span::current();
+
+		tokio::task::spawn_blocking(move || span.in_scope(|| f(&engine)))
+			.await
+			.map_err(|ex| Error::BlockingTaskFailed(ex.to_string()))?
+	}
+
+	/// Picks up the timers of games interrupted by a restart: open votes
+	/// close at their deadline, or right away when it passed meanwhile.
+	/// The narrator writes again while there's time left, the game goes on
+	/// without the interlude otherwise. Pending lines are released at the
+	/// end of their undo window.
+	pub fn resume_interrupted(&self) -> Result<()> {
+		let mut conn = self.model_manager.db()?;
+
+		for session in Session::list_by_status(&mut conn, SessionStatus::Started)? {
+			if let Some(message_id) = session.pending_message_id {
+				spawn_turn_release_task(
+					self.clone(),
+					session.id,
+					message_id,
+					session
+						.pending_until
+						.unwrap_or_else(|| Utc::now().naive_utc()),
+				);
+			} else if session.narration_ends_at.is_some() {
+				spawn_narrator_task(
+					self.clone(),
+					self.model_manager.clone(),
+					session,
+				);
+			}
+		}
+
+		for session in Session::list_by_status(&mut conn, SessionStatus::Voting)? {
+			spawn_voting_timeout(
+				session.id,
+				session
+					.voting_ends_at
+					.unwrap_or_else(|| Utc::now().naive_utc()),
+				self.model_manager.clone(),
+				self.ai_client.clone(),
+				self.game_events_manager.clone(),
+			);
+		}
+
+		Ok(())
+	}
+
 	/// Creates a new session with a host player.
 	pub fn create_session(
 		&self,
 		theme: &str,
 		host_user_id: Uuid, // user_id of host user (not player_id)
-		max_rounds: i32,
+		settings: SessionSettings,
 	) -> Result<Session> {
-		if max_rounds < 2 {
+		if settings.max_rounds < 2 {
 			return Err(Error::NotEnoughRounds);
 		}
+		if !(1..=MAX_STORY_VARIANTS).contains(&settings.story_variants) {
+			return Err(Error::InvalidStoryVariants(MAX_STORY_VARIANTS));
+		}
+		if !(1..=MAX_VISIBILITY_WORDS).contains(&settings.visibility_words) {
+			return Err(Error::InvalidVisibilityWords(MAX_VISIBILITY_WORDS));
+		}
+		if settings.play_mode == PlayMode::Rotating && settings.story_variants > 1 {
+			return Err(Error::VariantsNeedLinearMode);
+		}
+		if settings.play_mode == PlayMode::Rotating
+			&& settings.narrator != NarratorMode::Off
+		{
+			return Err(Error::NarratorNeedsLinearMode);
+		}
+		if !(0..=MAX_UNDO_WINDOW_SECS).contains(&settings.undo_window_secs) {
+			return Err(Error::InvalidUndoWindow(MAX_UNDO_WINDOW_SECS));
+		}
+		if settings.play_mode == PlayMode::Rotating && settings.undo_window_secs > 0
+		{
+			return Err(Error::UndoNeedsLinearMode);
+		}
 
-		let mut conn = self.model_manager.db();
+		let mut conn = self.model_manager.db()?;
+
+		let new_session = NewSession {
+			theme,
+			max_rounds: settings.max_rounds,
+			story_variants: settings.story_variants,
+			visibility: settings.visibility,
+			visibility_words: settings.visibility_words,
+			play_mode: settings.play_mode,
+			turn_order: settings.turn_order,
+			bot_backfill: settings.bot_backfill,
+			narrator: settings.narrator,
+			objectives: settings.objectives,
+			undo_window_secs: settings.undo_window_secs,
+		};
 
-		let new_session = NewSession { theme, max_rounds };
+		let session = conn.transaction::<_, Error, _>(|conn| {
+			let session = Session::create(conn, new_session)?;
 
-		let session = Session::create(&mut conn, new_session)?;
+			// Create host player linked to session with user_id
+			let new_host_player = NewPlayer {
+				session_id: session.id,
+				user_id: host_user_id,
+				is_ready: false,
+				is_host: true,
+				seat: 0,
+			};
 
-		// Create host player linked to session with user_id
-		let new_host_player = NewPlayer {
-			session_id: session.id,
-			user_id: host_user_id,
-			is_ready: false,
-			is_host: true,
-		};
+			Player::create(conn, new_host_player)?;
+
+			Ok(session)
+		})?;
 
-		Player::create(&mut conn, new_host_player)?;
+		let host_display_name = Profile::display_name(&mut conn, host_user_id)?;
 
-		self.game_events_manager
-			.send_session_event(SessionEvent::Created {
+		self.game_events_manager.send_session_event(
+			session.version,
+			SessionEvent::Created {
 				session_id: session.id,
 				theme: session.theme.clone(),
 				max_rounds: session.max_rounds,
 				users: vec![UserInSessionDto {
 					user_id: host_user_id,
+					display_name: host_display_name,
 					is_ready: false,
 					is_host: true,
+					seat: 0,
+					is_bot: false,
 				}],
-			});
+			},
+		);
 
 		Ok(session)
 	}
@@ -90,8 +260,12 @@ impl GameEngine {
 		session_id: Uuid,
 		user_id: Uuid,
 		ready: bool,
+		expected_version: Option<i64>,
 	) -> Result<Player> {
-		let mut conn = self.model_manager.db();
+		let mut conn = self.model_manager.db()?;
+
+		let session = Session::get(&mut conn, session_id)?;
+		ensure_version(&session, expected_version)?;
 
 		let player_id = PlayerId {
 			session_id,
@@ -101,13 +275,21 @@ impl GameEngine {
 		let mut player = Player::get(&mut conn, player_id)?;
 		player.is_ready = ready;
 
-		let updated = Player::update(&mut conn, player_id, &player)?;
+		let (updated, version) = conn.transaction::<_, Error, _>(|conn| {
+			let updated = Player::update(conn, player_id, &player)?;
+			let version = Session::bump_version(conn, session_id, session.version)
+				.map_err(stale_on_not_found)?;
+
+			Ok((updated, version))
+		})?;
 
 		self.game_events_manager.send_game_event(
 			player.session_id,
 			None,
+			version,
 			GameEvent::PlayerReady {
 				user_id: player.user_id,
+				display_name: Profile::display_name(&mut conn, user_id)?,
 				ready,
 			},
 		);
@@ -116,12 +298,18 @@ impl GameEngine {
 	}
 
 	/// Allows a user to join a session (if it's still waiting).
-	pub fn join_session(&self, session_id: Uuid, user_id: Uuid) -> Result<Player> {
-		let mut conn = self.model_manager.db();
+	pub fn join_session(
+		&self,
+		session_id: Uuid,
+		user_id: Uuid,
+		expected_version: Option<i64>,
+	) -> Result<Player> {
+		let mut conn = self.model_manager.db()?;
 		let session = Session::get(&mut conn, session_id)?;
 		if session.status != SessionStatus::Waiting {
 			return Err(Error::AlreadyStarted);
 		}
+		ensure_version(&session, expected_version)?;
 
 		let player_id = PlayerId {
 			session_id,
@@ -133,101 +321,367 @@ impl GameEngine {
 			return Err(Error::AlreadyJoined);
 		}
 
-		let new_player = NewPlayer {
-			session_id,
-			user_id,
-			is_ready: false,
-			is_host: false,
-		};
+		let (player, version) = conn.transaction::<_, Error, _>(|conn| {
+			let new_player = NewPlayer {
+				session_id,
+				user_id,
+				is_ready: false,
+				is_host: false,
+				seat: Player::next_seat(conn, session_id)?,
+			};
+			let player = Player::create(conn, new_player)?;
+			let version = Session::bump_version(conn, session_id, session.version)
+				.map_err(stale_on_not_found)?;
 
-		let player = Player::create(&mut conn, new_player)?;
+			Ok((player, version))
+		})?;
 
 		self.game_events_manager.send_game_event(
 			session_id,
 			None,
+			version,
 			GameEvent::PlayerJoined {
 				user_id: player.user_id,
+				display_name: Profile::display_name(&mut conn, user_id)?,
 			},
 		);
 
-		self.game_events_manager
-			.send_session_event(SessionEvent::UpdatePlayers {
+		self.game_events_manager.send_session_event(
+			version,
+			SessionEvent::UpdatePlayers {
 				session_id: session.id,
 				users: Session::list_users_in_session(&mut conn, session_id)?,
-			});
+			},
+		);
+
+		Ok(player)
+	}
+
+	/// Lets the host seat an AI bot in the lobby, the bot is always ready.
+	/// Bots count as players, so small groups can start.
+	pub fn add_bot(
+		&self,
+		session_id: Uuid,
+		host_user_id: Uuid,
+		name: Option<&str>,
+		persona: &str,
+		difficulty: BotDifficulty,
+		expected_version: Option<i64>,
+	) -> Result<Player> {
+		let persona = persona.trim();
+		if persona.is_empty() || persona.chars().count() > MAX_BOT_PERSONA_CHARS {
+			return Err(Error::InvalidBotPersona(MAX_BOT_PERSONA_CHARS));
+		}
+		let name = name.map(str::trim);
+		if name.is_some_and(|name| {
+			name.is_empty() || name.chars().count() > MAX_BOT_NAME_CHARS
+		}) {
+			return Err(Error::InvalidBotName(MAX_BOT_NAME_CHARS));
+		}
+
+		let mut conn = self.model_manager.db()?;
+		let session = Session::get(&mut conn, session_id)?;
+		if session.status != SessionStatus::Waiting {
+			return Err(Error::AlreadyStarted);
+		}
+		ensure_version(&session, expected_version)?;
+		self.ensure_host(&mut conn, session_id, host_user_id)?;
+
+		let (player, display_name, version) =
+			conn.transaction::<_, Error, _>(|conn| {
+				let seat = Player::next_seat(conn, session_id)?;
+				let display_name = name
+					.map(str::to_string)
+					.unwrap_or_else(|| format!("Bot {}", seat + 1));
+				let player = seat_bot(
+					conn,
+					session_id,
+					seat,
+					&display_name,
+					persona,
+					difficulty,
+				)?;
+				let version =
+					Session::bump_version(conn, session_id, session.version)
+						.map_err(stale_on_not_found)?;
+
+				Ok((player, display_name, version))
+			})?;
+
+		self.game_events_manager.send_game_event(
+			session_id,
+			None,
+			version,
+			GameEvent::PlayerJoined {
+				user_id: player.user_id,
+				display_name,
+			},
+		);
+
+		self.game_events_manager.send_session_event(
+			version,
+			SessionEvent::UpdatePlayers {
+				session_id,
+				users: Session::list_users_in_session(&mut conn, session_id)?,
+			},
+		);
 
 		Ok(player)
 	}
 
+	/// Lets the host take a bot out of the session, it leaves like a player.
+	pub fn remove_bot(
+		&self,
+		session_id: Uuid,
+		host_user_id: Uuid,
+		bot_user_id: Uuid,
+		expected_version: Option<i64>,
+	) -> Result<()> {
+		let mut conn = self.model_manager.db()?;
+		self.ensure_host(&mut conn, session_id, host_user_id)?;
+		if Bot::find(&mut conn, bot_user_id)?.is_none() {
+			return Err(Error::NotABot);
+		}
+
+		self.leave_session(session_id, bot_user_id, expected_version)
+	}
+
+	/// Fails unless `user_id` hosts the session.
+	fn ensure_host(
+		&self,
+		conn: &mut PgConnection,
+		session_id: Uuid,
+		user_id: Uuid,
+	) -> Result<()> {
+		let player = Player::get(
+			conn,
+			PlayerId {
+				session_id,
+				user_id,
+			},
+		)
+		.map_err(|_| Error::UserNotInSession)?;
+		if !player.is_host {
+			return Err(Error::NotHost);
+		}
+
+		Ok(())
+	}
+
 	/// Allows a user to leave the session.
 	/// Handles mid-game player leaving:
 	/// - Removes player by player.id
-	/// - Ends game if less than 2 players remain during started game
+	/// - Seats a stand-in bot if the session backfills with bots
+	/// - Ends game if less than 2 players, or only bots, remain during
+	///   started game
 	/// - Advances turn if the leaving player had the current turn
-	pub fn leave_session(&self, session_id: Uuid, user_id: Uuid) -> Result<()> {
-		let mut conn = self.model_manager.db();
+	pub fn leave_session(
+		&self,
+		session_id: Uuid,
+		user_id: Uuid,
+		expected_version: Option<i64>,
+	) -> Result<()> {
+		let mut conn = self.model_manager.db()?;
 
 		let player_id = PlayerId {
 			session_id,
 			user_id,
 		};
 
-		let player = Player::get(&mut conn, player_id);
-		if player.is_err() {
-			return Err(Error::UserNotInSession);
-		}
+		let player = Player::get(&mut conn, player_id)
+			.map_err(|_| Error::UserNotInSession)?;
 
-		let mut session = Session::get(&mut conn, session_id)?;
+		let session = Session::get(&mut conn, session_id)?;
 
 		// Disallow leave if session is already finished
 		if session.status == SessionStatus::Finished {
 			return Err(Error::AlreadyFinished);
 		}
+		ensure_version(&session, expected_version)?;
+
+		let (outcome, users_for_session, stand_in) = conn
+			.transaction::<_, Error, _>(|conn| {
+				Player::delete(conn, player_id)?;
+
+				let mut users_for_session =
+					Session::list_users_in_session(conn, session_id)?;
+				let humans_left = users_for_session.iter().any(|user| !user.is_bot);
+
+				// A bot keeps the seat (and turn) of a player leaving mid-game
+				let stand_in = if session.bot_backfill
+					&& session.status == SessionStatus::Started
+					&& humans_left && Bot::find(conn, user_id)?
+					.is_none()
+				{
+					let display_name =
+						format!("{} (bot)", Profile::display_name(conn, user_id)?);
+					let bot = seat_bot(
+						conn,
+						session_id,
+						player.seat,
+						&display_name,
+						STAND_IN_PERSONA,
+						BotDifficulty::Normal,
+					)?;
+					users_for_session =
+						Session::list_users_in_session(conn, session_id)?;
+					Some((bot, display_name))
+				} else {
+					None
+				};
+
+				let outcome = if (users_for_session.is_empty() || player.is_host)
+					&& session.status == SessionStatus::Waiting
+				{
+					Session::delete(conn, session_id)?;
+					LeaveOutcome::SessionDeleted
+				} else if session.status == SessionStatus::Started
+					&& (users_for_session.len() < 2 || !humans_left)
+				{
+					// If game started and less than 2 players remain, finish the game
+					let mut finished = session.clone();
+					finished.status = SessionStatus::Finished;
+					finished.current_user_id_turn = None;
+					finished.pending_message_id = None;
+					finished.pending_until = None;
+					LeaveOutcome::GameFinished(
+						Session::update_versioned(conn, &finished)
+							.map_err(stale_on_not_found)?,
+					)
+				} else if let Some((bot, _)) = &stand_in
+					&& session.current_user_id_turn == Some(user_id)
+				{
+					let mut passed = session.clone();
+					passed.current_user_id_turn = Some(bot.user_id);
+					LeaveOutcome::TurnPassed(
+						if session.pending_message_id.is_some() {
+							// The leaver's line is kept, the bot writes next round
+							self.advance_turn(conn, passed, None)?
+						} else {
+							Session::update_versioned(conn, &passed)
+								.map_err(stale_on_not_found)?
+						},
+					)
+				} else if session.status == SessionStatus::Started
+					&& session.play_mode == PlayMode::Rotating
+				{
+					// The round may have been waiting only on the leaver
+					let seats = Message::seats_in_round(
+						conn,
+						session_id,
+						session.current_round,
+					)?;
+					if users_for_session
+						.iter()
+						.all(|user| seats.contains(&user.seat))
+					{
+						LeaveOutcome::TurnPassed(
+							self.advance_round(conn, session.clone())?,
+						)
+					} else {
+						LeaveOutcome::Left(
+							Session::bump_version(conn, session_id, session.version)
+								.map_err(stale_on_not_found)?,
+						)
+					}
+				} else if session.current_user_id_turn == Some(player_id.user_id) {
+					// If the leaving player had the current turn, advance turn to next player
+					LeaveOutcome::TurnPassed(self.advance_turn(
+						conn,
+						session.clone(),
+						Some(player.clone()),
+					)?)
+				} else {
+					LeaveOutcome::Left(
+						Session::bump_version(conn, session_id, session.version)
+							.map_err(stale_on_not_found)?,
+					)
+				};
+
+				Ok((outcome, users_for_session, stand_in))
+			})?;
+
+		let version = match &outcome {
+			LeaveOutcome::SessionDeleted => session.version + 1,
+			LeaveOutcome::GameFinished(session)
+			| LeaveOutcome::TurnPassed(session) => session.version,
+			LeaveOutcome::Left(version) => *version,
+		};
 
-		Player::delete(&mut conn, player_id)?;
-
-		let users_for_session =
-			Session::list_users_in_session(&mut conn, session_id)?;
-
-		if (users_for_session.is_empty() || player.unwrap().is_host)
-			&& session.status == SessionStatus::Waiting
-		{
-			Session::delete(&mut conn, session_id)?;
-
+		if let LeaveOutcome::SessionDeleted = outcome {
 			self.game_events_manager.send_game_event(
 				session_id,
 				None,
+				version,
 				GameEvent::SessionDeleted,
 			);
 
 			self.game_events_manager
-				.send_session_event(SessionEvent::Deleted { session_id });
+				.send_session_event(version, SessionEvent::Deleted { session_id });
 		}
 
 		self.game_events_manager.send_game_event(
 			session_id,
 			None,
-			GameEvent::PlayerLeft { user_id },
+			version,
+			GameEvent::PlayerLeft {
+				user_id,
+				display_name: Profile::display_name(&mut conn, user_id)?,
+			},
 		);
 
-		self.game_events_manager
-			.send_session_event(SessionEvent::UpdatePlayers {
+		if let Some((bot, display_name)) = stand_in {
+			self.game_events_manager.send_game_event(
+				session_id,
+				None,
+				version,
+				GameEvent::PlayerJoined {
+					user_id: bot.user_id,
+					display_name,
+				},
+			);
+		}
+
+		self.game_events_manager.send_session_event(
+			version,
+			SessionEvent::UpdatePlayers {
 				session_id: session.id,
-				users: users_for_session.clone(),
-			});
-
-		// If game started and less than 2 players remain, finish the game
-		if session.status == SessionStatus::Started && users_for_session.len() < 2 {
-			session.status = SessionStatus::Finished;
-			session.current_user_id_turn = None;
-			Session::update(&mut conn, session.id, &session)?;
-			return Ok(());
+				users: users_for_session,
+			},
+		);
+
+		match outcome {
+			LeaveOutcome::TurnPassed(session) => {
+				self.announce_turn(&mut conn, &session)?
+			}
+			// Threads shift between the remaining players
+			LeaveOutcome::Left(version)
+				if session.status == SessionStatus::Started
+					&& session.play_mode == PlayMode::Rotating =>
+			{
+				self.send_thread_turns(
+					&mut conn,
+					&Session {
+						version,
+						..session.clone()
+					},
+				)?
+			}
+			_ => {}
 		}
 
-		// If the leaving player had the current turn, advance turn to next player
-		if session.current_user_id_turn == Some(player_id.user_id) {
-			let last_message = Message::get_last_by_session(&mut conn, session_id)?;
-			self.next_turn(session_id, last_message.content)?;
+		// The leaver may have been the last one the vote was waiting for
+		if session.status == SessionStatus::Voting {
+			match finish_voting(
+				&mut conn,
+				&self.game_events_manager,
+				session_id,
+				false,
+			) {
+				Ok(true) => self.spawn_judge(session_id),
+				Ok(false) => {}
+				Err(e) => warn!("Failed to close voting after leave: {:?}", e),
+			}
 		}
 
 		Ok(())
@@ -243,7 +697,7 @@ impl GameEngine {
 		session_id: Uuid,
 		host_player_id: PlayerId,
 	) -> Result<bool> {
-		let mut conn = self.model_manager.db();
+		let mut conn = self.model_manager.db()?;
 		let session = Session::get(&mut conn, session_id)?;
 		if session.status != SessionStatus::Waiting {
 			return Err(Error::AlreadyStarted);
@@ -273,18 +727,79 @@ impl GameEngine {
 		Ok(true)
 	}
 
+	/// Lets the host seat the players in the lobby, in `manual` turn order.
+	/// `user_ids` lists every player once, first seat first.
+	pub fn arrange_seats(
+		&self,
+		session_id: Uuid,
+		host_user_id: Uuid,
+		user_ids: Vec<Uuid>,
+		expected_version: Option<i64>,
+	) -> Result<Vec<UserInSessionDto>> {
+		let mut conn = self.model_manager.db()?;
+
+		let session = Session::get(&mut conn, session_id)?;
+		if session.status != SessionStatus::Waiting {
+			return Err(Error::AlreadyStarted);
+		}
+		ensure_version(&session, expected_version)?;
+		if session.turn_order != TurnOrderMode::Manual {
+			return Err(Error::NotManualTurnOrder);
+		}
+
+		self.ensure_host(&mut conn, session_id, host_user_id)?;
+
+		let version = conn.transaction::<_, Error, _>(|conn| {
+			let mut seated = Player::list_by_session(conn, session_id)?
+				.into_iter()
+				.map(|p| p.user_id)
+				.collect::<Vec<_>>();
+			let mut arranged = user_ids.clone();
+			seated.sort();
+			arranged.sort();
+			if seated != arranged {
+				return Err(Error::InvalidSeating);
+			}
+
+			Player::set_seats(conn, session_id, &user_ids)?;
+			Session::bump_version(conn, session_id, session.version)
+				.map_err(stale_on_not_found)
+		})?;
+
+		let users = Session::list_users_in_session(&mut conn, session_id)?;
+
+		self.game_events_manager.send_game_event(
+			session_id,
+			None,
+			version,
+			GameEvent::SeatsArranged { user_ids },
+		);
+
+		self.game_events_manager.send_session_event(
+			version,
+			SessionEvent::UpdatePlayers {
+				session_id,
+				users: users.clone(),
+			},
+		);
+
+		Ok(users)
+	}
+
 	/// TODO: improve json request body
 	/// Starts the game session:
 	/// - Starter player must be host
 	/// - Sets status to Started
 	/// - Sets current round to 1
-	/// - Sets the current turn to the first player's player_id
+	/// - Seats players as the turn order mode says
+	/// - Sets the current turn to the first seated player
 	pub fn start_game(
 		&self,
 		session_id: Uuid,
 		host_user_id: Uuid,
+		expected_version: Option<i64>,
 	) -> Result<Session> {
-		let mut conn = self.model_manager.db();
+		let mut conn = self.model_manager.db()?;
 
 		let host_player_id = PlayerId {
 			session_id,
@@ -297,172 +812,1072 @@ impl GameEngine {
 		self.can_start(session_id, host_player_id)?;
 
 		let mut session = Session::get(&mut conn, session_id)?;
+		ensure_version(&session, expected_version)?;
+
 		session.status = SessionStatus::Started;
 		session.current_round = 1;
-		session.current_user_id_turn = Some(host_user_id);
 
-		let updated = Session::update(&mut conn, session.id, &session)?;
+		let updated = conn.transaction::<_, Error, _>(|conn| {
+			let mut players = Player::list_by_session(conn, session_id)?;
+			if session.turn_order == TurnOrderMode::Shuffled {
+				players.shuffle(&mut rand::thread_rng());
+			}
+			// Seats from 0 on, closing gaps left by players who left the lobby
+			let seated = players.iter().map(|p| p.user_id).collect::<Vec<_>>();
+			Player::set_seats(conn, session_id, &seated)?;
+
+			if session.play_mode == PlayMode::Rotating {
+				// One thread per player, everyone writes at once
+				Thread::create_for_session(conn, session_id, seated.len() as i32)?;
+				session.current_user_id_turn = None;
+			} else {
+				session.current_user_id_turn = seated.first().copied();
+			}
+
+			// Bots play along without an objective of their own
+			let objectives = if session.objectives {
+				let bots = Bot::ids_in_session(conn, session_id)?;
+				let humans = seated
+					.iter()
+					.copied()
+					.filter(|user_id| !bots.contains(user_id))
+					.collect::<Vec<_>>();
+				PlayerObjective::create_for_session(
+					conn,
+					session_id,
+					&assign_objectives(&humans),
+				)?
+			} else {
+				Vec::new()
+			};
+
+			let updated = Session::update_versioned(conn, &session)
+				.map_err(stale_on_not_found)?;
+			Ok((updated, objectives))
+		})?;
+		let (updated, objectives) = updated;
 
-		self.game_events_manager
-			.send_session_event(SessionEvent::Started {
+		self.game_events_manager.send_session_event(
+			updated.version,
+			SessionEvent::Started {
 				session_id: session.id,
-			});
+			},
+		);
 
 		self.game_events_manager.send_game_event(
 			session_id,
 			None,
+			updated.version,
 			GameEvent::GameStarted,
 		);
 
-		self.game_events_manager.send_game_event(
+		for objective in objectives {
+			self.game_events_manager.send_game_event(
+				session_id,
+				Some(GameEventReceiver {
+					user_id: objective.user_id,
+				}),
+				updated.version,
+				GameEvent::SecretObjective {
+					objective: objective.objective,
+				},
+			);
+		}
+
+		check_achievements(
+			&mut conn,
+			&self.game_events_manager,
 			session_id,
-			None,
-			GameEvent::NewTurn {
-				user_id: session.current_user_id_turn.unwrap(),
-			},
+			updated.version,
 		);
 
+		if updated.play_mode == PlayMode::Rotating {
+			self.announce_turn(&mut conn, &updated)?;
+			return Ok(updated);
+		}
+
+		if let Some(user_id) = updated.current_user_id_turn {
+			self.game_events_manager.send_game_event(
+				session_id,
+				None,
+				updated.version,
+				GameEvent::NewTurn {
+					user_id,
+					display_name: Profile::display_name(&mut conn, user_id)?,
+				},
+			);
+		}
+
 		Ok(updated)
 	}
 
 	/// Advances the turn to the next player in the session.
 	/// - If last player had the turn, increases round.
 	/// - Ends game if max rounds reached.
-	pub fn next_turn(
-		&self,
-		session_id: Uuid,
-		last_player_message: String,
-	) -> Result<()> {
-		let mut conn = self.model_manager.db();
+	pub fn next_turn(&self, session_id: Uuid) -> Result<()> {
+		let mut conn = self.model_manager.db()?;
 
-		let mut session = Session::get(&mut conn, session_id)?;
+		let session = Session::get(&mut conn, session_id)?;
+		let session = self.advance_turn(&mut conn, session, None)?;
 
-		let players = Player::list_by_session(&mut conn, session_id)?;
+		self.announce_turn(&mut conn, &session)
+	}
+
+	/// Moves `session` to the next player (or round) and persists it.
+	/// `left` is the player on turn who just left, they still count for
+	/// the order of the current round.
+	/// Fails with `StaleVersion` if the session changed since it was read.
+	fn advance_turn(
+		&self,
+		conn: &mut PgConnection,
+		mut session: Session,
+		left: Option<Player>,
+	) -> Result<Session> {
+		let players = Player::list_by_session(conn, session.id)?;
 		if players.is_empty() {
 			return Err(Error::NotEnoughPlayers);
 		}
+		// Moving on releases a line still in its undo window
+		session.pending_message_id = None;
+		session.pending_until = None;
+
+		let mut in_round = players.clone();
+		let current = match left {
+			Some(left) => {
+				let seat = in_round.partition_point(|p| p.seat < left.seat);
+				let user_id = left.user_id;
+				in_round.insert(seat, left);
+				Some(user_id)
+			}
+			None => session.current_user_id_turn,
+		};
+		let order =
+			round_order(&in_round, session.turn_order, session.current_round);
 
-		// Find index of current player
-		let current_index = session
-			.current_user_id_turn
-			.and_then(|uid| players.iter().position(|p| p.user_id == uid))
-			.unwrap_or(usize::MAX);
+		// Find position of current player
+		let next = match current
+			.and_then(|uid| order.iter().position(|p| p.user_id == uid))
+		{
+			// If current player not found, start from first player
+			None => order.first(),
+			Some(index) => order.get(index + 1),
+		}
+		.map(|p| p.user_id);
 
-		let next_index = if current_index == usize::MAX {
-			0 // If current player not found, start from first player
+		if let Some(user_id) = next {
+			// Advance turn to next player
+			session.current_user_id_turn = Some(user_id);
 		} else {
-			current_index + 1
-		};
-
-		if next_index >= players.len() {
 			// End of round, increment round count
 			session.current_round += 1;
 			if session.current_round > session.max_rounds {
 				// Max rounds reached — wait for story generation
 				session.status = SessionStatus::WaitingForStoryGeneration;
 				session.current_user_id_turn = None;
+			} else if session.narrator != NarratorMode::Off {
+				// Nobody's turn until the narrator bridged the rounds
+				session.current_user_id_turn = None;
+				session.narration_ends_at = Some(
+					Utc::now().naive_utc()
+						+ chrono::Duration::seconds(NARRATION_TIMEOUT_SECS),
+				);
 			} else {
-				// Start new round, first player gets turn
-				session.current_user_id_turn = Some(players[0].user_id);
+				// Start new round, its first player gets turn
+				session.current_user_id_turn =
+					round_order(&players, session.turn_order, session.current_round)
+						.first()
+						.map(|p| p.user_id);
 			}
-		} else {
-			// Advance turn to next player
-			session.current_user_id_turn = Some(players[next_index].user_id);
 		}
 
-		Session::update(&mut conn, session.id, &session)?;
+		Session::update_versioned(conn, &session).map_err(stale_on_not_found)
+	}
 
-		if session.current_user_id_turn.is_some()
-			&& session.status == SessionStatus::Started
-		{
-			self.game_events_manager.send_game_event(
-				session_id,
-				None,
-				GameEvent::NewTurn {
-					user_id: session.current_user_id_turn.unwrap(),
-				},
-			);
+	/// Notifies players about the turn `session` is now at and kicks off
+	/// story generation once all rounds are played.
+	/// The player on turn gets the excerpt the session's visibility allows,
+	/// a bot on turn writes from it. Between rounds with no one on turn,
+	/// the narrator writes first.
+	fn announce_turn(
+		&self,
+		conn: &mut PgConnection,
+		session: &Session,
+	) -> Result<()> {
+		match (session.status.clone(), session.current_user_id_turn) {
+			(SessionStatus::Started, _)
+				if session.play_mode == PlayMode::Rotating =>
+			{
+				self.game_events_manager.send_game_event(
+					session.id,
+					None,
+					session.version,
+					GameEvent::RoundStarted {
+						round: session.current_round,
+					},
+				);
 
-			self.game_events_manager.send_game_event(
-				session_id,
-				Some(GameEventReceiver {
-					user_id: session.current_user_id_turn.unwrap(),
-				}),
-				GameEvent::LastPlayerMessage {
-					content: last_player_message,
-				},
-			);
-		} else if session.status == SessionStatus::WaitingForStoryGeneration {
-			self.game_events_manager.send_game_event(
-				session_id,
-				None,
-				GameEvent::WaitingForStoryGeneration,
-			);
+				self.send_thread_turns(conn, session)?;
+			}
+			(SessionStatus::Started, Some(user_id)) => {
+				self.game_events_manager.send_game_event(
+					session.id,
+					None,
+					session.version,
+					GameEvent::NewTurn {
+						user_id,
+						display_name: Profile::display_name(conn, user_id)?,
+					},
+				);
 
-			if let Some(generation_guard) =
-				self.ai_client.try_acquire_generation(session_id)
+				let content = visible_excerpt(conn, session)?;
+				if let Some(bot) = Bot::find(conn, user_id)? {
+					spawn_bot_turn_task(
+						self.clone(),
+						session.id,
+						session.theme.clone(),
+						bot,
+						content,
+					);
+					return Ok(());
+				}
+
+				self.game_events_manager.send_game_event(
+					session.id,
+					Some(GameEventReceiver { user_id }),
+					session.version,
+					GameEvent::LastPlayerMessage { content },
+				);
+			}
+			(SessionStatus::Started, None)
+				if session.narrator != NarratorMode::Off =>
 			{
-				// Spawn async generation pipeline — does not block current thread
-				spawn_story_generation_task(
-					session_id,
-					generation_guard,
+				spawn_narrator_task(
+					self.clone(),
 					self.model_manager.clone(),
-					self.ai_client.clone(),
-					self.game_events_manager.clone(),
+					session.clone(),
 				);
-			} else {
-				return Ok(());
-			};
+			}
+			(SessionStatus::WaitingForStoryGeneration, _) => {
+				self.game_events_manager.send_game_event(
+					session.id,
+					None,
+					session.version,
+					GameEvent::WaitingForStoryGeneration,
+				);
+
+				if let Some(generation_guard) =
+					self.ai_client.try_acquire_generation(session.id)
+				{
+					if session.play_mode == PlayMode::Rotating {
+						spawn_thread_stories_task(
+							session.id,
+							session.version,
+							generation_guard,
+							self.model_manager.clone(),
+							self.ai_client.clone(),
+							self.game_events_manager.clone(),
+						);
+						return Ok(());
+					}
+
+					// Spawn async generation pipeline — does not block current thread
+					spawn_story_generation_task(
+						session.id,
+						session.version,
+						session.story_variants,
+						generation_guard,
+						self.model_manager.clone(),
+						self.ai_client.clone(),
+						self.game_events_manager.clone(),
+					);
+				}
+			}
+			_ => {}
 		}
 
 		Ok(())
 	}
 
-	/// Checks if it's the given player's turn (by player_id).
-	pub fn is_player_turn(&self, session_id: Uuid, user_id: Uuid) -> Result<bool> {
-		let mut conn = self.model_manager.db();
-
-		let session = Session::get(&mut conn, session_id)?;
-		Ok(session.current_user_id_turn == Some(user_id))
-	}
-
-	/// Submits a message (a move) by the player if it's their turn.
-	/// Then advances the turn.
-	pub fn submit_message(
+	/// Stores the narrator's interlude after `round` (if it was written) and
+	/// gives the turn to the first player of the next round.
+	pub(crate) fn finish_narration(
+		&self,
+		session_id: Uuid,
+		round: i32,
+		narration: Option<String>,
+	) -> Result<()> {
+		let mut conn = self.model_manager.db()?;
+
+		let session = conn.transaction::<_, Error, _>(|conn| {
+			let mut session = Session::get_for_update(conn, session_id)?;
+			// The game may have ended or moved on meanwhile
+			if session.status != SessionStatus::Started
+				|| session.current_round != round + 1
+				|| session.current_user_id_turn.is_some()
+			{
+				return Ok(None);
+			}
+
+			if let Some(content) = &narration {
+				Message::create(
+					conn,
+					NewMessage {
+						session_id,
+						user_id: None,
+						content,
+						round,
+						turn_order: NARRATION_TURN_ORDER,
+						thread_id: None,
+						used_suggestion: false,
+					},
+				)?;
+			}
+
+			let players = Player::list_by_session(conn, session_id)?;
+			session.current_user_id_turn =
+				round_order(&players, session.turn_order, session.current_round)
+					.first()
+					.map(|p| p.user_id);
+			session.narration_ends_at = None;
+
+			Session::update_versioned(conn, &session)
+				.map(Some)
+				.map_err(stale_on_not_found)
+		})?;
+
+		let Some(session) = session else {
+			return Ok(());
+		};
+
+		if let Some(content) = narration {
+			self.game_events_manager.send_game_event(
+				session_id,
+				None,
+				session.version,
+				GameEvent::NarratorComplete { round, content },
+			);
+		}
+
+		self.announce_turn(&mut conn, &session)
+	}
+
+	/// Unlocks what the user earned outside of a game, e.g. by rating a
+	/// story of `session_id`. They're told on that session's channel,
+	/// failures are only logged.
+	pub fn check_achievements_of(
+		&self,
+		session_id: Uuid,
+		user_id: Uuid,
+	) -> Result<()> {
+		let mut conn = self.model_manager.db()?;
+
+		check_user_achievements(
+			&mut conn,
+			&self.game_events_manager,
+			session_id,
+			user_id,
+		);
+
+		Ok(())
+	}
+
+	/// Checks if it's the given player's turn (by player_id).
+	pub fn is_player_turn(&self, session_id: Uuid, user_id: Uuid) -> Result<bool> {
+		let mut conn = self.model_manager.db()?;
+
+		let session = Session::get(&mut conn, session_id)?;
+		Ok(session.current_user_id_turn == Some(user_id))
+	}
+
+	/// Streams a few writing ideas to the player on turn, from what they may
+	/// see. Limited per turn, the message of the turn is flagged.
+	pub fn request_suggestions(
+		&self,
+		session_id: Uuid,
+		user_id: Uuid,
+		expected_version: Option<i64>,
+	) -> Result<()> {
+		let mut conn = self.model_manager.db()?;
+
+		let session = conn.transaction::<_, Error, _>(|conn| {
+			let session = Session::get_for_update(conn, session_id)?;
+			ensure_version(&session, expected_version)?;
+			if session.status != SessionStatus::Started
+				|| session.current_user_id_turn != Some(user_id)
+			{
+				return Err(Error::InvalidTurn);
+			}
+			// The line is written, the turn only waits for its undo window
+			if session.pending_message_id.is_some() {
+				return Err(Error::TurnPending);
+			}
+
+			let used = Suggestion::count_for_turn(
+				conn,
+				session_id,
+				user_id,
+				session.current_round,
+			)?;
+			if used >= MAX_SUGGESTIONS_PER_TURN {
+				return Err(Error::SuggestionLimitReached(MAX_SUGGESTIONS_PER_TURN));
+			}
+
+			Suggestion::create(
+				conn,
+				NewSuggestion {
+					session_id,
+					user_id,
+					round: session.current_round,
+				},
+			)?;
+
+			Ok(session)
+		})?;
+
+		let excerpt = visible_excerpt(&mut conn, &session)?;
+		spawn_suggestion_task(
+			self.game_events_manager.clone(),
+			self.ai_client.clone(),
+			session,
+			user_id,
+			excerpt,
+		);
+
+		Ok(())
+	}
+
+	/// Submits a message (a move) by the player if it's their turn.
+	/// Then advances the turn, after the session's undo window if it has one.
+	pub fn submit_message(
 		&self,
 		session_id: Uuid,
 		user_id: Uuid,
 		content: &str,
+		expected_version: Option<i64>,
 	) -> Result<()> {
+		if content.trim().is_empty() {
+			return Err(Error::EmptyMessage);
+		}
+
+		let mut conn = self.model_manager.db()?;
+
+		let session = Session::get(&mut conn, session_id)?;
+		if session.play_mode == PlayMode::Rotating {
+			return self.submit_thread_message(
+				&mut conn,
+				session_id,
+				user_id,
+				content,
+				expected_version,
+			);
+		}
+
+		self.submit_turn(&mut conn, session, user_id, content, expected_version)
+	}
+
+	/// Gives up the player's turn, recorded as an empty contribution.
+	/// The turn moves on right away, there's nothing to take back.
+	pub fn pass_turn(
+		&self,
+		session_id: Uuid,
+		user_id: Uuid,
+		expected_version: Option<i64>,
+	) -> Result<()> {
+		let mut conn = self.model_manager.db()?;
+
+		let session = Session::get(&mut conn, session_id)?;
+		if session.play_mode == PlayMode::Rotating {
+			return self.submit_thread_message(
+				&mut conn,
+				session_id,
+				user_id,
+				"",
+				expected_version,
+			);
+		}
+
+		self.submit_turn(&mut conn, session, user_id, "", expected_version)
+	}
+
+	/// Linear sessions: stores the line of the player on turn, empty for a
+	/// pass. With an undo window the line stays pending and the turn moves
+	/// on once the window closed, bots and passes hand it on right away.
+	fn submit_turn(
+		&self,
+		conn: &mut PgConnection,
+		session: Session,
+		user_id: Uuid,
+		content: &str,
+		expected_version: Option<i64>,
+	) -> Result<()> {
+		let session_id = session.id;
+		// The client saw the session before the release below
+		ensure_version(&session, expected_version)?;
+		// A window that closed with no task left to release it
+		let session = match session.pending_message_id {
+			Some(message_id) if undo_window_closed(&session) => {
+				self.release_turn(session_id, message_id)?;
+				Session::get(conn, session_id)?
+			}
+			_ => session,
+		};
+
 		// Check if it's the player's turn
-		if !Self::is_player_turn(&self, session_id, user_id)? {
+		if session.current_user_id_turn != Some(user_id) {
 			return Err(Error::InvalidTurn);
 		}
+		if session.pending_message_id.is_some() {
+			return Err(Error::TurnPending);
+		}
 
-		let mut conn = self.model_manager.db();
+		Player::get(
+			conn,
+			PlayerId {
+				session_id,
+				user_id,
+			},
+		)
+		.map_err(|_| Error::PlayerNotFound)?;
 
-		let session = Session::get(&mut conn, session_id)?;
-		let players = Player::list_by_session(&mut conn, session_id)?;
+		// Lines are listed by the order they were written in the round, with
+		// a rotating starter that's not the seat order
+		let turn_order = Message::authors_in_round(
+			conn,
+			session_id,
+			session.current_round,
+		)?
+		.len() as i32;
 
-		// Find player's turn order (index)
-		let turn_index = players
-			.iter()
-			.position(|p| p.user_id == user_id)
-			.ok_or(Error::PlayerNotFound)?;
+		let used_suggestion = Suggestion::count_for_turn(
+			conn,
+			session_id,
+			user_id,
+			session.current_round,
+		)? > 0;
+
+		let passed = content.is_empty();
+		let buffered = !passed
+			&& session.undo_window_secs > 0
+			&& Bot::find(conn, user_id)?.is_none();
 
 		let new_message = NewMessage {
 			session_id,
-			user_id,
+			user_id: Some(user_id),
 			content,
 			round: session.current_round,
-			turn_order: turn_index as i32,
+			turn_order,
+			thread_id: None,
+			used_suggestion,
 		};
 
-		Message::create(&mut conn, new_message.clone())?;
+		// Store the message and advance (or hold) the turn atomically
+		let (session, pending) = conn.transaction::<_, Error, _>(|conn| {
+			let message = Message::create(conn, new_message)?;
+			if !buffered {
+				return Ok((self.advance_turn(conn, session, None)?, None));
+			}
+
+			let window = chrono::Duration::seconds(session.undo_window_secs.into());
+			let until = Utc::now().naive_utc() + window;
+			let held = Session {
+				pending_message_id: Some(message.id),
+				pending_until: Some(until),
+				..session
+			};
+			let held = Session::update_versioned(conn, &held)
+				.map_err(stale_on_not_found)?;
+			Ok((held, Some((message.id, until))))
+		})?;
+
+		metrics::counter!("game_turns_submitted_total").increment(1);
+
+		if let Some((message_id, until)) = pending {
+			self.game_events_manager.send_game_event(
+				session_id,
+				None,
+				session.version,
+				GameEvent::MessagePending {
+					user_id,
+					undo_window_secs: session.undo_window_secs,
+				},
+			);
+			spawn_turn_release_task(self.clone(), session_id, message_id, until);
+			return Ok(());
+		}
+
+		if passed {
+			self.game_events_manager.send_game_event(
+				session_id,
+				None,
+				session.version,
+				GameEvent::PlayerPassed {
+					user_id,
+					display_name: Profile::display_name(conn, user_id)?,
+				},
+			);
+		}
+
+		self.announce_turn(conn, &session)
+	}
+
+	/// Takes back the player's line while it's in its undo window.
+	/// The turn stays with the player, who writes again.
+	pub fn retract_message(
+		&self,
+		session_id: Uuid,
+		user_id: Uuid,
+		expected_version: Option<i64>,
+	) -> Result<()> {
+		let mut conn = self.model_manager.db()?;
+
+		let session = conn.transaction::<_, Error, _>(|conn| {
+			let mut session = Session::get_for_update(conn, session_id)?;
+			ensure_version(&session, expected_version)?;
+			let message_id = pending_message_of(&session, user_id)?;
+
+			Message::delete(conn, message_id)?;
+			session.pending_message_id = None;
+			session.pending_until = None;
+
+			Session::update_versioned(conn, &session).map_err(stale_on_not_found)
+		})?;
+
+		self.game_events_manager.send_game_event(
+			session_id,
+			None,
+			session.version,
+			GameEvent::MessageRetracted { user_id },
+		);
+
+		Ok(())
+	}
+
+	/// Rewrites the player's line while it's in its undo window.
+	/// No one else has seen it yet, so no one is told.
+	pub fn edit_message(
+		&self,
+		session_id: Uuid,
+		user_id: Uuid,
+		content: &str,
+		expected_version: Option<i64>,
+	) -> Result<()> {
+		if content.trim().is_empty() {
+			return Err(Error::EmptyMessage);
+		}
+
+		let mut conn = self.model_manager.db()?;
+
+		conn.transaction::<_, Error, _>(|conn| {
+			let session = Session::get_for_update(conn, session_id)?;
+			ensure_version(&session, expected_version)?;
+			let message_id = pending_message_of(&session, user_id)?;
+
+			let mut message = Message::get(conn, message_id)?;
+			message.content = content.to_string();
+			Message::update(conn, message_id, &message)?;
+
+			Ok(())
+		})
+	}
+
+	/// Hands the turn on once the undo window of `message_id` closed.
+	/// Does nothing when the message was taken back or the game moved on.
+	pub(crate) fn release_turn(
+		&self,
+		session_id: Uuid,
+		message_id: Uuid,
+	) -> Result<()> {
+		let mut conn = self.model_manager.db()?;
+
+		let session = conn.transaction::<_, Error, _>(|conn| {
+			let session = Session::get_for_update(conn, session_id)?;
+			if session.status != SessionStatus::Started
+				|| session.pending_message_id != Some(message_id)
+			{
+				return Ok(None);
+			}
+
+			self.advance_turn(conn, session, None).map(Some)
+		})?;
+
+		match session {
+			Some(session) => self.announce_turn(&mut conn, &session),
+			None => Ok(()),
+		}
+	}
+
+	/// Rotating sessions: the player writes on their thread of the round.
+	/// The round ends once every player has written.
+	fn submit_thread_message(
+		&self,
+		conn: &mut PgConnection,
+		session_id: Uuid,
+		user_id: Uuid,
+		content: &str,
+		expected_version: Option<i64>,
+	) -> Result<()> {
+		let (session, round_over) = conn.transaction::<_, Error, _>(|conn| {
+			let session = Session::get_for_update(conn, session_id)?;
+			ensure_version(&session, expected_version)?;
+			if session.status != SessionStatus::Started {
+				return Err(Error::InvalidTurn);
+			}
+
+			let players = Player::list_by_session(conn, session_id)?;
+			let seat = players
+				.iter()
+				.find(|p| p.user_id == user_id)
+				.map(|p| p.seat)
+				.ok_or(Error::PlayerNotFound)?;
+
+			let seats =
+				Message::seats_in_round(conn, session_id, session.current_round)?;
+			if seats.contains(&seat) {
+				return Err(Error::AlreadySubmitted);
+			}
+
+			let threads = Thread::list_by_session(conn, session_id)?;
+			let thread = thread_for(&threads, seat, session.current_round)
+				.ok_or(Error::Unknown)?;
+
+			Message::create(
+				conn,
+				NewMessage {
+					session_id,
+					user_id: Some(user_id),
+					content,
+					round: session.current_round,
+					turn_order: seat,
+					thread_id: Some(thread.id),
+					used_suggestion: false,
+				},
+			)?;
+
+			let round_over = players
+				.iter()
+				.all(|p| p.seat == seat || seats.contains(&p.seat));
+			if round_over {
+				return Ok((self.advance_round(conn, session)?, true));
+			}
+
+			let version = Session::bump_version(conn, session_id, session.version)
+				.map_err(stale_on_not_found)?;
+			Ok((Session { version, ..session }, false))
+		})?;
+
+		metrics::counter!("game_turns_submitted_total").increment(1);
+
+		self.game_events_manager.send_game_event(
+			session_id,
+			None,
+			session.version,
+			GameEvent::ThreadSubmitted {
+				user_id,
+				display_name: Profile::display_name(conn, user_id)?,
+			},
+		);
 
-		// Advance the turn after message submission
-		self.next_turn(session_id, new_message.content.to_string())?;
+		if round_over {
+			self.announce_turn(conn, &session)?;
+		}
 
 		Ok(())
 	}
+
+	/// Rotating sessions: starts the next round, or story generation after
+	/// the last one.
+	fn advance_round(
+		&self,
+		conn: &mut PgConnection,
+		mut session: Session,
+	) -> Result<Session> {
+		session.current_round += 1;
+		if session.current_round > session.max_rounds {
+			session.status = SessionStatus::WaitingForStoryGeneration;
+		}
+
+		Session::update_versioned(conn, &session).map_err(stale_on_not_found)
+	}
+
+	/// Sends each player who hasn't written this round their thread,
+	/// showing what the session's visibility allows. Bots write right away.
+	fn send_thread_turns(
+		&self,
+		conn: &mut PgConnection,
+		session: &Session,
+	) -> Result<()> {
+		let players = Player::list_by_session(conn, session.id)?;
+		let threads = Thread::list_by_session(conn, session.id)?;
+		let seats =
+			Message::seats_in_round(conn, session.id, session.current_round)?;
+
+		for player in &players {
+			if seats.contains(&player.seat) {
+				continue;
+			}
+			let Some(thread) =
+				thread_for(&threads, player.seat, session.current_round)
+			else {
+				continue;
+			};
+
+			let contents = Message::list_by_thread(conn, thread.id)?
+				.into_iter()
+				.map(|m| m.content)
+				.collect::<Vec<_>>();
+			let content = visibility::excerpt(
+				session.visibility,
+				session.visibility_words as usize,
+				&contents,
+			);
+
+			if let Some(bot) = Bot::find(conn, player.user_id)? {
+				spawn_bot_turn_task(
+					self.clone(),
+					session.id,
+					session.theme.clone(),
+					bot,
+					content,
+				);
+				continue;
+			}
+
+			self.game_events_manager.send_game_event(
+				session.id,
+				Some(GameEventReceiver {
+					user_id: player.user_id,
+				}),
+				session.version,
+				GameEvent::ThreadTurn {
+					thread_id: thread.id,
+					thread_index: thread.thread_index,
+					content,
+				},
+			);
+		}
+
+		Ok(())
+	}
+
+	/// Votes for a story variant, replacing the user's earlier vote.
+	/// The vote closes as soon as every player has voted.
+	pub fn vote_variant(
+		&self,
+		session_id: Uuid,
+		user_id: Uuid,
+		variant_id: Uuid,
+		expected_version: Option<i64>,
+	) -> Result<()> {
+		let mut conn = self.model_manager.db()?;
+
+		let session = Session::get(&mut conn, session_id)?;
+		if session.status != SessionStatus::Voting {
+			return Err(Error::NotVoting);
+		}
+		ensure_version(&session, expected_version)?;
+
+		Player::get(
+			&mut conn,
+			PlayerId {
+				session_id,
+				user_id,
+			},
+		)
+		.map_err(|_| Error::UserNotInSession)?;
+
+		let variant = StoryVariant::get(&mut conn, variant_id)
+			.optional()?
+			.filter(|v| v.session_id == session_id)
+			.ok_or(Error::VariantNotFound)?;
+
+		StoryVariantVote::cast(&mut conn, session_id, user_id, variant.id)?;
+
+		if finish_voting(&mut conn, &self.game_events_manager, session_id, false)? {
+			self.spawn_judge(session_id);
+		}
+
+		Ok(())
+	}
+
+	/// Lists the session's story variants with their current votes.
+	pub fn list_variants(
+		&self,
+		session_id: Uuid,
+	) -> Result<Vec<(StoryVariant, i64)>> {
+		let mut conn = self.model_manager.db()?;
+
+		let tally = StoryVariantVote::tally(&mut conn, session_id)?;
+		let variants = StoryVariant::list_by_session(&mut conn, session_id)?
+			.into_iter()
+			.map(|variant| {
+				let votes = tally
+					.iter()
+					.find(|(id, _)| *id == variant.id)
+					.map_or(0, |(_, votes)| *votes);
+				(variant, votes)
+			})
+			.collect();
+
+		Ok(variants)
+	}
+
+	/// Judged objectives of a competitive session, best score first.
+	pub fn scoreboard(&self, session_id: Uuid) -> Result<Vec<ObjectiveScoreDto>> {
+		let mut conn = self.model_manager.db()?;
+
+		if !Session::get(&mut conn, session_id)?.objectives {
+			return Err(Error::NoObjectives);
+		}
+
+		scoreboard(&mut conn, session_id)
+	}
+
+	fn spawn_judge(&self, session_id: Uuid) {
+		spawn_judge_task(
+			session_id,
+			self.model_manager.clone(),
+			self.ai_client.clone(),
+			self.game_events_manager.clone(),
+		);
+	}
+}
+
+/// Thread the player in `seat` writes on in `round` (1-based):
+/// each round every thread moves on to the next seat.
+fn thread_for(threads: &[Thread], seat: i32, round: i32) -> Option<&Thread> {
+	if threads.is_empty() {
+		return None;
+	}
+	let index = (seat + round - 1).rem_euclid(threads.len() as i32);
+	threads.get(index as usize)
+}
+
+/// Creates a bot with its profile and seats it, ready to play.
+fn seat_bot(
+	conn: &mut PgConnection,
+	session_id: Uuid,
+	seat: i32,
+	display_name: &str,
+	persona: &str,
+	difficulty: BotDifficulty,
+) -> Result<Player> {
+	let bot = Bot::create(conn, persona, difficulty)?;
+	Profile::upsert(
+		conn,
+		&Profile {
+			display_name: Some(display_name.to_string()),
+			..Profile::empty(bot.user_id)
+		},
+	)?;
+
+	let player = Player::create(
+		conn,
+		NewPlayer {
+			session_id,
+			user_id: bot.user_id,
+			is_ready: true,
+			is_host: false,
+			seat,
+		},
+	)?;
+
+	Ok(player)
+}
+
+/// Players in the order they write in `round`, from `players` in seat order.
+/// A rotating starter goes by seat, so the rotation keeps its place when
+/// players leave: an empty seat's round goes to the next seat taken.
+fn round_order(players: &[Player], mode: TurnOrderMode, round: i32) -> Vec<&Player> {
+	let seat_count = players.iter().map(|p| p.seat + 1).max().unwrap_or(0);
+	let start = match mode {
+		TurnOrderMode::RotatingStarter if seat_count > 0 => {
+			let seat = (round - 1).max(0) % seat_count;
+			players.partition_point(|p| p.seat < seat) % players.len()
+		}
+		_ => 0,
+	};
+
+	players[start..].iter().chain(&players[..start]).collect()
+}
+
+/// Reads only the messages the session's visibility mode needs.
+/// The first player of a round also reads the narrator's interlude before it.
+fn visible_excerpt(conn: &mut PgConnection, session: &Session) -> Result<String> {
+	let last = match session.visibility {
+		VisibilityMode::Open => None,
+		_ => Message::get_last_by_session(conn, session.id).optional()?,
+	};
+	let contents = match session.visibility {
+		VisibilityMode::Blind => Vec::new(),
+		// Narration is part of the session's messages already
+		VisibilityMode::Open => Message::list_by_session(conn, session.id)?
+			.into_iter()
+			.map(|m| m.content)
+			.filter(|content| !content.is_empty())
+			.collect(),
+		_ => last.iter().map(|m| m.content.clone()).collect(),
+	};
+
+	let mut excerpt = visibility::excerpt(
+		session.visibility,
+		session.visibility_words as usize,
+		&contents,
+	);
+
+	if session.narrator != NarratorMode::Off
+		&& let Some(last) = last.filter(|m| m.round < session.current_round)
+		&& let Some(narration) =
+			Message::get_narration(conn, session.id, last.round)?
+	{
+		if !excerpt.is_empty() {
+			excerpt.push_str("\n\n");
+		}
+		excerpt.push_str(&narration.content);
+	}
+
+	Ok(excerpt)
+}
+
+/// The user's line in its undo window, the only one that can be changed.
+fn pending_message_of(session: &Session, user_id: Uuid) -> Result<Uuid> {
+	match session.pending_message_id {
+		Some(message_id)
+			if session.current_user_id_turn == Some(user_id)
+				&& !undo_window_closed(session) =>
+		{
+			Ok(message_id)
+		}
+		_ => Err(Error::NothingToUndo),
+	}
+}
+
+/// Whether the pending line is past its undo window.
+/// Lines pending without a deadline count as released.
+fn undo_window_closed(session: &Session) -> bool {
+	session
+		.pending_until
+		.is_none_or(|until| until <= Utc::now().naive_utc())
+}
+
+/// Fails with `StaleVersion` when the client acted on an outdated snapshot.
+fn ensure_version(session: &Session, expected_version: Option<i64>) -> Result<()> {
+	match expected_version {
+		Some(expected) if expected != session.version => Err(Error::StaleVersion),
+		_ => Ok(()),
+	}
+}
+
+/// How long until `deadline`, nothing once it passed.
+pub(crate) fn time_until(deadline: NaiveDateTime) -> Duration {
+	(deadline - Utc::now().naive_utc())
+		.to_std()
+		.unwrap_or_default()
+}
+
+/// Versioned updates report a concurrent change as `NotFound`.
+pub(crate) fn stale_on_not_found(err: diesel::result::Error) -> Error {
+	match err {
+		diesel::result::Error::NotFound => Error::StaleVersion,
+		other => other.into(),
+	}
 }
@@ -50,11 +50,88 @@ pub enum Error {
 	#[error("User is not in session")]
 	UserNotInSession,
 
+	#[error("Session state is stale, refresh and retry")]
+	StaleVersion,
+
+	#[error("Story variants must be between 1 and {0}")]
+	InvalidStoryVariants(i32),
+
+	#[error("Visible words must be between 1 and {0}")]
+	InvalidVisibilityWords(i32),
+
+	#[error("Story variants are only available in linear play mode")]
+	VariantsNeedLinearMode,
+
+	#[error("The narrator is only available in linear play mode")]
+	NarratorNeedsLinearMode,
+
+	#[error("Already written this round")]
+	AlreadySubmitted,
+
+	#[error("Undo window must be between 0 and {0} seconds")]
+	InvalidUndoWindow(i32),
+
+	#[error("The undo window is only available in linear play mode")]
+	UndoNeedsLinearMode,
+
+	#[error("Message must not be empty, pass the turn instead")]
+	EmptyMessage,
+
+	#[error("Waiting for the undo window of the last message to close")]
+	TurnPending,
+
+	#[error("No message to take back")]
+	NothingToUndo,
+
+	#[error("Seats can only be arranged in manual turn order")]
+	NotManualTurnOrder,
+
+	#[error("Seating must list every player once")]
+	InvalidSeating,
+
+	#[error("Bot persona must be 1 to {0} characters")]
+	InvalidBotPersona(usize),
+
+	#[error("Bot name must be 1 to {0} characters")]
+	InvalidBotName(usize),
+
+	#[error("Player is not a bot")]
+	NotABot,
+
+	#[error("At most {0} suggestions per turn")]
+	SuggestionLimitReached(i64),
+
+	#[error("Session is played without objectives")]
+	NoObjectives,
+
+	#[error("Session is not voting")]
+	NotVoting,
+
+	#[error("Story variant not found")]
+	VariantNotFound,
+
 	#[error("Unknown error occurred")]
 	Unknown,
 
 	#[error("AI generation error: {0}")]
 	AiGenerationError(String),
+
+	#[error("Database unavailable: {0}")]
+	DbUnavailable(String),
+
+	#[error("Blocking task failed: {0}")]
+	BlockingTaskFailed(String),
+}
+
+impl From<lib_core::model::error::Error> for Error {
+	fn from(ex: lib_core::model::error::Error) -> Self {
+		match ex {
+			lib_core::model::error::Error::Diesel(ex) => {
+				Error::DbError(Arc::new(ex))
+			}
+			other => Error::DbUnavailable(other.to_string()),
+		}
+	}
 }
 
 impl Error {
@@ -100,6 +177,90 @@ impl Error {
 				StatusCode::FORBIDDEN,
 				ClientError::GAME_ERROR(self.to_string()),
 			),
+			Error::StaleVersion => (
+				StatusCode::CONFLICT,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::InvalidStoryVariants(_) => (
+				StatusCode::BAD_REQUEST,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::InvalidVisibilityWords(_) => (
+				StatusCode::BAD_REQUEST,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::VariantsNeedLinearMode => (
+				StatusCode::BAD_REQUEST,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::NarratorNeedsLinearMode => (
+				StatusCode::BAD_REQUEST,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::AlreadySubmitted => (
+				StatusCode::CONFLICT,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::InvalidUndoWindow(_) => (
+				StatusCode::BAD_REQUEST,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::UndoNeedsLinearMode => (
+				StatusCode::BAD_REQUEST,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::EmptyMessage => (
+				StatusCode::BAD_REQUEST,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::NothingToUndo => (
+				StatusCode::BAD_REQUEST,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::TurnPending => (
+				StatusCode::CONFLICT,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::NotManualTurnOrder => (
+				StatusCode::BAD_REQUEST,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::InvalidSeating => (
+				StatusCode::BAD_REQUEST,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::InvalidBotPersona(_) => (
+				StatusCode::BAD_REQUEST,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::InvalidBotName(_) => (
+				StatusCode::BAD_REQUEST,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::NotABot => (
+				StatusCode::BAD_REQUEST,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::SuggestionLimitReached(_) => (
+				StatusCode::TOO_MANY_REQUESTS,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::NoObjectives => (
+				StatusCode::BAD_REQUEST,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::NotVoting => (
+				StatusCode::BAD_REQUEST,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::VariantNotFound => (
+				StatusCode::NOT_FOUND,
+				ClientError::GAME_ERROR(self.to_string()),
+			),
+			Error::DbUnavailable(_) => (
+				StatusCode::SERVICE_UNAVAILABLE,
+				ClientError::INTERNAL_SERVER_ERROR,
+			),
 			Error::Unknown => (
 				StatusCode::INTERNAL_SERVER_ERROR,
 				ClientError::INTERNAL_SERVER_ERROR,
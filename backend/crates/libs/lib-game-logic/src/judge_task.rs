@@ -0,0 +1,216 @@
+use std::{cmp::Reverse, sync::Arc};
+
+use diesel::{Connection, PgConnection};
+use lib_ai::{client::AiClient, models::GenerationConfig};
+use lib_core::{
+	dto::session::ObjectiveScoreDto,
+	model::{ModelManager, base::BasicDbOps},
+};
+use lib_game_events::{event::game::GameEvent, manager::GameEventsManager};
+use lib_messages::model::Message;
+use lib_players::objectives::model::PlayerObjective;
+use lib_profiles::model::Profile;
+use lib_sessions::model::Session;
+use rand::seq::SliceRandom;
+use tracing::{Instrument, error, info_span, warn};
+use uuid::Uuid;
+
+use crate::{
+	achievements::spawn_achievements_check,
+	error::{Error, Result},
+	story_generation_task::{chat_request, stream_chunks},
+};
+
+const JUDGE_INSTRUCTION: &str = "You judge a collaborative storytelling game. \
+	Every player had a secret objective to reach with their own lines. Decide \
+	for each numbered objective whether that player's lines achieve it. Reply \
+	only with a JSON array of the numbers of the objectives that were met, \
+	like [1, 3], or [] if none were.";
+
+/// Secret objectives handed out when a competitive game starts.
+pub const OBJECTIVES: [&str; 12] = [
+	"Get the word 'lighthouse' into the story.",
+	"Make a character betray someone.",
+	"Have it start raining.",
+	"Introduce a talking animal.",
+	"Make a character reveal a long-kept secret.",
+	"Get the word 'clockwork' into the story.",
+	"Have someone lose something important.",
+	"Bring in a character from the distant past.",
+	"Make two characters fall out with each other.",
+	"Have a character break a promise.",
+	"Get the word 'marmalade' into the story.",
+	"Make someone escape through a window.",
+];
+
+/// Points for a met objective.
+pub const OBJECTIVE_POINTS: i32 = 10;
+
+/// Hands every player a different objective while the catalogue lasts.
+pub(crate) fn assign_objectives(user_ids: &[Uuid]) -> Vec<(Uuid, &'static str)> {
+	let mut pool = OBJECTIVES.to_vec();
+	pool.shuffle(&mut rand::thread_rng());
+
+	user_ids
+		.iter()
+		.copied()
+		.zip(pool.into_iter().cycle())
+		.collect()
+}
+
+/// The judged objectives of a session, best score first.
+pub(crate) fn scoreboard(
+	conn: &mut PgConnection,
+	session_id: Uuid,
+) -> Result<Vec<ObjectiveScoreDto>> {
+	let objectives = PlayerObjective::list_by_session(conn, session_id)?;
+	let user_ids = objectives.iter().map(|o| o.user_id).collect::<Vec<_>>();
+	let mut names = Profile::display_names(conn, &user_ids)?;
+
+	let mut scores = objectives
+		.into_iter()
+		.filter_map(|o| {
+			Some(ObjectiveScoreDto {
+				user_id: o.user_id,
+				display_name: names.remove(&o.user_id).unwrap_or_default(),
+				objective: o.objective,
+				met: o.met?,
+				points: o.points,
+			})
+		})
+		.collect::<Vec<_>>();
+	scores.sort_by_key(|score| Reverse(score.points));
+
+	Ok(scores)
+}
+
+/// Spawn an async task that has the AI judge which secret objectives the
+/// finished story met, stores the points and sends the scoreboard.
+/// Does nothing for sessions played without objectives.
+/// Runs in a `judge` span, child of the caller's (request) span.
+pub(crate) fn spawn_judge_task(
+	session_id: Uuid,
+	model_manager: Arc<ModelManager>,
+	ai_client: Arc<AiClient>,
+	events: Arc<GameEventsManager>,
+) {
+	let span = info_span!("judge", %session_id);
+
+	tokio::spawn(
+		async move {
+			let loaded = model_manager
+				.run_blocking(move |conn| {
+					if !Session::get(conn, session_id)?.objectives {
+						return Ok(None);
+					}
+					Ok::<_, Error>(Some((
+						PlayerObjective::list_by_session(conn, session_id)?,
+						Message::list_by_session(conn, session_id)?,
+					)))
+				})
+				.await;
+
+			let (objectives, messages) = match loaded {
+				Ok(Some(loaded)) if !loaded.0.is_empty() => loaded,
+				Ok(_) => return,
+				Err(e) => {
+					error!("Failed to load objectives for judging: {:?}", e);
+					return;
+				}
+			};
+
+			let chat_req = chat_request(
+				JUDGE_INSTRUCTION.to_string(),
+				judge_prompt(&objectives, &messages),
+				Some(GenerationConfig { temperature: 0.0 }),
+			);
+			let met = match stream_chunks(&ai_client, chat_req, |_, _| {}).await {
+				Ok(verdict) => met_objectives(&verdict),
+				// Objectives stay unjudged, the story itself is already told
+				Err(e) => {
+					warn!("Judge generation failed: {:?}", e);
+					metrics::counter!("judge_failures_total").increment(1);
+					return;
+				}
+			};
+
+			let judged = model_manager
+				.run_blocking(move |conn| {
+					conn.transaction(|conn| {
+						for (index, objective) in objectives.iter().enumerate() {
+							let met = met.contains(&(index + 1));
+							let points = if met { OBJECTIVE_POINTS } else { 0 };
+							PlayerObjective::judge(
+								conn,
+								session_id,
+								objective.user_id,
+								met,
+								points,
+							)?;
+						}
+						let version = Session::get(conn, session_id)?.version;
+						Ok::<_, Error>((version, scoreboard(conn, session_id)?))
+					})
+				})
+				.await;
+
+			match judged {
+				Ok((version, scores)) => {
+					events.send_game_event(
+						session_id,
+						None,
+						version,
+						GameEvent::Scoreboard { scores },
+					);
+					// Points may have unlocked objective achievements
+					spawn_achievements_check(
+						session_id,
+						version,
+						model_manager,
+						events,
+					);
+				}
+				Err(e) => error!("Failed to store objective scores: {:?}", e),
+			}
+		}
+		.instrument(span),
+	);
+}
+
+/// Players' lines tagged with their number, then the numbered objectives.
+fn judge_prompt(objectives: &[PlayerObjective], messages: &[Message]) -> String {
+	let mut prompt = String::from("The story, line by line:\n");
+	for message in messages.iter().filter(|m| !m.content.is_empty()) {
+		let number = objectives
+			.iter()
+			.position(|o| Some(o.user_id) == message.user_id);
+		match number {
+			Some(index) => prompt.push_str(&format!("[Player {}] ", index + 1)),
+			None => prompt.push_str("[Other] "),
+		}
+		prompt.push_str(&message.content);
+		prompt.push('\n');
+	}
+
+	prompt.push_str("\nSecret objectives:\n");
+	for (index, objective) in objectives.iter().enumerate() {
+		prompt.push_str(&format!("{}. {}\n", index + 1, objective.objective));
+	}
+
+	prompt
+}
+
+/// Numbers of the met objectives in the judge's reply, none if unreadable.
+fn met_objectives(verdict: &str) -> Vec<usize> {
+	let (Some(start), Some(end)) = (verdict.find('['), verdict.rfind(']')) else {
+		return Vec::new();
+	};
+	if end < start {
+		return Vec::new();
+	}
+
+	serde_json::from_str(&verdict[start..=end]).unwrap_or_else(|e| {
+		warn!("Unreadable judge verdict: {:?}", e);
+		Vec::new()
+	})
+}
@@ -1,3 +1,12 @@
+pub mod achievements;
+pub mod bot_turn_task;
 pub mod engine;
 pub mod error;
+pub mod judge_task;
+pub mod narrator_task;
 pub mod story_generation_task;
+pub mod suggestion_task;
+pub mod thread_stories_task;
+pub mod turn_release_task;
+pub mod visibility;
+pub mod voting;
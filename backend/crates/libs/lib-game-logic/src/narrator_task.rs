@@ -0,0 +1,136 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use lib_core::model::{ModelManager, schema_enums::NarratorMode};
+use lib_game_events::event::game::GameEvent;
+use lib_messages::model::Message;
+use lib_sessions::model::Session;
+use tracing::{Instrument, Span, error, info_span, warn};
+
+use crate::{
+	engine::{GameEngine, time_until},
+	error::Error,
+	story_generation_task::{chat_request, prompt_from, stream_chunks},
+};
+
+const NARRATOR_INSTRUCTION: &str = "You are the narrator of a story written by \
+	several players in turns. Write one short paragraph that leads from the round \
+	that just ended into the next one. Don't end the story, leave the players room \
+	to continue it.";
+
+const TWIST_INSTRUCTION: &str =
+	"Introduce one unexpected plot twist the players have to deal with.";
+
+/// Seconds the narrator has to write, the game goes on without the
+/// interlude after that.
+pub const NARRATION_TIMEOUT_SECS: i64 = 90;
+
+/// Spawn an async task that streams the narrator's interlude after the
+/// round before `session`'s current one, then hands the turn on.
+/// The turn moves on by the session's narration deadline, whether the
+/// interlude was written, failed or is still streaming.
+/// Runs in a `narrator` span, child of the caller's (request) span.
+pub(crate) fn spawn_narrator_task(
+	engine: GameEngine,
+	model_manager: Arc<ModelManager>,
+	session: Session,
+) {
+	let session_id = session.id;
+	let round = session.current_round - 1;
+	let ends_at = session
+		.narration_ends_at
+		.unwrap_or_else(|| Utc::now().naive_utc());
+	let span = info_span!("narrator", %session_id, round);
+
+	tokio::spawn(
+		async move {
+			let mut generation = tokio::spawn(
+				narrate(engine.clone(), model_manager, session).in_current_span(),
+			);
+			let narration =
+				match tokio::time::timeout(time_until(ends_at), &mut generation)
+					.await
+				{
+					Ok(Ok(narration)) => narration,
+					// The game goes on without the interlude
+					Ok(Err(e)) => {
+						error!("Narrator generation panicked: {:?}", e);
+						metrics::counter!("narrator_failures_total").increment(1);
+						None
+					}
+					Err(_) => {
+						generation.abort();
+						warn!("Narrator ran past its deadline");
+						metrics::counter!("narrator_failures_total").increment(1);
+						None
+					}
+				};
+
+			let span = Span::current();
+			let finished = tokio::task::spawn_blocking(move || {
+				span.in_scope(|| {
+					engine.finish_narration(session_id, round, narration)
+				})
+			})
+			.await;
+
+			match finished {
+				Ok(Ok(())) => {}
+				Ok(Err(e)) => error!("Failed to resume after narration: {:?}", e),
+				Err(e) => error!("Narrator task failed: {:?}", e),
+			}
+		}
+		.instrument(span),
+	);
+}
+
+/// Streams the interlude to the players, `None` when nothing was written.
+async fn narrate(
+	engine: GameEngine,
+	model_manager: Arc<ModelManager>,
+	session: Session,
+) -> Option<String> {
+	let session_id = session.id;
+	let round = session.current_round - 1;
+
+	let messages = model_manager
+		.run_blocking(move |conn| {
+			Message::list_by_round(conn, session_id, round).map_err(Error::from)
+		})
+		.await
+		.unwrap_or_else(|e| {
+			error!("Failed to load messages for narration: {:?}", e);
+			Vec::new()
+		});
+
+	let mut instruction = NARRATOR_INSTRUCTION.to_string();
+	if session.narrator == NarratorMode::Twists {
+		instruction.push(' ');
+		instruction.push_str(TWIST_INSTRUCTION);
+	}
+	let prompt = format!("Theme: {}\n{}", session.theme, prompt_from(&messages));
+
+	let events = engine.game_events_manager().clone();
+	match stream_chunks(
+		&engine.ai_client,
+		chat_request(instruction, prompt, None),
+		|seq, chunk| {
+			events.send_game_event(
+				session_id,
+				None,
+				session.version,
+				GameEvent::NarratorChunk { round, seq, chunk },
+			);
+		},
+	)
+	.await
+	{
+		Ok(text) if !text.trim().is_empty() => Some(text.trim().to_string()),
+		Ok(_) => None,
+		Err(e) => {
+			warn!("Narrator generation failed: {:?}", e);
+			metrics::counter!("narrator_failures_total").increment(1);
+			None
+		}
+	}
+}
@@ -1,166 +1,439 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Instant};
 
 use lib_ai::{
 	client::{AiClient, GenerationGuard},
-	models::{ChatRequest, Content, Part, SystemInstruction},
+	models::{ChatRequest, Content, GenerationConfig, Part, SystemInstruction},
 };
 use lib_core::model::{ModelManager, base::BasicDbOps, schema_enums::SessionStatus};
 use lib_game_events::{event::game::GameEvent, manager::GameEventsManager};
 use lib_messages::model::Message;
 use lib_sessions::model::Session;
-use lib_stories::model::{NewStory, Story};
+use lib_stories::{
+	model::{NewStory, Story},
+	variants::model::{NewStoryVariant, StoryVariant},
+};
+use tracing::{Instrument, Span, error, info_span};
 use uuid::Uuid;
 
+use crate::{
+	achievements::spawn_achievements_check,
+	engine::stale_on_not_found,
+	error::Error,
+	judge_task::spawn_judge_task,
+	voting::{finish_voting, spawn_voting_timeout, start_voting},
+};
+
+pub(crate) const STORYTELLER_INSTRUCTION: &str =
+	"You are a storyteller... Collect all player messages into a story.";
+
+/// Tone and sampling temperature of a story variant.
+pub struct VariantStyle {
+	pub name: &'static str,
+	pub instruction: &'static str,
+	pub temperature: f32,
+}
+
+/// Variant `i` of a session uses style `i`.
+pub const VARIANT_STYLES: [VariantStyle; 5] = [
+	VariantStyle {
+		name: "classic",
+		instruction: "Tell it as a classic, well-paced tale.",
+		temperature: 0.7,
+	},
+	VariantStyle {
+		name: "humorous",
+		instruction: "Lean into humour and comic timing.",
+		temperature: 0.9,
+	},
+	VariantStyle {
+		name: "dark",
+		instruction: "Give it a darker, suspenseful tone.",
+		temperature: 0.8,
+	},
+	VariantStyle {
+		name: "poetic",
+		instruction: "Use lyrical, poetic prose.",
+		temperature: 1.0,
+	},
+	VariantStyle {
+		name: "cinematic",
+		instruction: "Write it like a fast-cut film scene.",
+		temperature: 1.1,
+	},
+];
+
+pub const MAX_STORY_VARIANTS: i32 = VARIANT_STYLES.len() as i32;
+
 /// Spawn an async task that collects messages, streams generation via AI client,
 /// relays chunks to events, saves the story and finalizes the session.
+/// With several `story_variants` they are generated one after another
+/// under the same guard, and players vote on them instead.
+/// Runs in a `story_generation` span, child of the caller's (request) span.
 pub fn spawn_story_generation_task(
 	session_id: Uuid,
+	session_version: i64,
+	story_variants: i32,
 	generation_guard: GenerationGuard, // use whatever concrete guard type your ai_client returns
 	model_manager: Arc<ModelManager>,
 	ai_client: Arc<AiClient>,
 	events: Arc<GameEventsManager>,
 ) {
+	let span = info_span!("story_generation", %session_id);
+
 	// Clone everything needed into the task
-	tokio::spawn(async move {
-		// Ensure the guard is held inside the task's scope
-		let _guard = generation_guard;
-
-		let model_manager_clone = model_manager.clone();
-
-		// Step A: build prompt from messages in blocking thread
-		let prompt = tokio::task::spawn_blocking(move || {
-			let mut conn = model_manager.db();
-			match Message::list_by_session(&mut conn, session_id) {
-				Ok(msgs) => {
-					let mut p = String::from("User messages::\n");
-					for m in msgs {
-						p.push_str(&format!("- {}\n", m.content));
-					}
-					p
-				}
-				Err(e) => {
-					eprintln!("Failed to load messages for generation: {:?}", e);
+	tokio::spawn(
+		async move {
+			// Ensure the guard is held inside the task's scope
+			let _guard = generation_guard;
+			let started_at = Instant::now();
+
+			// Step A: build prompt from messages in blocking thread
+			let prompt = model_manager
+				.run_blocking(move |conn| {
+					Message::list_by_session(conn, session_id).map_err(Error::from)
+				})
+				.await
+				.map(|msgs| prompt_from(&msgs))
+				.unwrap_or_else(|e| {
+					error!("Failed to load messages for generation: {:?}", e);
 					String::new()
+				});
+
+			if story_variants > 1 {
+				let variants = generate_variants(
+					session_id,
+					session_version,
+					story_variants,
+					prompt,
+					&model_manager,
+					&ai_client,
+					&events,
+				)
+				.await;
+
+				if !variants.is_empty() {
+					metrics::histogram!("story_generation_duration_seconds")
+						.record(started_at.elapsed().as_secs_f64());
 				}
+
+				open_vote(
+					session_id,
+					session_version,
+					variants,
+					model_manager,
+					ai_client,
+					events,
+				)
+				.await;
+				return;
 			}
-		})
-		.await
-		.unwrap_or_default();
-
-		// Build chat request
-		let chat_req = ChatRequest {
-                system_instruction: Some(SystemInstruction {
-                    parts: vec![Part {
-                        text: "You are a storyteller... Collect all player messages into a story.".into(),
-                    }],
-                }),
-                contents: vec![Content {
-                    role: "user".into(),
-                    parts: vec![Part { text: prompt }],
-                }],
-            };
-
-		// Step B: stream generation from ai_client
-		match ai_client.stream_generate_channel(chat_req).await {
-			Ok(mut rx) => {
-				let mut seq = 0u64;
-				let mut full = String::new();
-
-				while let Some(item) = rx.recv().await {
-					match item {
-						Ok(chunk) => {
-							seq += 1;
-							full.push_str(&chunk);
-							// send chunk event to all clients
-							events.send_game_event(
-								session_id,
-								None,
-								GameEvent::StoryChunk { seq, chunk },
-							);
-						}
-						Err(e) => {
-							events.send_game_event(
-								session_id,
-								None,
-								GameEvent::StoryComplete {
-									story_id: Uuid::new_v4(),
-									full_text: format!("Generation error: {:?}", e),
-								},
-							);
-							return;
-						}
-					}
-				}
 
-				// Step C: persist full story in blocking thread
-				let full_clone = full.clone();
-				let mm_for_save = model_manager_clone.clone();
-				let events_for_finish = events.clone();
-				tokio::task::spawn_blocking(move || {
-					let mut conn = mm_for_save.db();
-					let new_story = NewStory {
-						session_id,
-						content: &full_clone,
+			let chat_req =
+				chat_request(STORYTELLER_INSTRUCTION.to_string(), prompt, None);
+
+			// Step B: stream generation from ai_client
+			let mut streamed = false;
+			let full = match stream_chunks(&ai_client, chat_req, |seq, chunk| {
+				streamed = true;
+				// send chunk event to all clients
+				events.send_game_event(
+					session_id,
+					None,
+					session_version,
+					GameEvent::StoryChunk { seq, chunk },
+				);
+			})
+			.await
+			{
+				Ok(full) => full,
+				Err(e) => {
+					let full_text = if streamed {
+						record_generation_failure("stream");
+						format!("Generation error: {:?}", e)
+					} else {
+						record_generation_failure("start");
+						format!("Failed to start generation: {:?}", e)
 					};
+					events.send_game_event(
+						session_id,
+						None,
+						session_version,
+						GameEvent::StoryComplete {
+							story_id: Uuid::new_v4(),
+							full_text,
+						},
+					);
+					return;
+				}
+			};
+
+			// Step C: persist full story in blocking thread
+			let mm_for_save = model_manager.clone();
+			let events_for_finish = events.clone();
+			let ai_for_judge = ai_client.clone();
+			let span = Span::current();
+			tokio::task::spawn_blocking(move || {
+				let _entered = span.enter();
+				let new_story = NewStory {
+					session_id,
+					content: &full,
+					thread_id: None,
+				};
+
+				let created =
+					mm_for_save.db().map_err(Error::from).and_then(|mut conn| {
+						Story::create(&mut conn, new_story).map_err(Error::from)
+					});
+
+				match created {
+					Ok(story) => {
+						metrics::histogram!("story_generation_duration_seconds")
+							.record(started_at.elapsed().as_secs_f64());
 
-					match Story::create(&mut conn, new_story) {
-						Ok(story) => {
-							// notify clients about final story
-							events_for_finish.send_game_event(
-								session_id,
-								None,
-								GameEvent::StoryComplete {
-									story_id: story.id,
-									full_text: full_clone.clone(),
-								},
-							);
-
-							// finish session in DB
-							let mut conn2 = mm_for_save.db();
-							if let Ok(mut session) =
-								Session::get(&mut conn2, session_id)
-							{
+						// notify clients about final story
+						events_for_finish.send_game_event(
+							session_id,
+							None,
+							session_version,
+							GameEvent::StoryComplete {
+								story_id: story.id,
+								full_text: full.clone(),
+							},
+						);
+
+						// finish session in DB
+						let finished = mm_for_save
+							.db()
+							.map_err(Error::from)
+							.and_then(|mut conn| {
+								let mut session =
+									Session::get(&mut conn, session_id)?;
 								session.status = SessionStatus::Finished;
 								session.current_user_id_turn = None;
-								let _ = Session::update(
-									&mut conn2, session.id, &session,
-								);
+								Session::update_versioned(&mut conn, &session)
+									.map_err(stale_on_not_found)
+							});
+						let finished_version = match finished {
+							Ok(session) => session.version,
+							Err(e) => {
+								error!("Failed to finish session: {:?}", e);
+								return;
 							}
+						};
+
+						// and notify that game finished
+						events_for_finish.send_game_event(
+							session_id,
+							None,
+							finished_version,
+							GameEvent::GameFinished,
+						);
 
-							// and notify that game finished
-							events_for_finish.send_game_event(
-								session_id,
-								None,
-								GameEvent::GameFinished,
-							);
-						}
-						Err(e) => {
-							eprintln!("Failed to save story: {:?}", e);
-							events_for_finish.send_game_event(
-								session_id,
-								None,
-								GameEvent::StoryComplete {
-									story_id: Uuid::new_v4(),
-									full_text: format!(
-										"Failed to save story: {:?}",
-										e
-									),
-								},
-							);
-						}
+						spawn_achievements_check(
+							session_id,
+							finished_version,
+							mm_for_save.clone(),
+							events_for_finish.clone(),
+						);
+						spawn_judge_task(
+							session_id,
+							mm_for_save,
+							ai_for_judge,
+							events_for_finish,
+						);
 					}
-				});
-			}
+					Err(e) => {
+						record_generation_failure("save");
+						error!("Failed to save story: {:?}", e);
+						events_for_finish.send_game_event(
+							session_id,
+							None,
+							session_version,
+							GameEvent::StoryComplete {
+								story_id: Uuid::new_v4(),
+								full_text: format!("Failed to save story: {:?}", e),
+							},
+						);
+					}
+				}
+			});
+		}
+		.instrument(span),
+	);
+}
+
+/// Generates and saves each variant, failed ones are skipped.
+async fn generate_variants(
+	session_id: Uuid,
+	session_version: i64,
+	count: i32,
+	prompt: String,
+	model_manager: &ModelManager,
+	ai_client: &AiClient,
+	events: &GameEventsManager,
+) -> Vec<StoryVariant> {
+	let mut variants = Vec::new();
+
+	for (variant_index, style) in
+		(0..).zip(VARIANT_STYLES.iter().take(count as usize))
+	{
+		let chat_req = chat_request(
+			format!("{STORYTELLER_INSTRUCTION} {}", style.instruction),
+			prompt.clone(),
+			Some(GenerationConfig {
+				temperature: style.temperature,
+			}),
+		);
+
+		let content = match stream_chunks(ai_client, chat_req, |seq, chunk| {
+			events.send_game_event(
+				session_id,
+				None,
+				session_version,
+				GameEvent::VariantChunk {
+					variant_index,
+					seq,
+					chunk,
+				},
+			)
+		})
+		.await
+		{
+			Ok(content) => content,
 			Err(e) => {
-				events.send_game_event(
-					session_id,
-					None,
-					GameEvent::StoryComplete {
-						story_id: Uuid::new_v4(),
-						full_text: format!("Failed to start generation: {:?}", e),
+				record_generation_failure("stream");
+				error!("Failed to generate variant {variant_index}: {:?}", e);
+				continue;
+			}
+		};
+
+		let saved = model_manager
+			.run_blocking(move |conn| {
+				StoryVariant::create(
+					conn,
+					NewStoryVariant {
+						session_id,
+						variant_index,
+						style: style.name,
+						temperature: style.temperature,
+						content: &content,
 					},
-				);
+				)
+				.map_err(Error::from)
+			})
+			.await;
+
+		match saved {
+			Ok(variant) => variants.push(variant),
+			Err(e) => {
+				record_generation_failure("save");
+				error!("Failed to save variant {variant_index}: {:?}", e);
 			}
 		}
-	});
+	}
+
+	variants
+}
+
+/// Opens the vote on the generated variants.
+/// A single variant wins right away, none at all reports a failed story.
+async fn open_vote(
+	session_id: Uuid,
+	session_version: i64,
+	variants: Vec<StoryVariant>,
+	model_manager: Arc<ModelManager>,
+	ai_client: Arc<AiClient>,
+	events: Arc<GameEventsManager>,
+) {
+	if variants.is_empty() {
+		events.send_game_event(
+			session_id,
+			None,
+			session_version,
+			GameEvent::StoryComplete {
+				story_id: Uuid::new_v4(),
+				full_text: "Failed to generate story variants".to_string(),
+			},
+		);
+		return;
+	}
+
+	let only_one = variants.len() == 1;
+	let events_for_vote = events.clone();
+	let started = model_manager
+		.run_blocking(move |conn| {
+			let ends_at =
+				start_voting(conn, &events_for_vote, session_id, &variants)?;
+			let finished =
+				only_one && finish_voting(conn, &events_for_vote, session_id, true)?;
+			Ok::<_, Error>((ends_at, finished))
+		})
+		.await;
+
+	match started {
+		Ok((_, true)) => {
+			spawn_judge_task(session_id, model_manager, ai_client, events)
+		}
+		Ok((ends_at, false)) if !only_one => spawn_voting_timeout(
+			session_id,
+			ends_at,
+			model_manager,
+			ai_client,
+			events,
+		),
+		Ok(_) => {}
+		Err(e) => error!("Failed to start voting: {:?}", e),
+	}
+}
+
+/// Streams a generation, handing each chunk to `on_chunk` with its
+/// sequence number, and returns the full text.
+pub(crate) async fn stream_chunks(
+	ai_client: &AiClient,
+	chat_req: ChatRequest,
+	mut on_chunk: impl FnMut(u64, String),
+) -> lib_ai::error::Result<String> {
+	let mut rx = ai_client.stream_generate_channel(chat_req).await?;
+	let mut seq = 0u64;
+	let mut full = String::new();
+
+	while let Some(item) = rx.recv().await {
+		let chunk = item?;
+		seq += 1;
+		full.push_str(&chunk);
+		on_chunk(seq, chunk);
+	}
+
+	Ok(full)
+}
+
+/// Lists the messages for the storyteller, in play order.
+pub(crate) fn prompt_from(messages: &[Message]) -> String {
+	let mut p = String::from("User messages::\n");
+	for m in messages.iter().filter(|m| !m.content.is_empty()) {
+		p.push_str(&format!("- {}\n", m.content));
+	}
+	p
+}
+
+pub(crate) fn chat_request(
+	instruction: String,
+	prompt: String,
+	generation_config: Option<GenerationConfig>,
+) -> ChatRequest {
+	ChatRequest {
+		system_instruction: Some(SystemInstruction {
+			parts: vec![Part { text: instruction }],
+		}),
+		contents: vec![Content {
+			role: "user".into(),
+			parts: vec![Part { text: prompt }],
+		}],
+		generation_config,
+	}
+}
+
+pub(crate) fn record_generation_failure(stage: &'static str) {
+	metrics::counter!("story_generation_failures_total", "stage" => stage)
+		.increment(1);
 }
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use lib_ai::{client::AiClient, models::GenerationConfig};
+use lib_game_events::{
+	event::game::{GameEvent, GameEventReceiver},
+	manager::GameEventsManager,
+};
+use lib_sessions::model::Session;
+use tracing::{Instrument, info_span, warn};
+use uuid::Uuid;
+
+use crate::story_generation_task::{chat_request, stream_chunks};
+
+const SUGGESTION_INSTRUCTION: &str = "You help a player of a collaborative \
+	storytelling game who is stuck on their turn. Offer three short, different \
+	ideas for how they could continue the story, one per line, each under 20 \
+	words. Reply with the ideas only.";
+
+/// Times a player may ask for suggestions on one turn.
+pub const MAX_SUGGESTIONS_PER_TURN: i64 = 2;
+
+/// Ideas kept from a reply, the model may offer more than asked for.
+const MAX_IDEAS: usize = 3;
+
+/// Spawn an async task that streams writing ideas from the `excerpt` the
+/// player may see to that player alone.
+/// Runs in a `suggestions` span, child of the caller's (request) span.
+pub(crate) fn spawn_suggestion_task(
+	events: Arc<GameEventsManager>,
+	ai_client: Arc<AiClient>,
+	session: Session,
+	user_id: Uuid,
+	excerpt: String,
+) {
+	let span = info_span!("suggestions", session_id = %session.id, %user_id);
+
+	tokio::spawn(
+		async move {
+			let prompt = if excerpt.trim().is_empty() {
+				format!(
+					"Theme: {}\nThe player can't see any of the story.",
+					session.theme
+				)
+			} else {
+				format!(
+					"Theme: {}\nWhat the player can see of the story:\n{excerpt}",
+					session.theme
+				)
+			};
+			let chat_req = chat_request(
+				SUGGESTION_INSTRUCTION.to_string(),
+				prompt,
+				Some(GenerationConfig { temperature: 0.9 }),
+			);
+
+			let result = stream_chunks(&ai_client, chat_req, |seq, chunk| {
+				events.send_game_event(
+					session.id,
+					Some(GameEventReceiver { user_id }),
+					session.version,
+					GameEvent::SuggestionChunk { seq, chunk },
+				);
+			})
+			.await;
+
+			let event = match result {
+				Ok(text) => GameEvent::SuggestionsComplete {
+					suggestions: ideas_from(&text),
+				},
+				Err(e) => {
+					warn!("Suggestion generation failed: {:?}", e);
+					metrics::counter!("suggestion_failures_total").increment(1);
+					GameEvent::Error {
+						message: "Suggestions are unavailable right now".to_string(),
+					}
+				}
+			};
+
+			events.send_game_event(
+				session.id,
+				Some(GameEventReceiver { user_id }),
+				session.version,
+				event,
+			);
+		}
+		.instrument(span),
+	);
+}
+
+/// One idea per line, without list markers.
+fn ideas_from(text: &str) -> Vec<String> {
+	text.lines()
+		.map(|line| {
+			line.trim_start_matches(|c: char| {
+				c.is_ascii_digit() || matches!(c, '.' | ')' | '-' | '*' | ' ')
+			})
+			.trim()
+		})
+		.filter(|line| !line.is_empty())
+		.take(MAX_IDEAS)
+		.map(str::to_string)
+		.collect()
+}
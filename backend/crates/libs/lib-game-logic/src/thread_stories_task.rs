@@ -0,0 +1,190 @@
+use std::{sync::Arc, time::Instant};
+
+use lib_ai::client::{AiClient, GenerationGuard};
+use lib_core::model::{ModelManager, base::BasicDbOps, schema_enums::SessionStatus};
+use lib_game_events::{event::game::GameEvent, manager::GameEventsManager};
+use lib_messages::{model::Message, threads::model::Thread};
+use lib_sessions::model::Session;
+use lib_stories::model::{NewStory, Story};
+use tracing::{Instrument, error, info_span};
+use uuid::Uuid;
+
+use crate::{
+	achievements::spawn_achievements_check,
+	engine::stale_on_not_found,
+	error::{Error, Result},
+	judge_task::spawn_judge_task,
+	story_generation_task::{
+		STORYTELLER_INSTRUCTION, chat_request, prompt_from,
+		record_generation_failure, stream_chunks,
+	},
+};
+
+/// Spawns the storytelling of a rotating session: one story per thread,
+/// told one after another under the same guard. The session finishes once
+/// at least one thread got its story.
+/// Runs in a `thread_stories` span, child of the caller's (request) span.
+pub fn spawn_thread_stories_task(
+	session_id: Uuid,
+	session_version: i64,
+	generation_guard: GenerationGuard,
+	model_manager: Arc<ModelManager>,
+	ai_client: Arc<AiClient>,
+	events: Arc<GameEventsManager>,
+) {
+	let span = info_span!("thread_stories", %session_id);
+
+	tokio::spawn(
+		async move {
+			let _guard = generation_guard;
+			let started_at = Instant::now();
+
+			let threads = model_manager
+				.run_blocking(move |conn| {
+					Thread::list_by_session(conn, session_id)?
+						.into_iter()
+						.map(|thread| {
+							Ok((
+								thread.id,
+								Message::list_by_thread(conn, thread.id)?,
+							))
+						})
+						.collect::<Result<Vec<_>>>()
+				})
+				.await;
+
+			let threads = match threads {
+				Ok(threads) => threads,
+				Err(e) => {
+					error!("Failed to load threads for generation: {:?}", e);
+					return;
+				}
+			};
+
+			let mut told = 0;
+			for (thread_id, messages) in threads {
+				if messages.is_empty() {
+					continue;
+				}
+
+				let event = match tell_thread(
+					session_id,
+					session_version,
+					thread_id,
+					&messages,
+					&model_manager,
+					&ai_client,
+					&events,
+				)
+				.await
+				{
+					Ok(story) => {
+						told += 1;
+						GameEvent::ThreadStoryComplete {
+							thread_id,
+							story_id: story.id,
+							full_text: story.content,
+						}
+					}
+					Err(e) => {
+						error!("Failed to tell thread {thread_id}: {:?}", e);
+						GameEvent::ThreadStoryComplete {
+							thread_id,
+							story_id: Uuid::new_v4(),
+							full_text: format!("Generation error: {:?}", e),
+						}
+					}
+				};
+
+				events.send_game_event(session_id, None, session_version, event);
+			}
+
+			if told == 0 {
+				return;
+			}
+
+			metrics::histogram!("story_generation_duration_seconds")
+				.record(started_at.elapsed().as_secs_f64());
+
+			let finished = model_manager
+				.run_blocking(move |conn| {
+					let mut session = Session::get(conn, session_id)?;
+					session.status = SessionStatus::Finished;
+					session.current_user_id_turn = None;
+					Session::update_versioned(conn, &session)
+						.map_err(stale_on_not_found)
+				})
+				.await;
+
+			match finished {
+				Ok(session) => {
+					events.send_game_event(
+						session_id,
+						None,
+						session.version,
+						GameEvent::GameFinished,
+					);
+					spawn_achievements_check(
+						session_id,
+						session.version,
+						model_manager.clone(),
+						events.clone(),
+					);
+					spawn_judge_task(session_id, model_manager, ai_client, events);
+				}
+				Err(e) => error!("Failed to finish session: {:?}", e),
+			}
+		}
+		.instrument(span),
+	);
+}
+
+/// Streams the thread's story as `ThreadStoryChunk` events and saves it.
+async fn tell_thread(
+	session_id: Uuid,
+	session_version: i64,
+	thread_id: Uuid,
+	messages: &[Message],
+	model_manager: &ModelManager,
+	ai_client: &AiClient,
+	events: &GameEventsManager,
+) -> Result<Story> {
+	let chat_req = chat_request(
+		STORYTELLER_INSTRUCTION.to_string(),
+		prompt_from(messages),
+		None,
+	);
+
+	let content = stream_chunks(ai_client, chat_req, |seq, chunk| {
+		events.send_game_event(
+			session_id,
+			None,
+			session_version,
+			GameEvent::ThreadStoryChunk {
+				thread_id,
+				seq,
+				chunk,
+			},
+		)
+	})
+	.await
+	.map_err(|e| {
+		record_generation_failure("stream");
+		Error::AiGenerationError(e.to_string())
+	})?;
+
+	model_manager
+		.run_blocking(move |conn| {
+			Story::create(
+				conn,
+				NewStory {
+					session_id,
+					content: &content,
+					thread_id: Some(thread_id),
+				},
+			)
+			.map_err(Error::from)
+		})
+		.await
+		.inspect_err(|_| record_generation_failure("save"))
+}
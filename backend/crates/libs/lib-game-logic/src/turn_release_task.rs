@@ -0,0 +1,40 @@
+use chrono::NaiveDateTime;
+use tracing::{Instrument, Span, error, info_span};
+use uuid::Uuid;
+
+use crate::engine::{GameEngine, time_until};
+
+/// Longest undo window a host can set, in seconds.
+pub const MAX_UNDO_WINDOW_SECS: i32 = 30;
+
+/// Spawn an async task that hands the turn on once the undo window of
+/// `message_id` closed at `until`, unless the message was taken back
+/// meanwhile. Right away when `until` passed already (e.g. during a restart).
+/// Runs in a `turn_release` span, child of the caller's (request) span.
+pub(crate) fn spawn_turn_release_task(
+	engine: GameEngine,
+	session_id: Uuid,
+	message_id: Uuid,
+	until: NaiveDateTime,
+) {
+	let span = info_span!("turn_release", %session_id, %message_id);
+
+	tokio::spawn(
+		async move {
+			tokio::time::sleep(time_until(until)).await;
+
+			let span = Span::current();
+			let released = tokio::task::spawn_blocking(move || {
+				span.in_scope(|| engine.release_turn(session_id, message_id))
+			})
+			.await;
+
+			match released {
+				Ok(Ok(())) => {}
+				Ok(Err(e)) => error!("Failed to hand the turn on: {:?}", e),
+				Err(e) => error!("Turn release task failed: {:?}", e),
+			}
+		}
+		.instrument(span),
+	);
+}
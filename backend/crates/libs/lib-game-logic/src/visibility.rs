@@ -0,0 +1,38 @@
+use lib_core::model::schema_enums::VisibilityMode;
+
+pub const MAX_VISIBILITY_WORDS: i32 = 100;
+
+const SENTENCE_ENDS: [char; 4] = ['.', '!', '?', '…'];
+
+/// What the player on turn sees of the story so far.
+/// `contents` are the messages in play order.
+pub fn excerpt(mode: VisibilityMode, words: usize, contents: &[String]) -> String {
+	let last = contents.last().map(String::as_str).unwrap_or_default();
+
+	match mode {
+		VisibilityMode::Previous => last.to_string(),
+		VisibilityMode::LastWords => last_words(last, words),
+		VisibilityMode::LastSentence => last_sentence(last).to_string(),
+		VisibilityMode::Open => contents.join("\n"),
+		VisibilityMode::Blind => String::new(),
+	}
+}
+
+fn last_words(text: &str, count: usize) -> String {
+	let words = text.split_whitespace().collect::<Vec<_>>();
+	words[words.len().saturating_sub(count)..].join(" ")
+}
+
+fn last_sentence(text: &str) -> &str {
+	let text = text.trim_end();
+	// Skip the closing punctuation of the last sentence itself
+	let body = text.trim_end_matches(SENTENCE_ENDS);
+
+	let start = body
+		.char_indices()
+		.rev()
+		.find(|(_, c)| SENTENCE_ENDS.contains(c))
+		.map_or(0, |(i, c)| i + c.len_utf8());
+
+	text[start..].trim_start()
+}
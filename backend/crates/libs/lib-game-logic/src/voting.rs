@@ -0,0 +1,200 @@
+use std::{collections::HashMap, sync::Arc};
+
+use chrono::{NaiveDateTime, Utc};
+use diesel::{Connection, PgConnection};
+use lib_ai::client::AiClient;
+use lib_core::{
+	config::core_config,
+	dto::story::{StoryVariantDto, VariantVotesDto},
+	model::{ModelManager, base::BasicDbOps, schema_enums::SessionStatus},
+};
+use lib_game_events::{event::game::GameEvent, manager::GameEventsManager};
+use lib_players::{bots::model::Bot, model::Player};
+use lib_sessions::model::Session;
+use lib_stories::{
+	model::{NewStory, Story},
+	variants::model::{StoryVariant, StoryVariantVote},
+};
+use tracing::{Instrument, Span, error};
+use uuid::Uuid;
+
+use crate::{
+	achievements::check_achievements,
+	engine::{stale_on_not_found, time_until},
+	error::{Error, Result},
+	judge_task::spawn_judge_task,
+};
+
+/// A forced close may race with a vote or a leave, retried this many times.
+const FORCE_CLOSE_ATTEMPTS: usize = 3;
+
+/// Moves the session to `Voting` and announces the variants.
+/// Returns when the vote closes.
+pub(crate) fn start_voting(
+	conn: &mut PgConnection,
+	events: &GameEventsManager,
+	session_id: Uuid,
+	variants: &[StoryVariant],
+) -> Result<NaiveDateTime> {
+	let timeout_secs = core_config().VOTING_TIMEOUT_SECS;
+	let ends_at =
+		Utc::now().naive_utc() + chrono::Duration::seconds(timeout_secs as i64);
+
+	let mut session = Session::get(conn, session_id)?;
+	session.status = SessionStatus::Voting;
+	session.current_user_id_turn = None;
+	session.voting_ends_at = Some(ends_at);
+
+	let session =
+		Session::update_versioned(conn, &session).map_err(stale_on_not_found)?;
+
+	events.send_game_event(
+		session_id,
+		None,
+		session.version,
+		GameEvent::VotingStarted {
+			variants: variants
+				.iter()
+				.cloned()
+				.map(StoryVariantDto::from)
+				.collect(),
+			timeout_secs,
+		},
+	);
+
+	Ok(ends_at)
+}
+
+/// Closes the vote once every remaining player voted, or right away when
+/// `force` is set. The variant with most votes wins, ties go to the
+/// earlier variant. Returns whether the vote was closed.
+pub(crate) fn finish_voting(
+	conn: &mut PgConnection,
+	events: &GameEventsManager,
+	session_id: Uuid,
+	force: bool,
+) -> Result<bool> {
+	let outcome = conn.transaction::<_, Error, _>(|conn| {
+		let mut session = Session::get(conn, session_id)?;
+		if session.status != SessionStatus::Voting {
+			return Ok(None);
+		}
+
+		// Bots don't vote
+		let bots = Bot::ids_in_session(conn, session_id)?;
+		let voters = Player::list_by_session(conn, session_id)?
+			.iter()
+			.filter(|p| !bots.contains(&p.user_id))
+			.count();
+		let cast = StoryVariantVote::count_by_session(conn, session_id)?;
+		if !force && (cast as usize) < voters {
+			return Ok(None);
+		}
+
+		let variants = StoryVariant::list_by_session(conn, session_id)?;
+		let tally: HashMap<Uuid, i64> = StoryVariantVote::tally(conn, session_id)?
+			.into_iter()
+			.collect();
+		let votes_for = |id: &Uuid| tally.get(id).copied().unwrap_or(0);
+
+		let winner = variants
+			.iter()
+			.min_by_key(|v| (-votes_for(&v.id), v.variant_index))
+			.ok_or(Error::VariantNotFound)?;
+
+		let story = Story::create(
+			conn,
+			NewStory {
+				session_id,
+				content: &winner.content,
+				thread_id: None,
+			},
+		)?;
+
+		session.status = SessionStatus::Finished;
+		session.voting_ends_at = None;
+		let session =
+			Session::update_versioned(conn, &session).map_err(stale_on_not_found)?;
+
+		let votes = variants
+			.iter()
+			.map(|v| VariantVotesDto {
+				variant_id: v.id,
+				votes: votes_for(&v.id),
+			})
+			.collect::<Vec<_>>();
+
+		Ok(Some((session.version, winner.id, story, votes)))
+	})?;
+
+	let Some((version, winner_variant_id, story, votes)) = outcome else {
+		return Ok(false);
+	};
+
+	events.send_game_event(
+		session_id,
+		None,
+		version,
+		GameEvent::VotingResult {
+			winner_variant_id,
+			story_id: story.id,
+			votes,
+		},
+	);
+
+	events.send_game_event(
+		session_id,
+		None,
+		version,
+		GameEvent::StoryComplete {
+			story_id: story.id,
+			full_text: story.content,
+		},
+	);
+
+	events.send_game_event(session_id, None, version, GameEvent::GameFinished);
+	check_achievements(conn, events, session_id, version);
+
+	Ok(true)
+}
+
+/// Closes the vote at `ends_at`, whoever has voted by then.
+/// Right away when the deadline passed already (e.g. during a restart).
+pub(crate) fn spawn_voting_timeout(
+	session_id: Uuid,
+	ends_at: NaiveDateTime,
+	model_manager: Arc<ModelManager>,
+	ai_client: Arc<AiClient>,
+	events: Arc<GameEventsManager>,
+) {
+	let span = Span::current();
+
+	tokio::spawn(
+		async move {
+			tokio::time::sleep(time_until(ends_at)).await;
+
+			for _ in 0..FORCE_CLOSE_ATTEMPTS {
+				let events_for_close = events.clone();
+				let result = model_manager
+					.run_blocking(move |conn| {
+						finish_voting(conn, &events_for_close, session_id, true)
+					})
+					.await;
+
+				match result {
+					Err(Error::StaleVersion) => continue,
+					Err(e) => error!("Failed to close voting: {:?}", e),
+					Ok(true) => spawn_judge_task(
+						session_id,
+						model_manager.clone(),
+						ai_client.clone(),
+						events.clone(),
+					),
+					Ok(false) => {}
+				}
+				break;
+			}
+		}
+		.instrument(span),
+	);
+}
@@ -52,22 +52,101 @@ impl BasicDbOps for Message {
 }
 
 impl Message {
+	/// Messages of the session in turn order.
 	pub fn list_by_session(
 		conn: &mut PgConnection,
 		session_id: Uuid,
 	) -> QueryResult<Vec<Self>> {
 		messages::table
 			.filter(messages::session_id.eq(session_id))
+			.order_by((
+				messages::round.asc(),
+				messages::turn_order.asc(),
+				messages::created_at.asc(),
+			))
 			.load(conn)
 	}
 
+	/// The latest message written by a player, narration and passes aside.
 	pub fn get_last_by_session(
 		conn: &mut PgConnection,
 		session_id: Uuid,
 	) -> QueryResult<Self> {
 		messages::table
 			.filter(messages::session_id.eq(session_id))
+			.filter(messages::user_id.is_not_null())
+			.filter(messages::content.ne(""))
 			.order_by(messages::created_at.desc())
 			.first(conn)
 	}
+
+	/// Messages of the thread in round order.
+	pub fn list_by_thread(
+		conn: &mut PgConnection,
+		thread_id: Uuid,
+	) -> QueryResult<Vec<Self>> {
+		messages::table
+			.filter(messages::thread_id.eq(thread_id))
+			.order_by((messages::round.asc(), messages::created_at.asc()))
+			.load(conn)
+	}
+
+	/// Users who wrote in the given round.
+	pub fn authors_in_round(
+		conn: &mut PgConnection,
+		session_id: Uuid,
+		round: i32,
+	) -> QueryResult<Vec<Uuid>> {
+		messages::table
+			.filter(messages::session_id.eq(session_id))
+			.filter(messages::round.eq(round))
+			.filter(messages::user_id.is_not_null())
+			.select(messages::user_id.assume_not_null())
+			.distinct()
+			.load(conn)
+	}
+
+	/// Seats written from in the given round, a seat keeps counting when a
+	/// bot takes it over.
+	pub fn seats_in_round(
+		conn: &mut PgConnection,
+		session_id: Uuid,
+		round: i32,
+	) -> QueryResult<Vec<i32>> {
+		messages::table
+			.filter(messages::session_id.eq(session_id))
+			.filter(messages::round.eq(round))
+			.filter(messages::user_id.is_not_null())
+			.select(messages::turn_order)
+			.distinct()
+			.load(conn)
+	}
+
+	/// Players' messages of the given round in turn order.
+	pub fn list_by_round(
+		conn: &mut PgConnection,
+		session_id: Uuid,
+		round: i32,
+	) -> QueryResult<Vec<Self>> {
+		messages::table
+			.filter(messages::session_id.eq(session_id))
+			.filter(messages::round.eq(round))
+			.filter(messages::user_id.is_not_null())
+			.order_by((messages::turn_order.asc(), messages::created_at.asc()))
+			.load(conn)
+	}
+
+	/// The narrator's interlude after the given round, if any.
+	pub fn get_narration(
+		conn: &mut PgConnection,
+		session_id: Uuid,
+		round: i32,
+	) -> QueryResult<Option<Self>> {
+		messages::table
+			.filter(messages::session_id.eq(session_id))
+			.filter(messages::round.eq(round))
+			.filter(messages::user_id.is_null())
+			.first(conn)
+			.optional()
+	}
 }
@@ -1,2 +1,4 @@
 pub mod db_ops;
 pub mod model;
+pub mod suggestions;
+pub mod threads;
@@ -3,24 +3,35 @@ use diesel::prelude::{AsChangeset, Insertable, Queryable};
 use lib_core::model::schema::messages;
 use uuid::Uuid;
 
+/// Turn order of the narrator's interlude, after every player of the round.
+pub const NARRATION_TURN_ORDER: i32 = i32::MAX;
+
 #[derive(Debug, Queryable, AsChangeset)]
 #[diesel(table_name = messages)]
 pub struct Message {
 	pub id: Uuid,
 	pub session_id: Uuid,
-	pub user_id: Uuid,
+	/// `None` for the narrator's interludes.
+	pub user_id: Option<Uuid>,
+	/// Empty when the player passed.
 	pub content: String,
 	pub round: i32,
 	pub turn_order: i32,
 	pub created_at: NaiveDateTime,
+	/// Set in rotating sessions only.
+	pub thread_id: Option<Uuid>,
+	/// The player asked for suggestions on this turn.
+	pub used_suggestion: bool,
 }
 
 #[derive(Debug, Insertable, Clone)]
 #[diesel(table_name = messages)]
 pub struct NewMessage<'a> {
 	pub session_id: Uuid,
-	pub user_id: Uuid,
+	pub user_id: Option<Uuid>,
 	pub content: &'a str,
 	pub round: i32,
 	pub turn_order: i32,
+	pub thread_id: Option<Uuid>,
+	pub used_suggestion: bool,
 }
@@ -0,0 +1,32 @@
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use lib_core::model::schema::suggestions;
+use uuid::Uuid;
+
+use crate::suggestions::model::{NewSuggestion, Suggestion};
+
+impl Suggestion {
+	pub fn create(
+		conn: &mut PgConnection,
+		item: NewSuggestion,
+	) -> QueryResult<Self> {
+		diesel::insert_into(suggestions::table)
+			.values(item)
+			.get_result(conn)
+	}
+
+	/// Suggestions the user asked for on their turn in `round`.
+	pub fn count_for_turn(
+		conn: &mut PgConnection,
+		session_id: Uuid,
+		user_id: Uuid,
+		round: i32,
+	) -> QueryResult<i64> {
+		suggestions::table
+			.filter(suggestions::session_id.eq(session_id))
+			.filter(suggestions::user_id.eq(user_id))
+			.filter(suggestions::round.eq(round))
+			.count()
+			.get_result(conn)
+	}
+}
@@ -0,0 +1,22 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::{Insertable, Queryable};
+use lib_core::model::schema::suggestions;
+use uuid::Uuid;
+
+/// A player asking for writing ideas on their turn.
+#[derive(Debug, Queryable, Clone)]
+pub struct Suggestion {
+	pub id: Uuid,
+	pub session_id: Uuid,
+	pub user_id: Uuid,
+	pub round: i32,
+	pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = suggestions)]
+pub struct NewSuggestion {
+	pub session_id: Uuid,
+	pub user_id: Uuid,
+	pub round: i32,
+}
@@ -0,0 +1,38 @@
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use lib_core::model::schema::threads;
+use uuid::Uuid;
+
+use crate::threads::model::Thread;
+
+impl Thread {
+	/// Creates threads `0..count` of the session.
+	pub fn create_for_session(
+		conn: &mut PgConnection,
+		session_id: Uuid,
+		count: i32,
+	) -> QueryResult<Vec<Self>> {
+		let rows = (0..count)
+			.map(|thread_index| {
+				(
+					threads::session_id.eq(session_id),
+					threads::thread_index.eq(thread_index),
+				)
+			})
+			.collect::<Vec<_>>();
+
+		diesel::insert_into(threads::table)
+			.values(rows)
+			.get_results(conn)
+	}
+
+	pub fn list_by_session(
+		conn: &mut PgConnection,
+		session_id: Uuid,
+	) -> QueryResult<Vec<Self>> {
+		threads::table
+			.filter(threads::session_id.eq(session_id))
+			.order_by(threads::thread_index.asc())
+			.load(conn)
+	}
+}
@@ -0,0 +1,13 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::Queryable;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// One "paper" of a rotating session, passed on to the next player each round.
+#[derive(Debug, Queryable, Clone, Serialize)]
+pub struct Thread {
+	pub id: Uuid,
+	pub session_id: Uuid,
+	pub thread_index: i32,
+	pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,56 @@
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use lib_core::model::{
+	schema::{bots, players, users},
+	schema_enums::BotDifficulty,
+};
+use uuid::Uuid;
+
+use crate::bots::model::{Bot, NewBot};
+
+impl Bot {
+	/// Creates the bot together with its user.
+	pub fn create(
+		conn: &mut PgConnection,
+		persona: &str,
+		difficulty: BotDifficulty,
+	) -> QueryResult<Self> {
+		let user_id = Uuid::new_v4();
+
+		diesel::insert_into(users::table)
+			.values((
+				users::id.eq(user_id),
+				users::username.eq(format!("bot-{}", user_id.simple())),
+				users::is_guest.eq(true),
+			))
+			.execute(conn)?;
+
+		diesel::insert_into(bots::table)
+			.values(NewBot {
+				user_id,
+				persona,
+				difficulty,
+			})
+			.get_result(conn)
+	}
+
+	/// The bot behind `user_id`, `None` for people.
+	pub fn find(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+	) -> QueryResult<Option<Self>> {
+		bots::table.find(user_id).get_result(conn).optional()
+	}
+
+	/// User ids of the bots seated in the session.
+	pub fn ids_in_session(
+		conn: &mut PgConnection,
+		session_id: Uuid,
+	) -> QueryResult<Vec<Uuid>> {
+		bots::table
+			.inner_join(players::table.on(players::user_id.eq(bots::user_id)))
+			.filter(players::session_id.eq(session_id))
+			.select(bots::user_id)
+			.load(conn)
+	}
+}
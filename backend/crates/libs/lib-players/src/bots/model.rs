@@ -0,0 +1,24 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::{Insertable, Queryable};
+use lib_core::model::{schema::bots, schema_enums::BotDifficulty};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// An AI-controlled player. Each bot is a user of its own, seated in one
+/// session.
+#[derive(Debug, Queryable, Clone, Serialize)]
+pub struct Bot {
+	pub user_id: Uuid,
+	/// How the bot writes, given to the model as is.
+	pub persona: String,
+	pub difficulty: BotDifficulty,
+	pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = bots)]
+pub struct NewBot<'a> {
+	pub user_id: Uuid,
+	pub persona: &'a str,
+	pub difficulty: BotDifficulty,
+}
@@ -51,16 +51,52 @@ impl BasicDbOps for Player {
 }
 
 impl Player {
+	/// Players of the session in seat order.
 	pub fn list_by_session(
 		conn: &mut PgConnection,
 		sid: Uuid,
 	) -> QueryResult<Vec<Self>> {
 		Self::table()
 			.filter(players::session_id.eq(sid))
-			.order(players::joined_at.asc())
+			.order(players::seat.asc())
 			.load::<Self>(conn)
 	}
 
+	/// The host, or the earliest player to join once the host left.
+	pub fn get_host(
+		conn: &mut PgConnection,
+		sid: Uuid,
+	) -> QueryResult<Option<Self>> {
+		Self::table()
+			.filter(players::session_id.eq(sid))
+			.order((players::is_host.desc(), players::joined_at.asc()))
+			.first::<Self>(conn)
+			.optional()
+	}
+
+	/// The seat after the last taken one.
+	pub fn next_seat(conn: &mut PgConnection, sid: Uuid) -> QueryResult<i32> {
+		Self::table()
+			.filter(players::session_id.eq(sid))
+			.select(diesel::dsl::max(players::seat))
+			.first::<Option<i32>>(conn)
+			.map(|seat| seat.map_or(0, |seat| seat + 1))
+	}
+
+	/// Seats the users in the given order, from seat 0.
+	pub fn set_seats(
+		conn: &mut PgConnection,
+		sid: Uuid,
+		user_ids: &[Uuid],
+	) -> QueryResult<()> {
+		for (seat, user_id) in (0..).zip(user_ids) {
+			diesel::update(Self::table().find((sid, user_id)))
+				.set(players::seat.eq(seat))
+				.execute(conn)?;
+		}
+		Ok(())
+	}
+
 	pub fn list_by_user(
 		conn: &mut PgConnection,
 		uid: Uuid,
@@ -1,2 +1,4 @@
+pub mod bots;
 pub mod db_ops;
 pub mod model;
+pub mod objectives;
@@ -11,6 +11,8 @@ pub struct Player {
 	pub joined_at: NaiveDateTime,
 	pub is_ready: bool,
 	pub is_host: bool,
+	/// Position in the turn order, kept when other players leave.
+	pub seat: i32,
 }
 
 #[derive(Debug, Insertable)]
@@ -20,6 +22,7 @@ pub struct NewPlayer {
 	pub user_id: Uuid,
 	pub is_ready: bool,
 	pub is_host: bool,
+	pub seat: i32,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
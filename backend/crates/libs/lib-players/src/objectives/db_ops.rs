@@ -0,0 +1,56 @@
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use lib_core::model::schema::player_objectives;
+use uuid::Uuid;
+
+use crate::objectives::model::PlayerObjective;
+
+impl PlayerObjective {
+	/// Hands out `(user_id, objective)` pairs for the session.
+	pub fn create_for_session(
+		conn: &mut PgConnection,
+		session_id: Uuid,
+		assigned: &[(Uuid, &str)],
+	) -> QueryResult<Vec<Self>> {
+		let rows = assigned
+			.iter()
+			.map(|(user_id, objective)| {
+				(
+					player_objectives::session_id.eq(session_id),
+					player_objectives::user_id.eq(user_id),
+					player_objectives::objective.eq(objective),
+				)
+			})
+			.collect::<Vec<_>>();
+
+		diesel::insert_into(player_objectives::table)
+			.values(rows)
+			.get_results(conn)
+	}
+
+	pub fn list_by_session(
+		conn: &mut PgConnection,
+		session_id: Uuid,
+	) -> QueryResult<Vec<Self>> {
+		player_objectives::table
+			.filter(player_objectives::session_id.eq(session_id))
+			.order_by(player_objectives::created_at.asc())
+			.load(conn)
+	}
+
+	/// Stores the judge's verdict on the user's objective.
+	pub fn judge(
+		conn: &mut PgConnection,
+		session_id: Uuid,
+		user_id: Uuid,
+		met: bool,
+		points: i32,
+	) -> QueryResult<Self> {
+		diesel::update(player_objectives::table.find((session_id, user_id)))
+			.set((
+				player_objectives::met.eq(met),
+				player_objectives::points.eq(points),
+			))
+			.get_result(conn)
+	}
+}
@@ -0,0 +1,16 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::Queryable;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// A player's secret objective in a competitive session and its score.
+#[derive(Debug, Queryable, Clone, Serialize)]
+pub struct PlayerObjective {
+	pub session_id: Uuid,
+	pub user_id: Uuid,
+	pub objective: String,
+	/// `None` until the judge looked at the story.
+	pub met: Option<bool>,
+	pub points: i32,
+	pub created_at: NaiveDateTime,
+}
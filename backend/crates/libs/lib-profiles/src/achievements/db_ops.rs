@@ -0,0 +1,97 @@
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use lib_core::model::schema::user_achievements;
+use uuid::Uuid;
+
+use crate::{
+	achievements::model::{
+		ACHIEVEMENTS, Achievement, PlayVariety, Progress, UserAchievement,
+	},
+	stats::model::UserStats,
+};
+
+/// Themes the user wrote in (ignoring case) and the most humans seated in
+/// a started game the user hosted.
+const PLAY_VARIETY_SQL: &str = r"
+	SELECT
+		(
+			SELECT COUNT(DISTINCT lower(s.theme))
+			FROM sessions s
+			WHERE s.id IN (SELECT session_id FROM messages WHERE user_id = $1)
+		) AS distinct_themes,
+		(
+			SELECT COALESCE(MAX(seated), 0)
+			FROM (
+				SELECT COUNT(*) AS seated
+				FROM players p
+				JOIN players h
+					ON h.session_id = p.session_id AND h.user_id = $1 AND h.is_host
+				JOIN sessions s ON s.id = p.session_id AND s.status <> 'waiting'
+				WHERE p.user_id NOT IN (SELECT user_id FROM bots)
+				GROUP BY p.session_id
+			) games
+		) AS largest_hosted_game
+";
+
+impl Progress {
+	pub fn for_user(conn: &mut PgConnection, user_id: Uuid) -> QueryResult<Self> {
+		let stats = UserStats::for_user(conn, user_id)?;
+		let variety: PlayVariety = diesel::sql_query(PLAY_VARIETY_SQL)
+			.bind::<diesel::sql_types::Uuid, _>(user_id)
+			.get_result(conn)?;
+
+		Ok(Self {
+			games_finished: stats.games_finished,
+			words_contributed: stats.words_contributed,
+			distinct_themes: variety.distinct_themes,
+			largest_hosted_game: variety.largest_hosted_game,
+			win_points: stats.win_points,
+			stories_rated: stats.stories_rated,
+		})
+	}
+}
+
+impl UserAchievement {
+	pub fn list_by_user(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+	) -> QueryResult<Vec<Self>> {
+		user_achievements::table
+			.filter(user_achievements::user_id.eq(user_id))
+			.order_by(user_achievements::unlocked_at.asc())
+			.load(conn)
+	}
+
+	/// Unlocks every achievement the user's progress meets,
+	/// returns the ones that weren't unlocked before.
+	pub fn unlock_earned(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+	) -> QueryResult<Vec<Achievement>> {
+		let progress = Progress::for_user(conn, user_id)?;
+		let earned = ACHIEVEMENTS
+			.iter()
+			.filter(|achievement| achievement.rule.is_met(&progress))
+			.map(|achievement| {
+				(
+					user_achievements::user_id.eq(user_id),
+					user_achievements::achievement_id.eq(achievement.id),
+				)
+			})
+			.collect::<Vec<_>>();
+		if earned.is_empty() {
+			return Ok(Vec::new());
+		}
+
+		let unlocked: Vec<String> = diesel::insert_into(user_achievements::table)
+			.values(earned)
+			.on_conflict_do_nothing()
+			.returning(user_achievements::achievement_id)
+			.get_results(conn)?;
+
+		Ok(ACHIEVEMENTS
+			.into_iter()
+			.filter(|achievement| unlocked.iter().any(|id| id == achievement.id))
+			.collect())
+	}
+}
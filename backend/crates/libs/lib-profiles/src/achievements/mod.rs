@@ -0,0 +1,2 @@
+pub mod db_ops;
+pub mod model;
@@ -0,0 +1,119 @@
+use chrono::NaiveDateTime;
+use diesel::{
+	prelude::{Queryable, QueryableByName},
+	sql_types::BigInt,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// What a user has to reach to unlock an achievement.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(tag = "kind", content = "target", rename_all = "snake_case")]
+pub enum Rule {
+	GamesFinished(i64),
+	WordsContributed(i64),
+	DistinctThemes(i64),
+	/// Humans seated in a started game the user hosted.
+	HostedPlayers(i64),
+	WinPoints(i64),
+	StoriesRated(i64),
+}
+
+impl Rule {
+	pub fn is_met(&self, progress: &Progress) -> bool {
+		match *self {
+			Rule::GamesFinished(target) => progress.games_finished >= target,
+			Rule::WordsContributed(target) => progress.words_contributed >= target,
+			Rule::DistinctThemes(target) => progress.distinct_themes >= target,
+			Rule::HostedPlayers(target) => progress.largest_hosted_game >= target,
+			Rule::WinPoints(target) => progress.win_points >= target,
+			Rule::StoriesRated(target) => progress.stories_rated >= target,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Achievement {
+	pub id: &'static str,
+	pub title: &'static str,
+	pub description: &'static str,
+	pub rule: Rule,
+}
+
+pub const ACHIEVEMENTS: [Achievement; 8] = [
+	Achievement {
+		id: "first_story",
+		title: "Once Upon a Time",
+		description: "Finish your first game.",
+		rule: Rule::GamesFinished(1),
+	},
+	Achievement {
+		id: "storyteller",
+		title: "Storyteller",
+		description: "Finish 10 games.",
+		rule: Rule::GamesFinished(10),
+	},
+	Achievement {
+		id: "wordsmith",
+		title: "Wordsmith",
+		description: "Contribute 1000 words.",
+		rule: Rule::WordsContributed(1000),
+	},
+	Achievement {
+		id: "genre_hopper",
+		title: "Genre Hopper",
+		description: "Write in 5 different themes.",
+		rule: Rule::DistinctThemes(5),
+	},
+	Achievement {
+		id: "full_house",
+		title: "Full House",
+		description: "Host a game with 6 players.",
+		rule: Rule::HostedPlayers(6),
+	},
+	Achievement {
+		id: "schemer",
+		title: "Schemer",
+		description: "Earn your first points for a secret objective.",
+		rule: Rule::WinPoints(1),
+	},
+	Achievement {
+		id: "mastermind",
+		title: "Mastermind",
+		description: "Earn 50 points for secret objectives.",
+		rule: Rule::WinPoints(50),
+	},
+	Achievement {
+		id: "critic",
+		title: "Critic",
+		description: "Rate 10 stories.",
+		rule: Rule::StoriesRated(10),
+	},
+];
+
+/// Counters the rules are checked against.
+#[derive(Debug, Clone, Default)]
+pub struct Progress {
+	pub games_finished: i64,
+	pub words_contributed: i64,
+	pub distinct_themes: i64,
+	pub largest_hosted_game: i64,
+	pub win_points: i64,
+	pub stories_rated: i64,
+}
+
+/// The counters not covered by `UserStats`, in one query.
+#[derive(Debug, QueryableByName)]
+pub(crate) struct PlayVariety {
+	#[diesel(sql_type = BigInt)]
+	pub distinct_themes: i64,
+	#[diesel(sql_type = BigInt)]
+	pub largest_hosted_game: i64,
+}
+
+#[derive(Debug, Queryable, Clone, Serialize)]
+pub struct UserAchievement {
+	pub user_id: Uuid,
+	pub achievement_id: String,
+	pub unlocked_at: NaiveDateTime,
+}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use diesel::associations::HasTable;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use lib_core::model::base::BasicDbOps;
+use lib_core::model::schema::{profiles, users};
+use uuid::Uuid;
+
+use crate::model::{Profile, UserProfile};
+
+impl HasTable for Profile {
+	type Table = profiles::table;
+	fn table() -> Self::Table {
+		profiles::table
+	}
+}
+
+impl BasicDbOps for Profile {
+	type Id = Uuid;
+	type Insert<'a> = Profile;
+
+	fn create<'a>(conn: &mut PgConnection, item: Profile) -> QueryResult<Self> {
+		diesel::insert_into(Self::table())
+			.values(item)
+			.get_result(conn)
+	}
+
+	fn get(conn: &mut PgConnection, id: Self::Id) -> QueryResult<Self> {
+		profiles::table.find(id).get_result(conn)
+	}
+
+	fn list(conn: &mut PgConnection) -> QueryResult<Vec<Self>> {
+		profiles::table.load(conn)
+	}
+
+	fn update(
+		conn: &mut PgConnection,
+		id: Self::Id,
+		changes: &Self,
+	) -> QueryResult<Self> {
+		diesel::update(profiles::table.find(id))
+			.set(changes)
+			.get_result(conn)
+	}
+
+	fn delete(conn: &mut PgConnection, id: Self::Id) -> QueryResult<usize> {
+		diesel::delete(profiles::table.find(id)).execute(conn)
+	}
+}
+
+type UserProfileRow = (Uuid, String, bool, Option<Profile>);
+
+impl Profile {
+	/// Inserts the profile or replaces the stored one.
+	pub fn upsert(conn: &mut PgConnection, profile: &Profile) -> QueryResult<Self> {
+		diesel::insert_into(profiles::table)
+			.values(profile)
+			.on_conflict(profiles::user_id)
+			.do_update()
+			.set(profile)
+			.get_result(conn)
+	}
+
+	/// The stored profile, or an empty one for users who never set it.
+	pub fn get_or_empty(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+	) -> QueryResult<Self> {
+		profiles::table
+			.find(user_id)
+			.get_result(conn)
+			.optional()
+			.map(|profile| profile.unwrap_or_else(|| Profile::empty(user_id)))
+	}
+
+	pub fn get_user_profile(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+	) -> QueryResult<UserProfile> {
+		users::table
+			.left_join(profiles::table)
+			.filter(users::id.eq(user_id))
+			.select((
+				users::id,
+				users::username,
+				users::is_guest,
+				profiles::all_columns.nullable(),
+			))
+			.get_result::<UserProfileRow>(conn)
+			.map(into_user_profile)
+	}
+
+	/// Name to show for the user: display name, or username when unset.
+	pub fn display_name(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+	) -> QueryResult<String> {
+		Self::display_names(conn, &[user_id])?
+			.remove(&user_id)
+			.ok_or(diesel::result::Error::NotFound)
+	}
+
+	pub fn display_names(
+		conn: &mut PgConnection,
+		user_ids: &[Uuid],
+	) -> QueryResult<HashMap<Uuid, String>> {
+		let rows: Vec<(Uuid, String, Option<String>)> = users::table
+			.left_join(profiles::table)
+			.filter(users::id.eq_any(user_ids))
+			.select((
+				users::id,
+				users::username,
+				profiles::display_name.nullable(),
+			))
+			.load(conn)?;
+
+		Ok(rows
+			.into_iter()
+			.map(|(user_id, username, display_name)| {
+				(user_id, display_name.unwrap_or(username))
+			})
+			.collect())
+	}
+}
+
+fn into_user_profile(
+	(user_id, username, is_guest, profile): UserProfileRow,
+) -> UserProfile {
+	let profile = profile.unwrap_or_else(|| Profile::empty(user_id));
+
+	UserProfile {
+		user_id,
+		display_name: profile.display_name.unwrap_or_else(|| username.clone()),
+		username,
+		avatar_url: profile.avatar_url,
+		bio: profile.bio,
+		is_guest,
+	}
+}
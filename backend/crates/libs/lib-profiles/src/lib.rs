@@ -0,0 +1,4 @@
+pub mod achievements;
+pub mod db_ops;
+pub mod model;
+pub mod stats;
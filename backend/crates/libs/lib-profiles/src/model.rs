@@ -0,0 +1,39 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::{AsChangeset, Insertable, Queryable};
+use lib_core::model::schema::profiles;
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Debug, Queryable, Insertable, AsChangeset, Clone, Serialize)]
+#[diesel(treat_none_as_null = true)]
+#[diesel(table_name = profiles)]
+pub struct Profile {
+	pub user_id: Uuid,
+	pub display_name: Option<String>,
+	pub avatar_url: Option<String>,
+	pub bio: Option<String>,
+	pub updated_at: NaiveDateTime,
+}
+
+impl Profile {
+	pub fn empty(user_id: Uuid) -> Self {
+		Self {
+			user_id,
+			display_name: None,
+			avatar_url: None,
+			bio: None,
+			updated_at: chrono::Utc::now().naive_utc(),
+		}
+	}
+}
+
+/// Public view of a user, `display_name` falls back to the username.
+#[derive(Debug, Clone, Serialize)]
+pub struct UserProfile {
+	pub user_id: Uuid,
+	pub username: String,
+	pub display_name: String,
+	pub avatar_url: Option<String>,
+	pub bio: Option<String>,
+	pub is_guest: bool,
+}
@@ -0,0 +1,109 @@
+use chrono::NaiveDateTime;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use lib_core::model::schema::{player_objectives, story_ratings};
+use uuid::Uuid;
+
+use crate::{
+	model::Profile,
+	stats::model::{LeaderboardEntry, UserStats, WritingStats},
+};
+
+/// Games, words and turn times from the user's lines. A turn takes from the
+/// line before (in the same thread, for rotating sessions) to the user's own.
+const WRITING_STATS_SQL: &str = r"
+	WITH turns AS (
+		SELECT
+			m.session_id,
+			m.user_id,
+			m.content,
+			m.created_at - LAG(m.created_at) OVER (
+				PARTITION BY m.session_id, m.thread_id
+				ORDER BY m.created_at
+			) AS took
+		FROM messages m
+		WHERE m.session_id IN (SELECT session_id FROM messages WHERE user_id = $1)
+	)
+	SELECT
+		COUNT(DISTINCT t.session_id) AS games_played,
+		COUNT(DISTINCT t.session_id) FILTER (WHERE s.status = 'finished')
+			AS games_finished,
+		COALESCE(SUM(array_length(
+			regexp_split_to_array(NULLIF(btrim(t.content), ''), '\s+'), 1
+		)), 0)::BIGINT AS words_contributed,
+		AVG(EXTRACT(EPOCH FROM t.took))::FLOAT8 AS avg_turn_secs
+	FROM turns t
+	JOIN sessions s ON s.id = t.session_id
+	WHERE t.user_id = $1
+";
+
+impl UserStats {
+	pub fn for_user(conn: &mut PgConnection, user_id: Uuid) -> QueryResult<Self> {
+		let writing: WritingStats = diesel::sql_query(WRITING_STATS_SQL)
+			.bind::<diesel::sql_types::Uuid, _>(user_id)
+			.get_result(conn)?;
+
+		let stories_rated = story_ratings::table
+			.filter(story_ratings::user_id.eq(user_id))
+			.count()
+			.get_result(conn)?;
+
+		let win_points: Option<i64> = player_objectives::table
+			.filter(player_objectives::user_id.eq(user_id))
+			.select(diesel::dsl::sum(player_objectives::points))
+			.get_result(conn)?;
+
+		Ok(Self {
+			user_id,
+			games_played: writing.games_played,
+			games_finished: writing.games_finished,
+			words_contributed: writing.words_contributed,
+			avg_turn_secs: writing.avg_turn_secs,
+			stories_rated,
+			win_points: win_points.unwrap_or(0),
+		})
+	}
+}
+
+impl LeaderboardEntry {
+	/// Users with most win points, counting games started from `since` on
+	/// (all games when `None`).
+	pub fn top(
+		conn: &mut PgConnection,
+		since: Option<NaiveDateTime>,
+		limit: i64,
+	) -> QueryResult<Vec<Self>> {
+		let points = diesel::dsl::sum(player_objectives::points);
+		let mut query = player_objectives::table
+			.group_by(player_objectives::user_id)
+			.select((player_objectives::user_id, points))
+			.having(points.gt(0))
+			.order_by((points.desc(), player_objectives::user_id.asc()))
+			.limit(limit)
+			.into_boxed();
+		if let Some(since) = since {
+			query = query.filter(player_objectives::created_at.ge(since));
+		}
+		let rows: Vec<(Uuid, Option<i64>)> = query.load(conn)?;
+
+		let user_ids = rows.iter().map(|(user_id, _)| *user_id).collect::<Vec<_>>();
+		let mut names = Profile::display_names(conn, &user_ids)?;
+
+		let mut entries: Vec<Self> = Vec::with_capacity(rows.len());
+		for (index, (user_id, points)) in rows.into_iter().enumerate() {
+			let win_points = points.unwrap_or(0);
+			let rank = match entries.last() {
+				Some(above) if above.win_points == win_points => above.rank,
+				_ => index as i64 + 1,
+			};
+			entries.push(Self {
+				rank,
+				user_id,
+				display_name: names.remove(&user_id).unwrap_or_default(),
+				win_points,
+			});
+		}
+
+		Ok(entries)
+	}
+}
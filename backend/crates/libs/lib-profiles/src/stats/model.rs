@@ -0,0 +1,44 @@
+use diesel::{
+	prelude::QueryableByName,
+	sql_types::{BigInt, Double, Nullable},
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// A user's activity across all the games they wrote in.
+#[derive(Debug, Clone, Serialize)]
+pub struct UserStats {
+	pub user_id: Uuid,
+	pub games_played: i64,
+	/// Games that reached `finished`, also those ended early when too few
+	/// players were left, so not all of them got a story.
+	pub games_finished: i64,
+	pub words_contributed: i64,
+	/// Seconds from the line before to the user's own, `None` without turns.
+	pub avg_turn_secs: Option<f64>,
+	pub stories_rated: i64,
+	/// Points for met secret objectives.
+	pub win_points: i64,
+}
+
+/// Stats computed from the user's messages in one query.
+#[derive(Debug, QueryableByName)]
+pub(crate) struct WritingStats {
+	#[diesel(sql_type = BigInt)]
+	pub games_played: i64,
+	#[diesel(sql_type = BigInt)]
+	pub games_finished: i64,
+	#[diesel(sql_type = BigInt)]
+	pub words_contributed: i64,
+	#[diesel(sql_type = Nullable<Double>)]
+	pub avg_turn_secs: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LeaderboardEntry {
+	/// 1-based, users on equal points share a rank.
+	pub rank: i64,
+	pub user_id: Uuid,
+	pub display_name: String,
+	pub win_points: i64,
+}
@@ -0,0 +1,60 @@
+use std::{
+	convert::Infallible,
+	net::{IpAddr, SocketAddr},
+};
+
+use axum::{
+	extract::{ConnectInfo, FromRequestParts},
+	http::{Extensions, HeaderMap, request::Parts},
+};
+
+/// Address of the client, from the socket or, behind a trusted
+/// proxy, from the first `X-Forwarded-For` entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClientIp(pub Option<IpAddr>);
+
+/// Whether `X-Forwarded-For` is set by a proxy we trust.
+#[derive(Debug, Clone, Copy)]
+pub struct TrustProxy(pub bool);
+
+impl ClientIp {
+	pub fn from_parts(headers: &HeaderMap, extensions: &Extensions) -> Self {
+		let trust_proxy =
+			extensions.get::<TrustProxy>().is_some_and(|trust| trust.0);
+
+		let forwarded = trust_proxy
+			.then(|| {
+				headers
+					.get("x-forwarded-for")
+					.and_then(|hv| hv.to_str().ok())
+					.and_then(|s| s.split(',').next())
+					.and_then(|s| s.trim().parse::<IpAddr>().ok())
+			})
+			.flatten();
+
+		let peer = extensions
+			.get::<ConnectInfo<SocketAddr>>()
+			.map(|ConnectInfo(addr)| addr.ip());
+
+		ClientIp(forwarded.or(peer))
+	}
+
+	/// Key for per-IP limits, all unknown clients share one bucket.
+	pub fn key(&self) -> String {
+		match self.0 {
+			Some(ip) => format!("ip:{ip}"),
+			None => "ip:unknown".to_string(),
+		}
+	}
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ClientIp {
+	type Rejection = Infallible;
+
+	async fn from_request_parts(
+		parts: &mut Parts,
+		_state: &S,
+	) -> core::result::Result<Self, Self::Rejection> {
+		Ok(ClientIp::from_parts(&parts.headers, &parts.extensions))
+	}
+}
@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use axum::{
+	http::{HeaderValue, StatusCode, header::RETRY_AFTER},
+	response::{IntoResponse, Response},
+};
+use lib_core::client_error::ClientError;
+use serde::Serialize;
+use tracing::debug;
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug, Clone, thiserror::Error, Serialize)]
+pub enum Error {
+	#[error("Rate limit exceeded, retry after {retry_after_secs}s")]
+	RateLimited { retry_after_secs: u64 },
+}
+
+impl Error {
+	pub fn rate_limited(retry_after: Duration) -> Self {
+		// Round up, a zero Retry-After would invite an immediate retry
+		Error::RateLimited {
+			retry_after_secs: retry_after.as_secs() + 1,
+		}
+	}
+
+	pub fn retry_after_secs(&self) -> u64 {
+		match self {
+			Error::RateLimited { retry_after_secs } => *retry_after_secs,
+		}
+	}
+
+	pub fn client_status_and_error(&self) -> (StatusCode, ClientError) {
+		match self {
+			Error::RateLimited { retry_after_secs } => (
+				StatusCode::TOO_MANY_REQUESTS,
+				ClientError::TOO_MANY_REQUESTS(format!(
+					"Too many requests, retry in {retry_after_secs}s"
+				)),
+			),
+		}
+	}
+}
+
+impl IntoResponse for Error {
+	fn into_response(self) -> Response {
+		debug!("{:<12} - Error {self:?}", "INTO_RES");
+
+		let mut response = StatusCode::TOO_MANY_REQUESTS.into_response();
+
+		response
+			.headers_mut()
+			.insert(RETRY_AFTER, HeaderValue::from(self.retry_after_secs()));
+		response.extensions_mut().insert(self);
+
+		response
+	}
+}
@@ -0,0 +1,100 @@
+use std::{
+	sync::Arc,
+	task::{Context, Poll},
+};
+
+use axum::{
+	extract::Request,
+	response::{IntoResponse, Response},
+};
+use lib_core::ctx::CtxExtResult;
+use tower::{Layer, Service};
+
+use crate::{client_ip::ClientIp, limiter::RateLimiter};
+
+/// What requests are counted against.
+#[derive(Debug, Clone, Copy)]
+pub enum RateLimitKey {
+	ClientIp,
+	/// The authenticated user, falling back to the client ip.
+	UserOrClientIp,
+}
+
+/// Tower layer rejecting requests over the limit with `429`.
+#[derive(Clone)]
+pub struct RateLimitLayer {
+	limiter: Arc<RateLimiter>,
+	key: RateLimitKey,
+}
+
+impl RateLimitLayer {
+	pub fn new(limiter: RateLimiter, key: RateLimitKey) -> Self {
+		Self {
+			limiter: Arc::new(limiter),
+			key,
+		}
+	}
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+	type Service = RateLimit<S>;
+
+	fn layer(&self, inner: S) -> Self::Service {
+		RateLimit {
+			inner,
+			limiter: self.limiter.clone(),
+			key: self.key,
+		}
+	}
+}
+
+#[derive(Clone)]
+pub struct RateLimit<S> {
+	inner: S,
+	limiter: Arc<RateLimiter>,
+	key: RateLimitKey,
+}
+
+impl<S> Service<Request> for RateLimit<S>
+where
+	S: Service<Request, Response = Response> + Send + 'static,
+	S::Future: Send + 'static,
+{
+	type Response = Response;
+	type Error = S::Error;
+	type Future = futures_util::future::Either<
+		std::future::Ready<Result<Response, S::Error>>,
+		S::Future,
+	>;
+
+	fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		self.inner.poll_ready(cx)
+	}
+
+	fn call(&mut self, req: Request) -> Self::Future {
+		let key = request_key(self.key, &req);
+
+		match self.limiter.check(&key) {
+			Ok(()) => futures_util::future::Either::Right(self.inner.call(req)),
+			Err(err) => futures_util::future::Either::Left(std::future::ready(Ok(
+				err.into_response(),
+			))),
+		}
+	}
+}
+
+fn request_key(key: RateLimitKey, req: &Request) -> String {
+	let user_id = match key {
+		RateLimitKey::ClientIp => None,
+		RateLimitKey::UserOrClientIp => req
+			.extensions()
+			.get::<CtxExtResult>()
+			.and_then(|ctx| ctx.as_ref().ok())
+			.map(|ctx| ctx.user_id),
+	};
+
+	match user_id {
+		Some(user_id) => format!("user:{user_id}"),
+		None => ClientIp::from_parts(req.headers(), req.extensions()).key(),
+	}
+}
@@ -0,0 +1,5 @@
+pub mod client_ip;
+pub mod error;
+pub mod layer;
+pub mod limiter;
+pub mod throttle;
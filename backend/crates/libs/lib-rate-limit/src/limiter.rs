@@ -0,0 +1,81 @@
+use std::{
+	sync::atomic::{AtomicU64, Ordering},
+	time::{Duration, Instant},
+};
+
+use dashmap::DashMap;
+
+use crate::error::{Error, Result};
+
+/// Purge idle buckets every this many checks.
+const PURGE_EVERY: u64 = 1024;
+
+/// In-memory token bucket limiter, one bucket per key.
+pub struct RateLimiter {
+	/// Bucket size, i.e. the allowed burst.
+	capacity: f64,
+	/// Tokens added back per second.
+	refill_per_sec: f64,
+	buckets: DashMap<String, Bucket>,
+	checks: AtomicU64,
+}
+
+struct Bucket {
+	tokens: f64,
+	updated_at: Instant,
+}
+
+impl RateLimiter {
+	/// Allows `per_minute` requests per key, all of them usable as a burst.
+	pub fn per_minute(per_minute: u32) -> Self {
+		let capacity = f64::from(per_minute.max(1));
+
+		Self {
+			capacity,
+			refill_per_sec: capacity / 60.0,
+			buckets: DashMap::new(),
+			checks: AtomicU64::new(0),
+		}
+	}
+
+	/// Takes a token for `key`, or tells how long until one is available.
+	pub fn check(&self, key: &str) -> Result<()> {
+		if self
+			.checks
+			.fetch_add(1, Ordering::Relaxed)
+			.is_multiple_of(PURGE_EVERY)
+		{
+			self.purge_idle();
+		}
+
+		let now = Instant::now();
+		let mut bucket = self.buckets.entry(key.to_string()).or_insert(Bucket {
+			tokens: self.capacity,
+			updated_at: now,
+		});
+
+		let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+		bucket.tokens =
+			(bucket.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+		bucket.updated_at = now;
+
+		if bucket.tokens >= 1.0 {
+			bucket.tokens -= 1.0;
+			Ok(())
+		} else {
+			let missing = 1.0 - bucket.tokens;
+			Err(Error::rate_limited(Duration::from_secs_f64(
+				missing / self.refill_per_sec,
+			)))
+		}
+	}
+
+	/// Drops buckets that have refilled completely, they hold no state.
+	fn purge_idle(&self) {
+		let full_after =
+			Duration::from_secs_f64(self.capacity / self.refill_per_sec);
+
+		self.buckets
+			.retain(|_, bucket| bucket.updated_at.elapsed() < full_after);
+	}
+}
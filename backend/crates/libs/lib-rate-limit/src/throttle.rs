@@ -0,0 +1,95 @@
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+
+use crate::error::{Error, Result};
+
+/// Failed-attempt tracker with exponential lockout, used for logins.
+///
+/// The first `free_attempts` failures of a key are free. Every further
+/// failure locks the key for `base_lockout * 2^n`, capped at `max_lockout`.
+/// Failures are forgotten once `failure_window` passes without a new one.
+pub struct LoginThrottle {
+	free_attempts: u32,
+	base_lockout: Duration,
+	max_lockout: Duration,
+	failure_window: Duration,
+	entries: DashMap<String, Failures>,
+}
+
+struct Failures {
+	count: u32,
+	last_failure: Instant,
+	locked_until: Option<Instant>,
+}
+
+impl LoginThrottle {
+	pub fn new(
+		free_attempts: u32,
+		base_lockout: Duration,
+		max_lockout: Duration,
+		failure_window: Duration,
+	) -> Self {
+		Self {
+			free_attempts,
+			base_lockout,
+			max_lockout,
+			failure_window,
+			entries: DashMap::new(),
+		}
+	}
+
+	/// Fails if any of the keys is currently locked out.
+	pub fn check(&self, keys: &[&str]) -> Result<()> {
+		let now = Instant::now();
+
+		let retry_after = keys
+			.iter()
+			.filter_map(|key| self.entries.get(*key))
+			.filter_map(|entry| entry.locked_until)
+			.filter(|until| *until > now)
+			.map(|until| until - now)
+			.max();
+
+		match retry_after {
+			Some(retry_after) => Err(Error::rate_limited(retry_after)),
+			None => Ok(()),
+		}
+	}
+
+	pub fn record_failure(&self, keys: &[&str]) {
+		let now = Instant::now();
+		self.entries.retain(|_, entry| {
+			now.duration_since(entry.last_failure) < self.failure_window
+				|| entry.locked_until.is_some_and(|until| until > now)
+		});
+
+		for key in keys {
+			let mut entry =
+				self.entries.entry(key.to_string()).or_insert(Failures {
+					count: 0,
+					last_failure: now,
+					locked_until: None,
+				});
+
+			entry.count += 1;
+			entry.last_failure = now;
+
+			if entry.count > self.free_attempts {
+				let exponent = (entry.count - self.free_attempts - 1).min(16);
+				let lockout = self
+					.base_lockout
+					.saturating_mul(1 << exponent)
+					.min(self.max_lockout);
+				entry.locked_until = Some(now + lockout);
+			}
+		}
+	}
+
+	/// Clears the failures of the keys, e.g. the username after a good login.
+	pub fn record_success(&self, keys: &[&str]) {
+		for key in keys {
+			self.entries.remove(*key);
+		}
+	}
+}
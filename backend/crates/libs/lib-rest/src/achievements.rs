@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use axum::{
+	Router,
+	extract::{Extension, Json, Path},
+	http::StatusCode,
+	response::IntoResponse,
+	routing::get,
+};
+use diesel::OptionalExtension;
+use lib_core::model::ModelManager;
+use lib_profiles::{
+	achievements::model::{ACHIEVEMENTS, UserAchievement},
+	model::Profile,
+};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+pub fn achievement_routes() -> Router {
+	Router::new()
+		.route("/achievements", get(get_catalog))
+		.route("/users/{user_id}/achievements", get(get_user_achievements))
+}
+
+async fn get_catalog() -> impl IntoResponse {
+	(StatusCode::OK, Json(ACHIEVEMENTS))
+}
+
+async fn get_user_achievements(
+	Path(user_id): Path<Uuid>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+) -> Result<impl IntoResponse, Error> {
+	let unlocked = mm
+		.run_blocking_read(move |conn| {
+			if Profile::get_user_profile(conn, user_id)
+				.optional()?
+				.is_none()
+			{
+				return Ok(None);
+			}
+			UserAchievement::list_by_user(conn, user_id)
+				.map(Some)
+				.map_err(Error::from)
+		})
+		.await?;
+
+	match unlocked {
+		Some(unlocked) => Ok((StatusCode::OK, Json(unlocked)).into_response()),
+		None => Ok(StatusCode::NOT_FOUND.into_response()),
+	}
+}
@@ -0,0 +1,108 @@
+use std::path::PathBuf;
+
+use axum::{
+	body::Bytes,
+	extract::Path,
+	http::{StatusCode, header},
+	response::IntoResponse,
+};
+use lib_core::config::core_config;
+use uuid::Uuid;
+
+use crate::error::Error;
+
+/// Extensions of the image formats accepted as avatars.
+const AVATAR_EXTENSIONS: [&str; 4] = ["png", "jpg", "gif", "webp"];
+
+/// Detects the image format from its magic bytes,
+/// the client supplied content type is not trusted.
+pub fn detect_image_ext(bytes: &[u8]) -> Option<&'static str> {
+	if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+		Some("png")
+	} else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+		Some("jpg")
+	} else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+		Some("gif")
+	} else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP"
+	{
+		Some("webp")
+	} else {
+		None
+	}
+}
+
+fn content_type(ext: &str) -> &'static str {
+	match ext {
+		"png" => "image/png",
+		"jpg" => "image/jpeg",
+		"gif" => "image/gif",
+		_ => "image/webp",
+	}
+}
+
+fn avatar_path(file_name: &str) -> PathBuf {
+	PathBuf::from(&core_config().AVATAR_DIR).join(file_name)
+}
+
+/// Stores the avatar as `<user_id>.<ext>` and returns its public url.
+/// Avatars of the user in other formats are removed.
+pub async fn save_avatar(user_id: Uuid, bytes: Bytes) -> Result<String, Error> {
+	if bytes.len() > core_config().AVATAR_MAX_BYTES {
+		return Err(Error::Validation(format!(
+			"Avatar must be at most {} bytes",
+			core_config().AVATAR_MAX_BYTES
+		)));
+	}
+
+	let ext = detect_image_ext(&bytes).ok_or_else(|| {
+		Error::Validation(
+			"Avatar must be a png, jpeg, gif or webp image".to_string(),
+		)
+	})?;
+
+	tokio::fs::create_dir_all(&core_config().AVATAR_DIR)
+		.await
+		.map_err(|e| Error::Storage(e.to_string()))?;
+
+	for other in AVATAR_EXTENSIONS.iter().filter(|other| **other != ext) {
+		let _ =
+			tokio::fs::remove_file(avatar_path(&format!("{user_id}.{other}"))).await;
+	}
+
+	let file_name = format!("{user_id}.{ext}");
+	tokio::fs::write(avatar_path(&file_name), &bytes)
+		.await
+		.map_err(|e| Error::Storage(e.to_string()))?;
+
+	// The version query busts client caches after a re-upload
+	Ok(format!(
+		"/avatars/{file_name}?v={}",
+		chrono::Utc::now().timestamp()
+	))
+}
+
+/// Serves an uploaded avatar. Only `<uuid>.<known ext>` names are accepted,
+/// which also keeps requests inside the avatar directory.
+pub async fn get_avatar(Path(file_name): Path<String>) -> impl IntoResponse {
+	let valid = file_name.split_once('.').is_some_and(|(id, ext)| {
+		Uuid::parse_str(id).is_ok() && AVATAR_EXTENSIONS.contains(&ext)
+	});
+	if !valid {
+		return StatusCode::NOT_FOUND.into_response();
+	}
+
+	let ext = file_name.rsplit('.').next().unwrap_or_default();
+
+	match tokio::fs::read(avatar_path(&file_name)).await {
+		Ok(bytes) => (
+			StatusCode::OK,
+			[
+				(header::CONTENT_TYPE, content_type(ext)),
+				(header::CACHE_CONTROL, "public, max-age=86400"),
+			],
+			bytes,
+		)
+			.into_response(),
+		Err(_) => StatusCode::NOT_FOUND.into_response(),
+	}
+}
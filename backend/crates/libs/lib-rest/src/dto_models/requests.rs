@@ -1,3 +1,6 @@
+use lib_core::model::schema_enums::{
+	BotDifficulty, NarratorMode, PlayMode, TurnOrderMode, VisibilityMode,
+};
 use serde::Deserialize;
 use uuid::Uuid;
 
@@ -5,31 +8,180 @@ use uuid::Uuid;
 pub struct CreateSessionPayload {
 	pub theme: String,
 	pub max_rounds: i32,
+	/// Endings generated for the vote, a single story when absent.
+	pub story_variants: Option<i32>,
+	/// The previous message in full when absent.
+	#[serde(default)]
+	pub visibility: VisibilityMode,
+	/// Words shown in `last_words` mode, 10 when absent.
+	pub visibility_words: Option<i32>,
+	/// `linear` when absent.
+	#[serde(default)]
+	pub play_mode: PlayMode,
+	/// `join_order` when absent.
+	#[serde(default)]
+	pub turn_order: TurnOrderMode,
+	/// Bots take over the seats of players leaving mid-game, off when absent.
+	#[serde(default)]
+	pub bot_backfill: bool,
+	/// Interludes between rounds of linear sessions, `off` when absent.
+	#[serde(default)]
+	pub narrator: NarratorMode,
+	/// Competitive mode with secret objectives, off when absent.
+	#[serde(default)]
+	pub objectives: bool,
+	/// Seconds a line can be taken back in linear sessions, 0 when absent.
+	#[serde(default)]
+	pub undo_window_secs: i32,
 }
 
 #[derive(Deserialize)]
 pub struct JoinSessionPayload {
 	pub session_id: Uuid,
+	pub expected_version: Option<i64>,
 }
 
 #[derive(Deserialize)]
 pub struct LeaveSessionPayload {
 	pub session_id: Uuid,
+	pub expected_version: Option<i64>,
 }
 
 #[derive(Deserialize)]
 pub struct ReadyPayload {
 	pub session_id: Uuid,
 	pub is_ready: bool,
+	pub expected_version: Option<i64>,
 }
 
 #[derive(Deserialize)]
 pub struct StartGamePayload {
 	pub session_id: Uuid,
+	pub expected_version: Option<i64>,
 }
 
 #[derive(Deserialize)]
 pub struct SubmitMessagePayload {
 	pub session_id: Uuid,
 	pub content: String,
+	/// Session version the client acted on; stale versions are rejected.
+	pub expected_version: Option<i64>,
+}
+
+/// Every player once, first seat first.
+#[derive(Deserialize)]
+pub struct ArrangeSeatsPayload {
+	pub session_id: Uuid,
+	pub user_ids: Vec<Uuid>,
+	pub expected_version: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct AddBotPayload {
+	pub session_id: Uuid,
+	/// "Bot <seat>" when absent.
+	pub name: Option<String>,
+	pub persona: String,
+	/// `normal` when absent.
+	#[serde(default)]
+	pub difficulty: BotDifficulty,
+	pub expected_version: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct RemoveBotPayload {
+	pub session_id: Uuid,
+	pub user_id: Uuid,
+	pub expected_version: Option<i64>,
+}
+
+/// The line still in its undo window is replaced with `content`.
+#[derive(Deserialize)]
+pub struct EditMessagePayload {
+	pub session_id: Uuid,
+	pub content: String,
+	pub expected_version: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct RetractMessagePayload {
+	pub session_id: Uuid,
+	pub expected_version: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct PassPayload {
+	pub session_id: Uuid,
+	pub expected_version: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct SuggestPayload {
+	pub session_id: Uuid,
+	pub expected_version: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct VoteVariantPayload {
+	pub session_id: Uuid,
+	pub variant_id: Uuid,
+	pub expected_version: Option<i64>,
+}
+
+/// Absent fields are left unchanged, empty strings clear the field.
+#[derive(Deserialize)]
+pub struct UpdateProfilePayload {
+	pub display_name: Option<String>,
+	pub avatar_url: Option<String>,
+	pub bio: Option<String>,
+}
+
+/// 1-based page, `per_page` is capped by the handler.
+#[derive(Deserialize)]
+pub struct PageQuery {
+	pub page: Option<i64>,
+	pub per_page: Option<i64>,
+}
+
+/// `global` ranks all games, `weekly` those started in the last 7 days.
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardPeriod {
+	Global,
+	Weekly,
+}
+
+/// `limit` is capped by the handler.
+#[derive(Deserialize)]
+pub struct LeaderboardQuery {
+	pub limit: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct ExportQuery {
+	/// `md`, `html`, `epub` or `pdf`, Markdown when absent.
+	pub format: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateSharePayload {
+	/// Link lifetime, the link never expires when absent.
+	pub expires_in_hours: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct SharedStoryQuery {
+	/// `json` for the story as JSON, the HTML page otherwise.
+	pub format: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct RatePayload {
+	/// 1 to 5.
+	pub stars: i16,
+}
+
+#[derive(Deserialize)]
+pub struct StoryVisibilityPayload {
+	pub is_public: bool,
 }
@@ -1,5 +1,11 @@
 use chrono::NaiveDateTime;
-use lib_core::{dto::session::UserInSessionDto, model::schema_enums::SessionStatus};
+use lib_core::{
+	dto::{session::UserInSessionDto, story::ReactionCountDto},
+	model::schema_enums::{
+		NarratorMode, PlayMode, ReactionKind, SessionStatus, TurnOrderMode,
+		VisibilityMode,
+	},
+};
 use serde::Serialize;
 use uuid::Uuid;
 
@@ -12,8 +18,10 @@ pub struct SessionResponse {
 #[derive(Serialize)]
 pub struct PlayerResponse {
 	pub user_id: Uuid,
+	pub display_name: String,
 	pub is_ready: bool,
 	pub is_host: bool,
+	pub is_bot: bool,
 }
 
 #[derive(Serialize)]
@@ -25,6 +33,18 @@ pub struct SessionWithUsersDto {
 	pub max_rounds: i32,
 	pub current_round: i32,
 	pub created_at: NaiveDateTime,
+	pub version: i64,
+	pub story_variants: i32,
+	pub visibility: VisibilityMode,
+	pub visibility_words: i32,
+	pub play_mode: PlayMode,
+	pub turn_order: TurnOrderMode,
+	pub bot_backfill: bool,
+	pub narrator: NarratorMode,
+	pub objectives: bool,
+	pub undo_window_secs: i32,
+	/// Set while the player on turn can still take back their line.
+	pub pending_message_id: Option<Uuid>,
 	pub users: Vec<UserInSessionDto>,
 }
 
@@ -38,15 +58,134 @@ impl From<lib_sessions::model::SessionWithUsersInSession> for SessionWithUsersDt
 			max_rounds: model.max_rounds,
 			current_round: model.current_round,
 			created_at: model.created_at,
+			version: model.version,
+			story_variants: model.story_variants,
+			visibility: model.visibility,
+			visibility_words: model.visibility_words,
+			play_mode: model.play_mode,
+			turn_order: model.turn_order,
+			bot_backfill: model.bot_backfill,
+			narrator: model.narrator,
+			objectives: model.objectives,
+			undo_window_secs: model.undo_window_secs,
+			pending_message_id: model.pending_message_id,
 			users: model
 				.users
 				.into_iter()
 				.map(|user| UserInSessionDto {
 					user_id: user.user_id,
+					display_name: user.display_name,
 					is_ready: user.is_ready,
 					is_host: user.is_host,
+					seat: user.seat,
+					is_bot: user.is_bot,
 				})
 				.collect(),
 		}
 	}
 }
+
+#[derive(Serialize)]
+pub struct StoryParticipantDto {
+	pub user_id: Uuid,
+	pub display_name: String,
+}
+
+#[derive(Serialize)]
+pub struct StoryMessageDto {
+	/// `None` for the narrator's interludes.
+	pub user_id: Option<Uuid>,
+	pub display_name: String,
+	pub content: String,
+	pub round: i32,
+	pub turn_order: i32,
+	pub created_at: NaiveDateTime,
+	pub used_suggestion: bool,
+}
+
+#[derive(Serialize)]
+pub struct StoryDto {
+	pub id: Uuid,
+	pub session_id: Uuid,
+	pub thread_id: Option<Uuid>,
+	pub theme: String,
+	pub content: String,
+	pub created_at: NaiveDateTime,
+	pub is_public: bool,
+	pub participants: Vec<StoryParticipantDto>,
+	/// Original messages in turn order.
+	pub messages: Vec<StoryMessageDto>,
+}
+
+#[derive(Serialize)]
+pub struct Page<T> {
+	pub items: Vec<T>,
+	pub page: i64,
+	pub per_page: i64,
+	pub total: i64,
+}
+
+#[derive(Serialize)]
+pub struct ShareDto {
+	pub id: Uuid,
+	pub story_id: Uuid,
+	pub token: String,
+	/// Public link to hand out.
+	pub url: String,
+	pub expires_at: Option<NaiveDateTime>,
+	pub revoked_at: Option<NaiveDateTime>,
+	pub view_count: i64,
+	pub created_at: NaiveDateTime,
+}
+
+impl From<lib_stories::shares::model::StoryShare> for ShareDto {
+	fn from(share: lib_stories::shares::model::StoryShare) -> Self {
+		Self {
+			url: format!(
+				"{}/s/{}",
+				lib_core::config::core_config().PUBLIC_BASE_URL,
+				share.token
+			),
+			id: share.id,
+			story_id: share.story_id,
+			token: share.token,
+			expires_at: share.expires_at,
+			revoked_at: share.revoked_at,
+			view_count: share.view_count,
+			created_at: share.created_at,
+		}
+	}
+}
+
+#[derive(Serialize)]
+pub struct StoryStatsDto {
+	pub story_id: Uuid,
+	pub reactions: Vec<ReactionCountDto>,
+	pub rating_count: i64,
+	pub rating_avg: Option<f64>,
+	/// The caller's own votes.
+	pub my_reactions: Vec<ReactionKind>,
+	pub my_rating: Option<i16>,
+}
+
+#[derive(Serialize)]
+pub struct FeedItemDto {
+	pub id: Uuid,
+	pub session_id: Uuid,
+	pub theme: String,
+	/// Beginning of the story, the full text is at `GET /api/stories/{id}`.
+	pub excerpt: String,
+	pub created_at: NaiveDateTime,
+	pub reactions: Vec<ReactionCountDto>,
+	pub rating_count: i64,
+	pub rating_avg: Option<f64>,
+}
+
+#[derive(Serialize)]
+pub struct VariantWithVotesDto {
+	pub variant_id: Uuid,
+	pub variant_index: i32,
+	pub style: String,
+	pub content: String,
+	pub votes: i64,
+}
@@ -19,12 +19,52 @@ pub enum Error {
 
 	#[from(diesel::result::Error)]
 	DbError(#[serde_as(as = "DisplayFromStr")] Arc<diesel::result::Error>),
+
+	#[from(ignore)]
+	ModelError(
+		#[serde_as(as = "DisplayFromStr")] Arc<lib_core::model::error::Error>,
+	),
+
+	#[from(lib_export::error::Error)]
+	Export(#[serde_as(as = "DisplayFromStr")] Arc<lib_export::error::Error>),
+
+	#[from(ignore)]
+	Validation(String),
+
+	#[from(ignore)]
+	Forbidden(String),
+
+	#[from(ignore)]
+	Storage(String),
+}
+
+impl From<lib_core::model::error::Error> for Error {
+	fn from(ex: lib_core::model::error::Error) -> Self {
+		match ex {
+			lib_core::model::error::Error::Diesel(ex) => {
+				Error::DbError(Arc::new(ex))
+			}
+			other => Error::ModelError(Arc::new(other)),
+		}
+	}
 }
 
 impl Error {
 	pub fn client_status_and_error(&self) -> (StatusCode, ClientError) {
 		match self {
 			Error::GameEngineError(e) => e.client_status_and_error(),
+			Error::ModelError(_) => (
+				StatusCode::SERVICE_UNAVAILABLE,
+				ClientError::INTERNAL_SERVER_ERROR,
+			),
+			Error::Validation(detail) => (
+				StatusCode::BAD_REQUEST,
+				ClientError::VALIDATION_FAILED(detail.clone()),
+			),
+			Error::Forbidden(detail) => (
+				StatusCode::FORBIDDEN,
+				ClientError::FORBIDDEN(detail.clone()),
+			),
 			_ => (
 				StatusCode::INTERNAL_SERVER_ERROR,
 				ClientError::INTERNAL_SERVER_ERROR,
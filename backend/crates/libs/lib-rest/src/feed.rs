@@ -0,0 +1,320 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+	Router,
+	extract::{Extension, Json, Path, Query},
+	http::StatusCode,
+	response::IntoResponse,
+	routing::{get, put},
+};
+use diesel::{OptionalExtension, PgConnection};
+use lib_core::{
+	ctx::Ctx,
+	dto::story::ReactionCountDto,
+	model::{ModelManager, base::BasicDbOps, schema_enums::ReactionKind},
+};
+use lib_game_events::event::story::StoryEvent;
+use lib_game_logic::engine::GameEngine;
+use lib_rate_limit::layer::RateLimitLayer;
+use lib_stories::{
+	model::Story,
+	reactions::model::{StoryRating, StoryReaction},
+};
+use uuid::Uuid;
+
+use crate::{
+	dto_models::{
+		requests::{PageQuery, RatePayload, StoryVisibilityPayload},
+		responses::{FeedItemDto, Page, StoryStatsDto},
+	},
+	error::Error,
+};
+
+const DEFAULT_PER_PAGE: i64 = 20;
+const MAX_PER_PAGE: i64 = 50;
+const EXCERPT_MAX_CHARS: usize = 280;
+
+/// Feed, reactions and ratings, `mutation_limit` applies to votes.
+pub fn feed_routes(mutation_limit: RateLimitLayer) -> Router {
+	let mutations = Router::new()
+		.route(
+			"/stories/{story_id}/reactions/{kind}",
+			put(add_reaction).delete(remove_reaction),
+		)
+		.route(
+			"/stories/{story_id}/rating",
+			put(rate).delete(remove_rating),
+		)
+		.route("/stories/{story_id}/visibility", put(set_visibility))
+		.route_layer(mutation_limit);
+
+	Router::new()
+		.route("/feed", get(get_feed))
+		.route("/stories/{story_id}/stats", get(get_stats))
+		.merge(mutations)
+}
+
+async fn get_feed(
+	Query(query): Query<PageQuery>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+) -> Result<impl IntoResponse, Error> {
+	let page = query.page.unwrap_or(1).max(1);
+	let per_page = query
+		.per_page
+		.unwrap_or(DEFAULT_PER_PAGE)
+		.clamp(1, MAX_PER_PAGE);
+
+	let feed = mm
+		.run_blocking_read(move |conn| {
+			let total = Story::count_public(conn)?;
+			let stories = Story::feed(conn, per_page, (page - 1) * per_page)?;
+
+			let ids: Vec<Uuid> = stories.iter().map(|story| story.id).collect();
+			let mut reactions: HashMap<Uuid, Vec<ReactionCountDto>> = HashMap::new();
+			for (story_id, kind, count) in StoryReaction::counts(conn, &ids)? {
+				reactions
+					.entry(story_id)
+					.or_default()
+					.push(ReactionCountDto { kind, count });
+			}
+
+			let items = stories
+				.into_iter()
+				.map(|story| FeedItemDto {
+					reactions: reactions.remove(&story.id).unwrap_or_default(),
+					excerpt: excerpt(&story.content),
+					id: story.id,
+					session_id: story.session_id,
+					theme: story.theme,
+					created_at: story.created_at,
+					rating_count: story.rating_count,
+					rating_avg: story.rating_avg,
+				})
+				.collect();
+
+			Ok::<_, Error>(Page {
+				items,
+				page,
+				per_page,
+				total,
+			})
+		})
+		.await?;
+
+	Ok((StatusCode::OK, Json(feed)))
+}
+
+async fn get_stats(
+	ctx: Ctx,
+	Path(story_id): Path<Uuid>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+) -> Result<impl IntoResponse, Error> {
+	let stats = mm
+		.run_blocking_read(move |conn| {
+			if Story::get_visible(conn, story_id, ctx.user_id)?.is_none() {
+				return Ok(None);
+			}
+			story_stats(conn, story_id, ctx.user_id).map(Some)
+		})
+		.await?;
+
+	Ok(stats_response(stats))
+}
+
+async fn add_reaction(
+	ctx: Ctx,
+	Path((story_id, kind)): Path<(Uuid, ReactionKind)>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+	Extension(game_engine): Extension<Arc<GameEngine>>,
+) -> Result<impl IntoResponse, Error> {
+	let stats = mm
+		.run_blocking(move |conn| {
+			if Story::get_visible(conn, story_id, ctx.user_id)?.is_none() {
+				return Ok(None);
+			}
+			let changed = StoryReaction::add(conn, story_id, ctx.user_id, kind)?;
+			story_stats(conn, story_id, ctx.user_id)
+				.map(|stats| Some((changed, stats)))
+		})
+		.await?;
+
+	Ok(reactions_changed(&game_engine, stats))
+}
+
+async fn remove_reaction(
+	ctx: Ctx,
+	Path((story_id, kind)): Path<(Uuid, ReactionKind)>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+	Extension(game_engine): Extension<Arc<GameEngine>>,
+) -> Result<impl IntoResponse, Error> {
+	let stats = mm
+		.run_blocking(move |conn| {
+			if Story::get_visible(conn, story_id, ctx.user_id)?.is_none() {
+				return Ok(None);
+			}
+			let changed = StoryReaction::remove(conn, story_id, ctx.user_id, kind)?;
+			story_stats(conn, story_id, ctx.user_id)
+				.map(|stats| Some((changed, stats)))
+		})
+		.await?;
+
+	Ok(reactions_changed(&game_engine, stats))
+}
+
+async fn rate(
+	ctx: Ctx,
+	Path(story_id): Path<Uuid>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+	Extension(game_engine): Extension<Arc<GameEngine>>,
+	Json(payload): Json<RatePayload>,
+) -> Result<impl IntoResponse, Error> {
+	if !(1..=5).contains(&payload.stars) {
+		return Err(Error::Validation(
+			"Rating must be between 1 and 5 stars".to_string(),
+		));
+	}
+
+	let rated = mm
+		.run_blocking(move |conn| {
+			let Some(story) = Story::get_visible(conn, story_id, ctx.user_id)?
+			else {
+				return Ok(None);
+			};
+			StoryRating::upsert(conn, story_id, ctx.user_id, payload.stars)?;
+			story_stats(conn, story_id, ctx.user_id)
+				.map(|stats| Some((story.session_id, stats)))
+		})
+		.await?;
+
+	// Rating stories counts towards achievements
+	if let Some((session_id, _)) = rated {
+		game_engine
+			.run(move |engine| engine.check_achievements_of(session_id, ctx.user_id))
+			.await?;
+	}
+
+	Ok(rating_changed(
+		&game_engine,
+		rated.map(|(_, stats)| (true, stats)),
+	))
+}
+
+async fn remove_rating(
+	ctx: Ctx,
+	Path(story_id): Path<Uuid>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+	Extension(game_engine): Extension<Arc<GameEngine>>,
+) -> Result<impl IntoResponse, Error> {
+	let stats = mm
+		.run_blocking(move |conn| {
+			if Story::get_visible(conn, story_id, ctx.user_id)?.is_none() {
+				return Ok(None);
+			}
+			let changed = StoryRating::remove(conn, story_id, ctx.user_id)?;
+			story_stats(conn, story_id, ctx.user_id)
+				.map(|stats| Some((changed, stats)))
+		})
+		.await?;
+
+	Ok(rating_changed(&game_engine, stats))
+}
+
+/// Publishes the story to the feed, or takes it back. Participants only.
+async fn set_visibility(
+	ctx: Ctx,
+	Path(story_id): Path<Uuid>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+	Json(payload): Json<StoryVisibilityPayload>,
+) -> Result<impl IntoResponse, Error> {
+	let updated = mm
+		.run_blocking(move |conn| {
+			if Story::get(conn, story_id).optional()?.is_none() {
+				return Ok(false);
+			}
+			if !Story::is_participant(conn, story_id, ctx.user_id)? {
+				return Err(Error::Forbidden(
+					"Only participants can publish a story".to_string(),
+				));
+			}
+			Story::set_public(conn, story_id, payload.is_public)?;
+			Ok(true)
+		})
+		.await?;
+
+	if updated {
+		Ok(StatusCode::NO_CONTENT)
+	} else {
+		Ok(StatusCode::NOT_FOUND)
+	}
+}
+
+fn story_stats(
+	conn: &mut PgConnection,
+	story_id: Uuid,
+	user_id: Uuid,
+) -> Result<StoryStatsDto, Error> {
+	let reactions = StoryReaction::counts(conn, &[story_id])?
+		.into_iter()
+		.map(|(_, kind, count)| ReactionCountDto { kind, count })
+		.collect();
+	let rating = StoryRating::summary(conn, story_id)?;
+
+	Ok(StoryStatsDto {
+		story_id,
+		reactions,
+		rating_count: rating.count,
+		rating_avg: rating.average,
+		my_reactions: StoryReaction::kinds_by_user(conn, story_id, user_id)?,
+		my_rating: StoryRating::get_by_user(conn, story_id, user_id)?
+			.map(|rating| rating.stars),
+	})
+}
+
+/// Broadcasts the new totals when the vote changed anything.
+fn reactions_changed(
+	game_engine: &GameEngine,
+	stats: Option<(bool, StoryStatsDto)>,
+) -> axum::response::Response {
+	if let Some((true, stats)) = &stats {
+		game_engine.game_events_manager().send_story_event(
+			StoryEvent::ReactionsUpdated {
+				story_id: stats.story_id,
+				reactions: stats.reactions.clone(),
+			},
+		);
+	}
+
+	stats_response(stats.map(|(_, stats)| stats))
+}
+
+fn rating_changed(
+	game_engine: &GameEngine,
+	stats: Option<(bool, StoryStatsDto)>,
+) -> axum::response::Response {
+	if let Some((true, stats)) = &stats {
+		game_engine.game_events_manager().send_story_event(
+			StoryEvent::RatingUpdated {
+				story_id: stats.story_id,
+				rating_count: stats.rating_count,
+				rating_avg: stats.rating_avg,
+			},
+		);
+	}
+
+	stats_response(stats.map(|(_, stats)| stats))
+}
+
+fn stats_response(stats: Option<StoryStatsDto>) -> axum::response::Response {
+	match stats {
+		Some(stats) => (StatusCode::OK, Json(stats)).into_response(),
+		None => StatusCode::NOT_FOUND.into_response(),
+	}
+}
+
+fn excerpt(content: &str) -> String {
+	let mut excerpt: String = content.chars().take(EXCERPT_MAX_CHARS).collect();
+	if content.chars().count() > EXCERPT_MAX_CHARS {
+		excerpt.push('…');
+	}
+	excerpt
+}
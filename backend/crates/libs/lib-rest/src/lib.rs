@@ -1,4 +1,11 @@
+pub mod achievements;
+pub mod avatar;
 pub mod dto_models;
 pub mod error;
+pub mod feed;
+pub mod profile;
 pub mod router;
 pub mod session;
+pub mod share;
+pub mod stats;
+pub mod story;
@@ -0,0 +1,169 @@
+use std::sync::Arc;
+
+use axum::{
+	Router,
+	body::Bytes,
+	extract::{DefaultBodyLimit, Extension, Json, Path},
+	http::StatusCode,
+	response::IntoResponse,
+	routing::{get, put},
+};
+use diesel::OptionalExtension;
+use lib_core::{config::core_config, ctx::Ctx, model::ModelManager};
+use lib_profiles::model::Profile;
+use uuid::Uuid;
+
+use crate::{
+	avatar::save_avatar, dto_models::requests::UpdateProfilePayload, error::Error,
+};
+
+const DISPLAY_NAME_MAX_LEN: usize = 40;
+const BIO_MAX_LEN: usize = 500;
+const AVATAR_URL_MAX_LEN: usize = 2048;
+
+pub fn profile_routes() -> Router {
+	Router::new()
+		.route("/me", get(get_me).patch(update_me))
+		.route(
+			"/me/avatar",
+			// Leave room above the limit, so oversized uploads get a clear error
+			put(upload_avatar)
+				.layer(DefaultBodyLimit::max(core_config().AVATAR_MAX_BYTES + 1)),
+		)
+		.route("/users/{user_id}", get(get_user))
+}
+
+async fn get_me(
+	ctx: Ctx,
+	Extension(mm): Extension<Arc<ModelManager>>,
+) -> Result<impl IntoResponse, Error> {
+	let profile = mm
+		.run_blocking(move |conn| {
+			Profile::get_user_profile(conn, ctx.user_id).map_err(Error::from)
+		})
+		.await?;
+
+	Ok((StatusCode::OK, Json(profile)))
+}
+
+async fn update_me(
+	ctx: Ctx,
+	Extension(mm): Extension<Arc<ModelManager>>,
+	Json(payload): Json<UpdateProfilePayload>,
+) -> Result<impl IntoResponse, Error> {
+	let display_name = payload
+		.display_name
+		.map(|name| {
+			validate_text("Display name", &name, DISPLAY_NAME_MAX_LEN, false)
+		})
+		.transpose()?;
+	let bio = payload
+		.bio
+		.map(|bio| validate_text("Bio", &bio, BIO_MAX_LEN, true))
+		.transpose()?;
+	let avatar_url = payload
+		.avatar_url
+		.map(|url| validate_avatar_url(&url))
+		.transpose()?;
+
+	let profile = mm
+		.run_blocking(move |conn| {
+			let mut profile = Profile::get_or_empty(conn, ctx.user_id)?;
+
+			if let Some(display_name) = display_name {
+				profile.display_name = display_name;
+			}
+			if let Some(bio) = bio {
+				profile.bio = bio;
+			}
+			if let Some(avatar_url) = avatar_url {
+				profile.avatar_url = avatar_url;
+			}
+			profile.updated_at = chrono::Utc::now().naive_utc();
+
+			Profile::upsert(conn, &profile)?;
+			Profile::get_user_profile(conn, ctx.user_id).map_err(Error::from)
+		})
+		.await?;
+
+	Ok((StatusCode::OK, Json(profile)))
+}
+
+async fn upload_avatar(
+	ctx: Ctx,
+	Extension(mm): Extension<Arc<ModelManager>>,
+	body: Bytes,
+) -> Result<impl IntoResponse, Error> {
+	let avatar_url = save_avatar(ctx.user_id, body).await?;
+
+	let profile = mm
+		.run_blocking(move |conn| {
+			let mut profile = Profile::get_or_empty(conn, ctx.user_id)?;
+			profile.avatar_url = Some(avatar_url);
+			profile.updated_at = chrono::Utc::now().naive_utc();
+
+			Profile::upsert(conn, &profile)?;
+			Profile::get_user_profile(conn, ctx.user_id).map_err(Error::from)
+		})
+		.await?;
+
+	Ok((StatusCode::OK, Json(profile)))
+}
+
+async fn get_user(
+	Path(user_id): Path<Uuid>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+) -> Result<impl IntoResponse, Error> {
+	match mm
+		.run_blocking_read(move |conn| {
+			Profile::get_user_profile(conn, user_id)
+				.optional()
+				.map_err(Error::from)
+		})
+		.await?
+	{
+		Some(profile) => Ok((StatusCode::OK, Json(profile)).into_response()),
+		None => Ok(StatusCode::NOT_FOUND.into_response()),
+	}
+}
+
+/// Trims the text, `None` (clear the field) when nothing is left.
+/// Line breaks are the only control characters `multiline` fields accept.
+fn validate_text(
+	field: &str,
+	value: &str,
+	max_len: usize,
+	multiline: bool,
+) -> Result<Option<String>, Error> {
+	let value = value.trim();
+	if value.chars().count() > max_len {
+		return Err(Error::Validation(format!(
+			"{field} must be at most {max_len} characters"
+		)));
+	}
+	let allowed = |c: char| multiline && (c == '\n' || c == '\r');
+	if value.chars().any(|c| c.is_control() && !allowed(c)) {
+		return Err(Error::Validation(format!(
+			"{field} must not contain control characters"
+		)));
+	}
+
+	Ok((!value.is_empty()).then(|| value.to_string()))
+}
+
+fn validate_avatar_url(url: &str) -> Result<Option<String>, Error> {
+	let url = url.trim();
+	if url.is_empty() {
+		return Ok(None);
+	}
+
+	if url.len() > AVATAR_URL_MAX_LEN
+		|| !(url.starts_with("https://") || url.starts_with("http://"))
+	{
+		return Err(Error::Validation(
+			"Avatar url must be an http(s) url".to_string(),
+		));
+	}
+
+	Ok(Some(url.to_string()))
+}
@@ -1,13 +1,52 @@
 use std::sync::Arc;
 
-use crate::session::session_routes;
-use axum::Router;
-use lib_core::model::ModelManager;
+use crate::{
+	achievements::achievement_routes,
+	avatar::get_avatar,
+	feed::feed_routes,
+	profile::profile_routes,
+	session::session_routes,
+	share::{get_shared_story, share_routes},
+	stats::stats_routes,
+	story::story_routes,
+};
+use axum::{Router, routing::get};
+use lib_core::{config::core_config, model::ModelManager};
 use lib_game_logic::engine::GameEngine;
+use lib_rate_limit::{
+	layer::{RateLimitKey, RateLimitLayer},
+	limiter::RateLimiter,
+};
 
 pub fn rest_router(mm: Arc<ModelManager>, game_engine: Arc<GameEngine>) -> Router {
+	let mutation_limit = RateLimitLayer::new(
+		RateLimiter::per_minute(core_config().RATE_LIMIT_MUTATIONS_PER_MINUTE),
+		RateLimitKey::UserOrClientIp,
+	);
+
 	Router::new()
-		.nest("/api", session_routes())
+		.nest(
+			"/api",
+			session_routes(mutation_limit.clone())
+				.merge(feed_routes(mutation_limit))
+				.merge(profile_routes())
+				.merge(story_routes())
+				.merge(share_routes())
+				.merge(stats_routes())
+				.merge(achievement_routes()),
+		)
 		.layer(axum::Extension(mm))
 		.layer(axum::Extension(game_engine))
 }
+
+/// Uploaded avatars, public so they can be used in `<img>` tags.
+pub fn avatar_router() -> Router {
+	Router::new().route("/avatars/{file_name}", get(get_avatar))
+}
+
+/// Stories behind share links, public so links work without an account.
+pub fn public_share_router(mm: Arc<ModelManager>) -> Router {
+	Router::new()
+		.route("/s/{token}", get(get_shared_story))
+		.layer(axum::Extension(mm))
+}
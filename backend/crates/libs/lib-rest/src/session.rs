@@ -3,35 +3,61 @@ use axum::{
 	extract::{Extension, Json, Path},
 	http::StatusCode,
 	response::IntoResponse,
-	routing::{delete, get, post},
+	routing::{delete, get, post, put},
 };
 use lib_core::{ctx::Ctx, model::ModelManager};
-use lib_game_logic::engine::GameEngine;
-use lib_players::model::Player;
+use lib_game_logic::{
+	engine::{GameEngine, SessionSettings},
+	error::Error as GameEngineError,
+};
+use lib_players::{bots::model::Bot, model::Player};
+use lib_profiles::model::Profile;
+use lib_rate_limit::layer::RateLimitLayer;
 use lib_sessions::model::Session;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::dto_models::{
 	requests::{
-		CreateSessionPayload, JoinSessionPayload, LeaveSessionPayload, ReadyPayload,
-		StartGamePayload, SubmitMessagePayload,
+		AddBotPayload, ArrangeSeatsPayload, CreateSessionPayload,
+		EditMessagePayload, JoinSessionPayload, LeaveSessionPayload, PassPayload,
+		ReadyPayload, RemoveBotPayload, RetractMessagePayload, StartGamePayload,
+		SubmitMessagePayload, SuggestPayload, VoteVariantPayload,
+	},
+	responses::{
+		PlayerResponse, SessionResponse, SessionWithUsersDto, VariantWithVotesDto,
 	},
-	responses::{PlayerResponse, SessionResponse, SessionWithUsersDto},
 };
 
 use crate::error::Error;
 
-pub fn session_routes() -> Router {
-	Router::new()
+/// Session routes, `mutation_limit` applies to the state-changing ones only.
+pub fn session_routes(mutation_limit: RateLimitLayer) -> Router {
+	let mutations = Router::new()
 		.route("/sessions", post(create_session))
 		.route("/sessions/join", post(join_session))
 		.route("/sessions/leave", delete(leave_session))
 		.route("/sessions/ready", post(set_ready))
+		.route("/sessions/seats", put(arrange_seats))
+		.route("/sessions/bots", post(add_bot).delete(remove_bot))
 		.route("/sessions/start", post(start_game))
-		.route("/sessions/message", post(submit_message))
+		.route(
+			"/sessions/message",
+			post(submit_message)
+				.put(edit_message)
+				.delete(retract_message),
+		)
+		.route("/sessions/pass", post(pass_turn))
+		.route("/sessions/suggest", post(suggest))
+		.route("/sessions/vote", post(vote_variant))
+		.route_layer(mutation_limit);
+
+	Router::new()
 		.route("/sessions/{session_id}", get(get_session))
+		.route("/sessions/{session_id}/variants", get(get_variants))
+		.route("/sessions/{session_id}/scores", get(get_scores))
 		.route("/sessions", get(get_sessions))
+		.merge(mutations)
 }
 
 async fn create_session(
@@ -40,25 +66,34 @@ async fn create_session(
 	Extension(game_engine): Extension<Arc<GameEngine>>,
 	Json(payload): Json<CreateSessionPayload>,
 ) -> Result<impl IntoResponse, Error> {
-	let mut conn = mm.db();
-
-	let session = game_engine.create_session(
-		&payload.theme,
-		ctx.user_id,
-		payload.max_rounds,
-	)?;
+	let session = game_engine
+		.run(move |engine| {
+			engine.create_session(
+				&payload.theme,
+				ctx.user_id,
+				SessionSettings {
+					max_rounds: payload.max_rounds,
+					story_variants: payload.story_variants.unwrap_or(1),
+					visibility: payload.visibility,
+					visibility_words: payload.visibility_words.unwrap_or(10),
+					play_mode: payload.play_mode,
+					turn_order: payload.turn_order,
+					bot_backfill: payload.bot_backfill,
+					narrator: payload.narrator,
+					objectives: payload.objectives,
+					undo_window_secs: payload.undo_window_secs,
+				},
+			)
+		})
+		.await?;
 
-	let first_player_id = Player::list_by_session(&mut conn, session.id)
-		.unwrap()
-		.first()
-		.unwrap()
-		.user_id;
+	let host_user_id = host_user_id(&mm, session.id).await?;
 
 	Ok((
 		StatusCode::CREATED,
 		Json(SessionResponse {
 			session_id: session.id,
-			host_user_id: first_player_id,
+			host_user_id,
 		}),
 	))
 }
@@ -66,35 +101,37 @@ async fn create_session(
 async fn get_sessions(
 	Extension(mm): Extension<Arc<ModelManager>>,
 ) -> Result<impl IntoResponse, Error> {
-	let mut conn = mm.db();
-
-	Session::list_with_users(&mut conn)
-		.map_err(|e| Error::DbError(e.into()))
-		.map(|items| {
-			(
-				StatusCode::OK,
-				Json::<Vec<SessionWithUsersDto>>(
-					items.into_iter().map(|item| item.into()).collect(),
-				),
-			)
-		})
+	mm.run_blocking_read(|conn| {
+		Session::list_with_users(conn).map_err(|e| Error::DbError(e.into()))
+	})
+	.await
+	.map(|items| {
+		(
+			StatusCode::OK,
+			Json::<Vec<SessionWithUsersDto>>(
+				items.into_iter().map(|item| item.into()).collect(),
+			),
+		)
+	})
 }
 
 async fn join_session(
 	ctx: Ctx,
+	Extension(mm): Extension<Arc<ModelManager>>,
 	Extension(game_engine): Extension<Arc<GameEngine>>,
 	Json(payload): Json<JoinSessionPayload>,
 ) -> Result<impl IntoResponse, Error> {
-	let player = game_engine.join_session(payload.session_id, ctx.user_id)?;
+	let player = game_engine
+		.run(move |engine| {
+			engine.join_session(
+				payload.session_id,
+				ctx.user_id,
+				payload.expected_version,
+			)
+		})
+		.await?;
 
-	Ok((
-		StatusCode::OK,
-		Json(PlayerResponse {
-			user_id: player.user_id,
-			is_ready: player.is_ready,
-			is_host: player.is_host,
-		}),
-	))
+	Ok((StatusCode::OK, Json(player_response(&mm, player).await?)))
 }
 
 async fn leave_session(
@@ -102,50 +139,125 @@ async fn leave_session(
 	Extension(game_engine): Extension<Arc<GameEngine>>,
 	Json(payload): Json<LeaveSessionPayload>,
 ) -> Result<impl IntoResponse, Error> {
-	game_engine.leave_session(payload.session_id, ctx.user_id)?;
+	game_engine
+		.run(move |engine| {
+			engine.leave_session(
+				payload.session_id,
+				ctx.user_id,
+				payload.expected_version,
+			)
+		})
+		.await?;
 
 	Ok(StatusCode::NO_CONTENT.into_response())
 }
 
 async fn set_ready(
 	ctx: Ctx,
+	Extension(mm): Extension<Arc<ModelManager>>,
 	Extension(game_engine): Extension<Arc<GameEngine>>,
 	Json(payload): Json<ReadyPayload>,
 ) -> Result<impl IntoResponse, Error> {
-	let player =
-		game_engine.set_ready(payload.session_id, ctx.user_id, payload.is_ready)?;
+	let player = game_engine
+		.run(move |engine| {
+			engine.set_ready(
+				payload.session_id,
+				ctx.user_id,
+				payload.is_ready,
+				payload.expected_version,
+			)
+		})
+		.await?;
+
+	Ok((StatusCode::OK, Json(player_response(&mm, player).await?)))
+}
+
+async fn arrange_seats(
+	ctx: Ctx,
+	Extension(game_engine): Extension<Arc<GameEngine>>,
+	Json(payload): Json<ArrangeSeatsPayload>,
+) -> Result<impl IntoResponse, Error> {
+	let users = game_engine
+		.run(move |engine| {
+			engine.arrange_seats(
+				payload.session_id,
+				ctx.user_id,
+				payload.user_ids,
+				payload.expected_version,
+			)
+		})
+		.await?;
+
+	Ok((StatusCode::OK, Json(users)))
+}
+
+async fn add_bot(
+	ctx: Ctx,
+	Extension(mm): Extension<Arc<ModelManager>>,
+	Extension(game_engine): Extension<Arc<GameEngine>>,
+	Json(payload): Json<AddBotPayload>,
+) -> Result<impl IntoResponse, Error> {
+	let player = game_engine
+		.run(move |engine| {
+			engine.add_bot(
+				payload.session_id,
+				ctx.user_id,
+				payload.name.as_deref(),
+				&payload.persona,
+				payload.difficulty,
+				payload.expected_version,
+			)
+		})
+		.await?;
 
 	Ok((
-		StatusCode::OK,
-		Json(PlayerResponse {
-			user_id: player.user_id,
-			is_ready: player.is_ready,
-			is_host: player.is_host,
-		}),
+		StatusCode::CREATED,
+		Json(player_response(&mm, player).await?),
 	))
 }
 
+async fn remove_bot(
+	ctx: Ctx,
+	Extension(game_engine): Extension<Arc<GameEngine>>,
+	Json(payload): Json<RemoveBotPayload>,
+) -> Result<impl IntoResponse, Error> {
+	game_engine
+		.run(move |engine| {
+			engine.remove_bot(
+				payload.session_id,
+				ctx.user_id,
+				payload.user_id,
+				payload.expected_version,
+			)
+		})
+		.await?;
+
+	Ok(StatusCode::NO_CONTENT.into_response())
+}
+
 async fn start_game(
 	ctx: Ctx,
 	Extension(mm): Extension<Arc<ModelManager>>,
 	Extension(game_engine): Extension<Arc<GameEngine>>,
 	Json(payload): Json<StartGamePayload>,
 ) -> Result<impl IntoResponse, Error> {
-	let mut conn = mm.db();
-
-	let session = game_engine.start_game(payload.session_id, ctx.user_id)?;
+	let session = game_engine
+		.run(move |engine| {
+			engine.start_game(
+				payload.session_id,
+				ctx.user_id,
+				payload.expected_version,
+			)
+		})
+		.await?;
 
-	let first_player_id = Player::list_by_session(&mut conn, session.id)
-		.unwrap()
-		.first()
-		.unwrap()
-		.user_id;
+	let host_user_id = host_user_id(&mm, session.id).await?;
 
 	Ok((
 		StatusCode::OK,
 		Json(SessionResponse {
 			session_id: session.id,
-			host_user_id: first_player_id,
+			host_user_id,
 		}),
 	))
 }
@@ -155,26 +267,191 @@ async fn submit_message(
 	Extension(game_engine): Extension<Arc<GameEngine>>,
 	Json(payload): Json<SubmitMessagePayload>,
 ) -> Result<impl IntoResponse, Error> {
-	let _ = game_engine.submit_message(
-		payload.session_id,
-		ctx.user_id,
-		&payload.content,
-	)?;
+	game_engine
+		.run(move |engine| {
+			engine.submit_message(
+				payload.session_id,
+				ctx.user_id,
+				&payload.content,
+				payload.expected_version,
+			)
+		})
+		.await?;
+
+	Ok(StatusCode::OK.into_response())
+}
+
+async fn edit_message(
+	ctx: Ctx,
+	Extension(game_engine): Extension<Arc<GameEngine>>,
+	Json(payload): Json<EditMessagePayload>,
+) -> Result<impl IntoResponse, Error> {
+	game_engine
+		.run(move |engine| {
+			engine.edit_message(
+				payload.session_id,
+				ctx.user_id,
+				&payload.content,
+				payload.expected_version,
+			)
+		})
+		.await?;
 
 	Ok(StatusCode::OK.into_response())
 }
 
+async fn retract_message(
+	ctx: Ctx,
+	Extension(game_engine): Extension<Arc<GameEngine>>,
+	Json(payload): Json<RetractMessagePayload>,
+) -> Result<impl IntoResponse, Error> {
+	game_engine
+		.run(move |engine| {
+			engine.retract_message(
+				payload.session_id,
+				ctx.user_id,
+				payload.expected_version,
+			)
+		})
+		.await?;
+
+	Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+async fn pass_turn(
+	ctx: Ctx,
+	Extension(game_engine): Extension<Arc<GameEngine>>,
+	Json(payload): Json<PassPayload>,
+) -> Result<impl IntoResponse, Error> {
+	game_engine
+		.run(move |engine| {
+			engine.pass_turn(
+				payload.session_id,
+				ctx.user_id,
+				payload.expected_version,
+			)
+		})
+		.await?;
+
+	Ok(StatusCode::OK.into_response())
+}
+
+async fn suggest(
+	ctx: Ctx,
+	Extension(game_engine): Extension<Arc<GameEngine>>,
+	Json(payload): Json<SuggestPayload>,
+) -> Result<impl IntoResponse, Error> {
+	game_engine
+		.run(move |engine| {
+			engine.request_suggestions(
+				payload.session_id,
+				ctx.user_id,
+				payload.expected_version,
+			)
+		})
+		.await?;
+
+	// The ideas arrive as game events
+	Ok(StatusCode::ACCEPTED.into_response())
+}
+
+async fn vote_variant(
+	ctx: Ctx,
+	Extension(game_engine): Extension<Arc<GameEngine>>,
+	Json(payload): Json<VoteVariantPayload>,
+) -> Result<impl IntoResponse, Error> {
+	game_engine
+		.run(move |engine| {
+			engine.vote_variant(
+				payload.session_id,
+				ctx.user_id,
+				payload.variant_id,
+				payload.expected_version,
+			)
+		})
+		.await?;
+
+	Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+async fn get_variants(
+	Path(session_id): Path<Uuid>,
+	Extension(game_engine): Extension<Arc<GameEngine>>,
+) -> Result<impl IntoResponse, Error> {
+	let variants = game_engine
+		.run(move |engine| engine.list_variants(session_id))
+		.await?;
+
+	Ok(Json(
+		variants
+			.into_iter()
+			.map(|(variant, votes)| VariantWithVotesDto {
+				variant_id: variant.id,
+				variant_index: variant.variant_index,
+				style: variant.style,
+				content: variant.content,
+				votes,
+			})
+			.collect::<Vec<_>>(),
+	))
+}
+
+async fn get_scores(
+	Path(session_id): Path<Uuid>,
+	Extension(game_engine): Extension<Arc<GameEngine>>,
+) -> Result<impl IntoResponse, Error> {
+	let scores = game_engine
+		.run(move |engine| engine.scoreboard(session_id))
+		.await?;
+
+	Ok(Json(scores))
+}
+
 async fn get_session(
 	Path(session_id): Path<Uuid>,
 	Extension(mm): Extension<Arc<ModelManager>>,
 ) -> Result<impl IntoResponse, Error> {
-	let mut conn = mm.db();
-
-	match Session::get_with_users(&mut conn, session_id)
-		.map_err(|e| Error::DbError(e.into()))?
+	match mm
+		.run_blocking_read(move |conn| {
+			Session::get_with_users(conn, session_id)
+				.map_err(|e| Error::DbError(e.into()))
+		})
+		.await?
 	{
 		Some(item) => Ok((StatusCode::OK, Json::<SessionWithUsersDto>(item.into()))
 			.into_response()),
 		None => Ok(StatusCode::NOT_FOUND.into_response()),
 	}
 }
+
+/// Returns the user id of the session's host, whatever seat they're in.
+async fn host_user_id(mm: &ModelManager, session_id: Uuid) -> Result<Uuid, Error> {
+	mm.run_blocking(move |conn| {
+		Player::get_host(conn, session_id)?
+			.map(|player| player.user_id)
+			.ok_or(Error::GameEngineError(GameEngineError::PlayerNotFound))
+	})
+	.await
+}
+
+async fn player_response(
+	mm: &ModelManager,
+	player: Player,
+) -> Result<PlayerResponse, Error> {
+	let user_id = player.user_id;
+	let (display_name, is_bot) = mm
+		.run_blocking(move |conn| {
+			let display_name = Profile::display_name(conn, user_id)?;
+			let is_bot = Bot::find(conn, user_id)?.is_some();
+			Ok::<_, Error>((display_name, is_bot))
+		})
+		.await?;
+
+	Ok(PlayerResponse {
+		user_id: player.user_id,
+		display_name,
+		is_ready: player.is_ready,
+		is_host: player.is_host,
+		is_bot,
+	})
+}
@@ -0,0 +1,232 @@
+use std::sync::Arc;
+
+use axum::{
+	Router,
+	extract::{Extension, Json, Path, Query},
+	http::{HeaderMap, StatusCode, header},
+	response::{Html, IntoResponse},
+	routing::{delete, get},
+};
+use chrono::{Duration, Utc};
+use diesel::OptionalExtension;
+use lib_core::{
+	config::core_config,
+	ctx::Ctx,
+	model::{ModelManager, base::BasicDbOps},
+};
+use lib_export::html::{escape, render_with_head};
+use lib_stories::{
+	model::Story,
+	shares::model::{NewStoryShare, StoryShare},
+};
+use rand::RngCore;
+use uuid::Uuid;
+
+use crate::{
+	dto_models::{
+		requests::{CreateSharePayload, SharedStoryQuery},
+		responses::ShareDto,
+	},
+	error::Error,
+	story::{story_document, story_dto},
+};
+
+const MAX_EXPIRES_IN_HOURS: i64 = 24 * 365;
+const DESCRIPTION_MAX_CHARS: usize = 200;
+
+pub fn share_routes() -> Router {
+	Router::new()
+		.route(
+			"/stories/{story_id}/shares",
+			get(list_shares).post(create_share),
+		)
+		.route(
+			"/stories/{story_id}/shares/{share_id}",
+			delete(revoke_share),
+		)
+}
+
+async fn create_share(
+	ctx: Ctx,
+	Path(story_id): Path<Uuid>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+	Json(payload): Json<CreateSharePayload>,
+) -> Result<impl IntoResponse, Error> {
+	let expires_at = match payload.expires_in_hours {
+		Some(hours) if !(1..=MAX_EXPIRES_IN_HOURS).contains(&hours) => {
+			return Err(Error::Validation(format!(
+				"Expiry must be between 1 and {MAX_EXPIRES_IN_HOURS} hours"
+			)));
+		}
+		Some(hours) => Some(Utc::now().naive_utc() + Duration::hours(hours)),
+		None => None,
+	};
+
+	let share = mm
+		.run_blocking(move |conn| {
+			if Story::get(conn, story_id).optional()?.is_none() {
+				return Ok(None);
+			}
+			if !Story::is_participant(conn, story_id, ctx.user_id)? {
+				return Err(Error::Forbidden(
+					"Only participants can share a story".to_string(),
+				));
+			}
+
+			let token = generate_share_token();
+			StoryShare::create(
+				conn,
+				NewStoryShare {
+					story_id,
+					created_by: ctx.user_id,
+					token: &token,
+					expires_at,
+				},
+			)
+			.map(Some)
+			.map_err(Error::from)
+		})
+		.await?;
+
+	match share {
+		Some(share) => {
+			Ok((StatusCode::CREATED, Json(ShareDto::from(share))).into_response())
+		}
+		None => Ok(StatusCode::NOT_FOUND.into_response()),
+	}
+}
+
+/// Shares of the story created by the caller, revoked ones included.
+async fn list_shares(
+	ctx: Ctx,
+	Path(story_id): Path<Uuid>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+) -> Result<impl IntoResponse, Error> {
+	let shares = mm
+		.run_blocking_read(move |conn| {
+			StoryShare::list_by_story(conn, story_id, ctx.user_id)
+				.map_err(Error::from)
+		})
+		.await?;
+
+	Ok((
+		StatusCode::OK,
+		Json(shares.into_iter().map(ShareDto::from).collect::<Vec<_>>()),
+	))
+}
+
+async fn revoke_share(
+	ctx: Ctx,
+	Path((story_id, share_id)): Path<(Uuid, Uuid)>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+) -> Result<impl IntoResponse, Error> {
+	let revoked = mm
+		.run_blocking(move |conn| {
+			StoryShare::revoke(conn, story_id, share_id, ctx.user_id)
+				.map_err(Error::from)
+		})
+		.await?;
+
+	if revoked {
+		Ok(StatusCode::NO_CONTENT)
+	} else {
+		Ok(StatusCode::NOT_FOUND)
+	}
+}
+
+/// Public story behind a share link: JSON with `?format=json` or an
+/// `Accept: application/json` header, an HTML page with Open Graph tags otherwise.
+/// Every successful request counts as a view.
+pub async fn get_shared_story(
+	Path(token): Path<String>,
+	Query(query): Query<SharedStoryQuery>,
+	headers: HeaderMap,
+	Extension(mm): Extension<Arc<ModelManager>>,
+) -> Result<impl IntoResponse, Error> {
+	let wants_json = match query.format.as_deref() {
+		Some(format) => format.eq_ignore_ascii_case("json"),
+		None => headers
+			.get(header::ACCEPT)
+			.and_then(|accept| accept.to_str().ok())
+			.is_some_and(|accept| {
+				accept.contains("application/json") && !accept.contains("text/html")
+			}),
+	};
+
+	let story = mm
+		.run_blocking(move |conn| {
+			let Some(share) = StoryShare::record_view(conn, &token)? else {
+				return Ok(None);
+			};
+			let story = Story::get(conn, share.story_id)?;
+			story_dto(conn, story).map(|story| Some((share.token, story)))
+		})
+		.await?;
+
+	let Some((token, story)) = story else {
+		return Ok(StatusCode::NOT_FOUND.into_response());
+	};
+
+	// Links must stop working right after revocation, and not leak via referrers
+	let headers = [
+		(header::CACHE_CONTROL, "no-store"),
+		(header::REFERRER_POLICY, "no-referrer"),
+	];
+
+	if wants_json {
+		return Ok((StatusCode::OK, headers, Json(story)).into_response());
+	}
+
+	let document = story_document(story);
+	let head = open_graph_tags(
+		&document.title(),
+		&document.paragraphs().first().cloned().unwrap_or_default(),
+		&format!("{}/s/{token}", core_config().PUBLIC_BASE_URL),
+	);
+
+	Ok((
+		StatusCode::OK,
+		headers,
+		Html(render_with_head(&document, &head)),
+	)
+		.into_response())
+}
+
+fn open_graph_tags(title: &str, text: &str, url: &str) -> String {
+	let mut description: String = text.chars().take(DESCRIPTION_MAX_CHARS).collect();
+	if text.chars().count() > DESCRIPTION_MAX_CHARS {
+		description.push('…');
+	}
+
+	let tags = [
+		("og:type", "article"),
+		("og:site_name", "Threadly"),
+		("og:title", title),
+		("og:description", description.as_str()),
+		("og:url", url),
+	];
+
+	let mut head = String::new();
+	for (property, content) in tags {
+		head.push_str(&format!(
+			"<meta property=\"{property}\" content=\"{}\">\n",
+			escape(content)
+		));
+	}
+	head.push_str(&format!(
+		"<meta name=\"description\" content=\"{}\">\n\
+		 <meta name=\"twitter:card\" content=\"summary\">\n\
+		 <meta name=\"robots\" content=\"noindex\">\n",
+		escape(&description)
+	));
+
+	head
+}
+
+/// 128 random bits, hex encoded.
+fn generate_share_token() -> String {
+	let mut bytes = [0u8; 16];
+	rand::thread_rng().fill_bytes(&mut bytes);
+
+	hex::encode(bytes)
+}
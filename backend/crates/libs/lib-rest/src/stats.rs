@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use axum::{
+	Router,
+	extract::{Extension, Json, Path, Query},
+	http::StatusCode,
+	response::IntoResponse,
+	routing::get,
+};
+use chrono::{Duration, Utc};
+use diesel::OptionalExtension;
+use lib_core::model::ModelManager;
+use lib_profiles::{
+	model::Profile,
+	stats::model::{LeaderboardEntry, UserStats},
+};
+use uuid::Uuid;
+
+use crate::{
+	dto_models::requests::{LeaderboardPeriod, LeaderboardQuery},
+	error::Error,
+};
+
+const DEFAULT_LEADERBOARD_SIZE: i64 = 20;
+const MAX_LEADERBOARD_SIZE: i64 = 100;
+
+pub fn stats_routes() -> Router {
+	Router::new()
+		.route("/users/{user_id}/stats", get(get_user_stats))
+		.route("/leaderboards/{period}", get(get_leaderboard))
+}
+
+async fn get_user_stats(
+	Path(user_id): Path<Uuid>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+) -> Result<impl IntoResponse, Error> {
+	let stats = mm
+		.run_blocking_read(move |conn| {
+			if Profile::get_user_profile(conn, user_id)
+				.optional()?
+				.is_none()
+			{
+				return Ok(None);
+			}
+			UserStats::for_user(conn, user_id)
+				.map(Some)
+				.map_err(Error::from)
+		})
+		.await?;
+
+	match stats {
+		Some(stats) => Ok((StatusCode::OK, Json(stats)).into_response()),
+		None => Ok(StatusCode::NOT_FOUND.into_response()),
+	}
+}
+
+async fn get_leaderboard(
+	Path(period): Path<LeaderboardPeriod>,
+	Query(query): Query<LeaderboardQuery>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+) -> Result<impl IntoResponse, Error> {
+	let limit = query
+		.limit
+		.unwrap_or(DEFAULT_LEADERBOARD_SIZE)
+		.clamp(1, MAX_LEADERBOARD_SIZE);
+	let since = match period {
+		LeaderboardPeriod::Global => None,
+		LeaderboardPeriod::Weekly => {
+			Some((Utc::now() - Duration::days(7)).naive_utc())
+		}
+	};
+
+	let entries = mm
+		.run_blocking_read(move |conn| {
+			LeaderboardEntry::top(conn, since, limit).map_err(Error::from)
+		})
+		.await?;
+
+	Ok((StatusCode::OK, Json(entries)))
+}
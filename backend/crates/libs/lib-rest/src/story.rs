@@ -0,0 +1,269 @@
+use std::sync::Arc;
+
+use axum::{
+	Router,
+	extract::{Extension, Json, Path, Query},
+	http::{StatusCode, header},
+	response::IntoResponse,
+	routing::get,
+};
+use diesel::PgConnection;
+use lib_core::{
+	ctx::Ctx,
+	model::{ModelManager, base::BasicDbOps},
+};
+use lib_export::{document::StoryDocument, format::ExportFormat, render};
+use lib_messages::model::Message;
+use lib_players::model::Player;
+use lib_profiles::model::Profile;
+use lib_sessions::model::Session;
+use lib_stories::model::Story;
+use uuid::Uuid;
+
+use crate::{
+	dto_models::{
+		requests::{ExportQuery, PageQuery},
+		responses::{Page, StoryDto, StoryMessageDto, StoryParticipantDto},
+	},
+	error::Error,
+};
+
+const DEFAULT_PER_PAGE: i64 = 20;
+const MAX_PER_PAGE: i64 = 50;
+/// Shown as the author of narration.
+const NARRATOR_NAME: &str = "Narrator";
+
+pub fn story_routes() -> Router {
+	Router::new()
+		.route("/stories/{story_id}", get(get_story))
+		.route("/stories/{story_id}/export", get(export_story))
+		.route("/sessions/{session_id}/story", get(get_session_story))
+		.route("/sessions/{session_id}/stories", get(list_session_stories))
+		.route("/me/stories", get(list_my_stories))
+}
+
+async fn get_story(
+	ctx: Ctx,
+	Path(story_id): Path<Uuid>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+) -> Result<impl IntoResponse, Error> {
+	match mm
+		.run_blocking_read(move |conn| {
+			let Some(story) = Story::get_visible(conn, story_id, ctx.user_id)?
+			else {
+				return Ok(None);
+			};
+			story_dto(conn, story).map(Some)
+		})
+		.await?
+	{
+		Some(story) => Ok((StatusCode::OK, Json(story)).into_response()),
+		None => Ok(StatusCode::NOT_FOUND.into_response()),
+	}
+}
+
+async fn export_story(
+	ctx: Ctx,
+	Path(story_id): Path<Uuid>,
+	Query(query): Query<ExportQuery>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+) -> Result<impl IntoResponse, Error> {
+	let format = match query.format.as_deref() {
+		Some(format) => format
+			.parse::<ExportFormat>()
+			.map_err(|e| Error::Validation(e.to_string()))?,
+		None => ExportFormat::Markdown,
+	};
+
+	let Some((disposition, bytes)) = mm
+		.run_blocking_read(move |conn| {
+			let Some(story) = Story::get_visible(conn, story_id, ctx.user_id)?
+			else {
+				return Ok(None);
+			};
+			let document = story_document(story_dto(conn, story)?);
+
+			let bytes = render(&document, format)?;
+			Ok::<_, Error>(Some((
+				document.content_disposition(format.extension()),
+				bytes,
+			)))
+		})
+		.await?
+	else {
+		return Ok(StatusCode::NOT_FOUND.into_response());
+	};
+
+	Ok((
+		StatusCode::OK,
+		[
+			(header::CONTENT_TYPE, format.content_type().to_string()),
+			(header::CONTENT_DISPOSITION, disposition),
+		],
+		bytes,
+	)
+		.into_response())
+}
+
+async fn get_session_story(
+	ctx: Ctx,
+	Path(session_id): Path<Uuid>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+) -> Result<impl IntoResponse, Error> {
+	match mm
+		.run_blocking_read(move |conn| {
+			let Some(story) = Story::get_by_session(conn, session_id)? else {
+				return Ok(None);
+			};
+			if !story.is_visible_to(conn, ctx.user_id)? {
+				return Ok(None);
+			}
+			if story.thread_id.is_some() {
+				return Err(Error::Validation(format!(
+					"Session tells one story per thread, use /sessions/{session_id}/stories"
+				)));
+			}
+			story_dto(conn, story).map(Some)
+		})
+		.await?
+	{
+		Some(story) => Ok((StatusCode::OK, Json(story)).into_response()),
+		None => Ok(StatusCode::NOT_FOUND.into_response()),
+	}
+}
+
+/// Every story of the session, for rotating sessions one per thread.
+async fn list_session_stories(
+	ctx: Ctx,
+	Path(session_id): Path<Uuid>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+) -> Result<impl IntoResponse, Error> {
+	let stories = mm
+		.run_blocking_read(move |conn| {
+			let mut visible = Vec::new();
+			for story in Story::list_by_session(conn, session_id)? {
+				if story.is_visible_to(conn, ctx.user_id)? {
+					visible.push(story_dto(conn, story)?);
+				}
+			}
+			Ok::<_, Error>(visible)
+		})
+		.await?;
+
+	if stories.is_empty() {
+		return Ok(StatusCode::NOT_FOUND.into_response());
+	}
+
+	Ok((StatusCode::OK, Json(stories)).into_response())
+}
+
+async fn list_my_stories(
+	ctx: Ctx,
+	Query(query): Query<PageQuery>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+) -> Result<impl IntoResponse, Error> {
+	let page = query.page.unwrap_or(1).max(1);
+	let per_page = query
+		.per_page
+		.unwrap_or(DEFAULT_PER_PAGE)
+		.clamp(1, MAX_PER_PAGE);
+
+	let stories_page = mm
+		.run_blocking_read(move |conn| {
+			let total = Story::count_by_participant(conn, ctx.user_id)?;
+			let items = Story::list_by_participant(
+				conn,
+				ctx.user_id,
+				per_page,
+				(page - 1) * per_page,
+			)?
+			.into_iter()
+			.map(|story| story_dto(conn, story))
+			.collect::<Result<Vec<_>, Error>>()?;
+
+			Ok::<_, Error>(Page {
+				items,
+				page,
+				per_page,
+				total,
+			})
+		})
+		.await?;
+
+	Ok((StatusCode::OK, Json(stories_page)))
+}
+
+/// Input of the exporters and of the public share page.
+pub(crate) fn story_document(story: StoryDto) -> StoryDocument {
+	StoryDocument {
+		story_id: story.id,
+		theme: story.theme,
+		contributors: story
+			.participants
+			.into_iter()
+			.map(|participant| participant.display_name)
+			.collect(),
+		content: story.content,
+		created_at: story.created_at,
+	}
+}
+
+/// Story with its session theme, participants and messages in turn order.
+/// Participants are the authors of the messages plus players still seated,
+/// so players who left mid-game are not lost from the archive.
+pub(crate) fn story_dto(
+	conn: &mut PgConnection,
+	story: Story,
+) -> Result<StoryDto, Error> {
+	let session = Session::get(conn, story.session_id)?;
+	// Stories of rotating sessions tell a single thread
+	let messages = match story.thread_id {
+		Some(thread_id) => Message::list_by_thread(conn, thread_id)?,
+		None => Message::list_by_session(conn, story.session_id)?,
+	};
+	let players = Player::list_by_session(conn, story.session_id)?;
+
+	let mut participant_ids: Vec<Uuid> = Vec::new();
+	for user_id in messages
+		.iter()
+		.filter_map(|message| message.user_id)
+		.chain(players.iter().map(|player| player.user_id))
+	{
+		if !participant_ids.contains(&user_id) {
+			participant_ids.push(user_id);
+		}
+	}
+	let names = Profile::display_names(conn, &participant_ids)?;
+	let name_of = |user_id: &Uuid| names.get(user_id).cloned().unwrap_or_default();
+
+	Ok(StoryDto {
+		id: story.id,
+		session_id: story.session_id,
+		thread_id: story.thread_id,
+		theme: session.theme,
+		content: story.content,
+		created_at: story.created_at,
+		is_public: story.is_public,
+		participants: participant_ids
+			.iter()
+			.map(|user_id| StoryParticipantDto {
+				user_id: *user_id,
+				display_name: name_of(user_id),
+			})
+			.collect(),
+		messages: messages
+			.into_iter()
+			.map(|message| StoryMessageDto {
+				display_name: message
+					.user_id
+					.map_or_else(|| NARRATOR_NAME.to_string(), |id| name_of(&id)),
+				user_id: message.user_id,
+				content: message.content,
+				round: message.round,
+				turn_order: message.turn_order,
+				created_at: message.created_at,
+				used_suggestion: message.used_suggestion,
+			})
+			.collect(),
+	})
+}
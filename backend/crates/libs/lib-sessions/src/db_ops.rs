@@ -5,7 +5,9 @@ use diesel::pg::PgConnection;
 use diesel::prelude::*;
 use lib_core::dto::session::UserInSessionDto;
 use lib_core::model::base::BasicDbOps;
-use lib_core::model::schema::{players, sessions};
+use lib_core::model::schema::{bots, players, sessions};
+use lib_core::model::schema_enums::SessionStatus;
+use lib_profiles::model::Profile;
 use uuid::Uuid;
 
 use crate::model::{NewSession, UserInSession};
@@ -55,15 +57,103 @@ impl BasicDbOps for Session {
 }
 
 impl Session {
+	/// Updates the session only if its stored `version` still equals
+	/// `changes.version`, bumping the version by one.
+	/// Returns `NotFound` when the row was changed concurrently.
+	pub fn update_versioned(
+		conn: &mut PgConnection,
+		changes: &Self,
+	) -> QueryResult<Self> {
+		let next = Session {
+			version: changes.version + 1,
+			..changes.clone()
+		};
+
+		diesel::update(
+			sessions::table
+				.find(changes.id)
+				.filter(sessions::version.eq(changes.version)),
+		)
+		.set(&next)
+		.get_result(conn)
+	}
+
+	/// Bumps the session version for changes that live outside the
+	/// `sessions` row (players joining, getting ready, leaving...).
+	/// Returns `NotFound` when `current` is no longer the stored version.
+	pub fn bump_version(
+		conn: &mut PgConnection,
+		session_id: Uuid,
+		current: i64,
+	) -> QueryResult<i64> {
+		diesel::update(
+			sessions::table
+				.find(session_id)
+				.filter(sessions::version.eq(current)),
+		)
+		.set(sessions::version.eq(sessions::version + 1))
+		.returning(sessions::version)
+		.get_result(conn)
+	}
+
+	/// Reads the session and locks its row until the transaction ends,
+	/// so players acting at the same time are applied one after another.
+	pub fn get_for_update(conn: &mut PgConnection, id: Uuid) -> QueryResult<Self> {
+		sessions::table.find(id).for_update().first(conn)
+	}
+
+	pub fn list_by_status(
+		conn: &mut PgConnection,
+		status: SessionStatus,
+	) -> QueryResult<Vec<Self>> {
+		sessions::table
+			.filter(sessions::status.eq(status))
+			.load(conn)
+	}
+
+	/// Number of sessions per status.
+	pub fn count_by_status(
+		conn: &mut PgConnection,
+	) -> QueryResult<Vec<(SessionStatus, i64)>> {
+		sessions::table
+			.group_by(sessions::status)
+			.select((sessions::status, diesel::dsl::count_star()))
+			.load(conn)
+	}
+
 	pub fn list_users_in_session(
 		conn: &mut PgConnection,
 		session_id: Uuid,
 	) -> QueryResult<Vec<UserInSessionDto>> {
-		players::table
+		let players_info: Vec<(Uuid, bool, bool, i32, bool)> = players::table
+			.left_join(bots::table.on(bots::user_id.eq(players::user_id)))
 			.filter(players::session_id.eq(session_id))
-			.select((players::user_id, players::is_ready, players::is_host))
-			.load(conn)
-			.map(|players| players.into_iter().collect())
+			.order(players::seat.asc())
+			.select((
+				players::user_id,
+				players::is_ready,
+				players::is_host,
+				players::seat,
+				bots::user_id.nullable().is_not_null(),
+			))
+			.load(conn)?;
+
+		let user_ids: Vec<Uuid> = players_info.iter().map(|p| p.0).collect();
+		let mut names = Profile::display_names(conn, &user_ids)?;
+
+		Ok(players_info
+			.into_iter()
+			.map(
+				|(user_id, is_ready, is_host, seat, is_bot)| UserInSessionDto {
+					user_id,
+					display_name: names.remove(&user_id).unwrap_or_default(),
+					is_ready,
+					is_host,
+					seat,
+					is_bot,
+				},
+			)
+			.collect())
 	}
 
 	pub fn list_with_users(
@@ -71,25 +161,36 @@ impl Session {
 	) -> QueryResult<Vec<SessionWithUsersInSession>> {
 		let sessions_list: Vec<Session> = sessions::table.get_results(conn)?;
 
-		let players_info: Vec<(Uuid, Uuid, bool, bool)> = players::table
+		let players_info: Vec<(Uuid, Uuid, bool, bool, i32, bool)> = players::table
+			.left_join(bots::table.on(bots::user_id.eq(players::user_id)))
+			.order(players::seat.asc())
 			.select((
 				players::session_id,
 				players::user_id,
 				players::is_ready,
 				players::is_host,
+				players::seat,
+				bots::user_id.nullable().is_not_null(),
 			))
 			.get_results(conn)?;
 
+		let user_ids: Vec<Uuid> = players_info.iter().map(|p| p.1).collect();
+		let names = Profile::display_names(conn, &user_ids)?;
+
 		let mut users_map: HashMap<Uuid, Vec<UserInSession>> = HashMap::new();
 
-		for (session_id, user_id, is_ready, is_host) in players_info {
-			users_map.entry(session_id).or_insert_with(Vec::new).push(
-				UserInSession {
+		for (session_id, user_id, is_ready, is_host, seat, is_bot) in players_info {
+			users_map
+				.entry(session_id)
+				.or_default()
+				.push(UserInSession {
 					user_id,
+					display_name: names.get(&user_id).cloned().unwrap_or_default(),
 					is_ready,
 					is_host,
-				},
-			);
+					seat,
+					is_bot,
+				});
 		}
 
 		let result = sessions_list
@@ -105,6 +206,17 @@ impl Session {
 					max_rounds: session.max_rounds,
 					current_round: session.current_round,
 					created_at: session.created_at,
+					version: session.version,
+					story_variants: session.story_variants,
+					visibility: session.visibility,
+					visibility_words: session.visibility_words,
+					play_mode: session.play_mode,
+					turn_order: session.turn_order,
+					bot_backfill: session.bot_backfill,
+					narrator: session.narrator,
+					objectives: session.objectives,
+					undo_window_secs: session.undo_window_secs,
+					pending_message_id: session.pending_message_id,
 					users,
 				}
 			})
@@ -117,13 +229,24 @@ impl Session {
 		conn: &mut PgConnection,
 		session_id: Uuid,
 	) -> QueryResult<Option<SessionWithUsersInSession>> {
-		let players_info: Vec<(Uuid, bool, bool)> = players::table
+		let players_info: Vec<(Uuid, bool, bool, i32, bool)> = players::table
+			.left_join(bots::table.on(bots::user_id.eq(players::user_id)))
 			.filter(players::session_id.eq(session_id))
-			.select((players::user_id, players::is_ready, players::is_host))
+			.order(players::seat.asc())
+			.select((
+				players::user_id,
+				players::is_ready,
+				players::is_host,
+				players::seat,
+				bots::user_id.nullable().is_not_null(),
+			))
 			.get_results(conn)?;
 
 		let session: Session = sessions::table.find(session_id).get_result(conn)?;
 
+		let user_ids: Vec<Uuid> = players_info.iter().map(|p| p.0).collect();
+		let mut names = Profile::display_names(conn, &user_ids)?;
+
 		Ok(Some(SessionWithUsersInSession {
 			id: session.id,
 			theme: session.theme,
@@ -132,12 +255,26 @@ impl Session {
 			max_rounds: session.max_rounds,
 			current_round: session.current_round,
 			created_at: session.created_at,
+			version: session.version,
+			story_variants: session.story_variants,
+			visibility: session.visibility,
+			visibility_words: session.visibility_words,
+			play_mode: session.play_mode,
+			turn_order: session.turn_order,
+			bot_backfill: session.bot_backfill,
+			narrator: session.narrator,
+			objectives: session.objectives,
+			undo_window_secs: session.undo_window_secs,
+			pending_message_id: session.pending_message_id,
 			users: players_info
 				.into_iter()
-				.map(|(user_id, is_ready, is_host)| UserInSession {
+				.map(|(user_id, is_ready, is_host, seat, is_bot)| UserInSession {
 					user_id,
+					display_name: names.remove(&user_id).unwrap_or_default(),
 					is_ready,
 					is_host,
+					seat,
+					is_bot,
 				})
 				.collect(),
 		}))
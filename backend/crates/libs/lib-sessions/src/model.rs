@@ -1,6 +1,11 @@
 use chrono::NaiveDateTime;
 use diesel::prelude::{AsChangeset, Insertable, Queryable};
-use lib_core::model::{schema::sessions, schema_enums::SessionStatus};
+use lib_core::model::{
+	schema::sessions,
+	schema_enums::{
+		NarratorMode, PlayMode, SessionStatus, TurnOrderMode, VisibilityMode,
+	},
+};
 use serde::Serialize;
 use uuid::Uuid;
 
@@ -15,6 +20,30 @@ pub struct Session {
 	pub max_rounds: i32,
 	pub current_round: i32,
 	pub created_at: NaiveDateTime,
+	pub version: i64,
+	/// Stories generated at the end, players vote when more than one.
+	pub story_variants: i32,
+	pub visibility: VisibilityMode,
+	/// Words shown in `LastWords` mode.
+	pub visibility_words: i32,
+	pub play_mode: PlayMode,
+	pub turn_order: TurnOrderMode,
+	/// A bot takes over the seat of a player leaving mid-game.
+	pub bot_backfill: bool,
+	pub narrator: NarratorMode,
+	/// Competitive mode, players get secret objectives.
+	pub objectives: bool,
+	/// Seconds a line can be taken back before the turn moves on.
+	pub undo_window_secs: i32,
+	/// The line in its undo window, the turn moves on once it's released.
+	pub pending_message_id: Option<Uuid>,
+	/// When the open vote closes, whoever has voted by then.
+	pub voting_ends_at: Option<NaiveDateTime>,
+	/// Set while the narrator writes between rounds, the game goes on
+	/// without the interlude once it passed.
+	pub narration_ends_at: Option<NaiveDateTime>,
+	/// When the undo window of the pending line closes.
+	pub pending_until: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Insertable)]
@@ -22,13 +51,28 @@ pub struct Session {
 pub struct NewSession<'a> {
 	pub theme: &'a str,
 	pub max_rounds: i32,
+	pub story_variants: i32,
+	pub visibility: VisibilityMode,
+	pub visibility_words: i32,
+	pub play_mode: PlayMode,
+	pub turn_order: TurnOrderMode,
+	/// A bot takes over the seat of a player leaving mid-game.
+	pub bot_backfill: bool,
+	pub narrator: NarratorMode,
+	/// Competitive mode, players get secret objectives.
+	pub objectives: bool,
+	/// Seconds a line can be taken back before the turn moves on.
+	pub undo_window_secs: i32,
 }
 
 #[derive(Serialize)]
 pub struct UserInSession {
 	pub user_id: Uuid,
+	pub display_name: String,
 	pub is_ready: bool,
 	pub is_host: bool,
+	pub seat: i32,
+	pub is_bot: bool,
 }
 
 #[derive(Serialize)]
@@ -40,5 +84,16 @@ pub struct SessionWithUsersInSession {
 	pub max_rounds: i32,
 	pub current_round: i32,
 	pub created_at: chrono::NaiveDateTime,
+	pub version: i64,
+	pub story_variants: i32,
+	pub visibility: VisibilityMode,
+	pub visibility_words: i32,
+	pub play_mode: PlayMode,
+	pub turn_order: TurnOrderMode,
+	pub bot_backfill: bool,
+	pub narrator: NarratorMode,
+	pub objectives: bool,
+	pub undo_window_secs: i32,
+	pub pending_message_id: Option<Uuid>,
 	pub users: Vec<UserInSession>,
 }
@@ -2,7 +2,7 @@ use diesel::associations::HasTable;
 use diesel::pg::PgConnection;
 use diesel::prelude::*;
 use lib_core::model::base::BasicDbOps;
-use lib_core::model::schema::stories;
+use lib_core::model::schema::{messages, players, stories, threads};
 use uuid::Uuid;
 
 use crate::model::NewStory;
@@ -47,3 +47,118 @@ impl BasicDbOps for Story {
 		diesel::delete(stories::table.find(id)).execute(conn)
 	}
 }
+
+impl Story {
+	/// Latest story generated for the session.
+	pub fn get_by_session(
+		conn: &mut PgConnection,
+		session_id: Uuid,
+	) -> QueryResult<Option<Self>> {
+		stories::table
+			.filter(stories::session_id.eq(session_id))
+			.order_by(stories::created_at.desc())
+			.first(conn)
+			.optional()
+	}
+
+	/// All stories of the session, one per thread for rotating sessions,
+	/// in thread order.
+	pub fn list_by_session(
+		conn: &mut PgConnection,
+		session_id: Uuid,
+	) -> QueryResult<Vec<Self>> {
+		stories::table
+			.left_join(threads::table)
+			.filter(stories::session_id.eq(session_id))
+			.order_by((threads::thread_index.asc(), stories::created_at.asc()))
+			.select(stories::all_columns)
+			.load(conn)
+	}
+
+	/// Stories of sessions the user joined or wrote in, newest first.
+	pub fn list_by_participant(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+		limit: i64,
+		offset: i64,
+	) -> QueryResult<Vec<Self>> {
+		Self::participant_filter(user_id)
+			.order_by(stories::created_at.desc())
+			.limit(limit)
+			.offset(offset)
+			.load(conn)
+	}
+
+	pub fn set_public(
+		conn: &mut PgConnection,
+		id: Uuid,
+		is_public: bool,
+	) -> QueryResult<Self> {
+		diesel::update(stories::table.find(id))
+			.set(stories::is_public.eq(is_public))
+			.get_result(conn)
+	}
+
+	pub fn count_public(conn: &mut PgConnection) -> QueryResult<i64> {
+		stories::table
+			.filter(stories::is_public.eq(true))
+			.count()
+			.get_result(conn)
+	}
+
+	/// Whether the user joined or wrote in the story's session.
+	pub fn is_participant(
+		conn: &mut PgConnection,
+		story_id: Uuid,
+		user_id: Uuid,
+	) -> QueryResult<bool> {
+		diesel::select(diesel::dsl::exists(
+			Self::participant_filter(user_id).filter(stories::id.eq(story_id)),
+		))
+		.get_result(conn)
+	}
+
+	/// The story if the user may read it: a public one, or one they took part in.
+	pub fn get_visible(
+		conn: &mut PgConnection,
+		id: Uuid,
+		user_id: Uuid,
+	) -> QueryResult<Option<Self>> {
+		match stories::table.find(id).first::<Self>(conn).optional()? {
+			Some(story) if story.is_visible_to(conn, user_id)? => Ok(Some(story)),
+			_ => Ok(None),
+		}
+	}
+
+	/// Stories stay private to their players until published to the feed.
+	pub fn is_visible_to(
+		&self,
+		conn: &mut PgConnection,
+		user_id: Uuid,
+	) -> QueryResult<bool> {
+		Ok(self.is_public || Self::is_participant(conn, self.id, user_id)?)
+	}
+
+	pub fn count_by_participant(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+	) -> QueryResult<i64> {
+		Self::participant_filter(user_id).count().get_result(conn)
+	}
+
+	#[diesel::dsl::auto_type(no_type_alias)]
+	fn participant_filter(user_id: Uuid) -> _ {
+		let played = players::table
+			.filter(players::user_id.eq(user_id))
+			.select(players::session_id);
+		let wrote = messages::table
+			.filter(messages::user_id.eq(user_id))
+			.select(messages::session_id);
+
+		stories::table.filter(
+			stories::session_id
+				.eq_any(played)
+				.or(stories::session_id.eq_any(wrote)),
+		)
+	}
+}
@@ -1,2 +1,5 @@
 pub mod db_ops;
 pub mod model;
+pub mod reactions;
+pub mod shares;
+pub mod variants;
@@ -12,6 +12,10 @@ pub struct Story {
 	pub session_id: Uuid,
 	pub content: String,
 	pub created_at: NaiveDateTime,
+	/// Listed in the community feed.
+	pub is_public: bool,
+	/// The thread told, for stories of rotating sessions.
+	pub thread_id: Option<Uuid>,
 }
 
 #[derive(Debug, Insertable)]
@@ -19,4 +23,5 @@ pub struct Story {
 pub struct NewStory<'a> {
 	pub session_id: Uuid,
 	pub content: &'a str,
+	pub thread_id: Option<Uuid>,
 }
@@ -0,0 +1,187 @@
+use chrono::Utc;
+use diesel::dsl::{count_star, sql};
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Double, Nullable};
+use lib_core::model::schema::{story_ratings, story_reactions};
+use lib_core::model::schema_enums::ReactionKind;
+use uuid::Uuid;
+
+use crate::model::Story;
+use crate::reactions::model::{
+	FeedStory, RatingSummary, StoryRating, StoryReaction,
+};
+
+impl StoryReaction {
+	/// Adds the reaction, returns false if the user already reacted so.
+	pub fn add(
+		conn: &mut PgConnection,
+		story_id: Uuid,
+		user_id: Uuid,
+		kind: ReactionKind,
+	) -> QueryResult<bool> {
+		let inserted = diesel::insert_into(story_reactions::table)
+			.values(StoryReaction {
+				story_id,
+				user_id,
+				kind,
+				created_at: Utc::now().naive_utc(),
+			})
+			.on_conflict_do_nothing()
+			.execute(conn)?;
+
+		Ok(inserted == 1)
+	}
+
+	pub fn remove(
+		conn: &mut PgConnection,
+		story_id: Uuid,
+		user_id: Uuid,
+		kind: ReactionKind,
+	) -> QueryResult<bool> {
+		let deleted =
+			diesel::delete(story_reactions::table.find((story_id, user_id, kind)))
+				.execute(conn)?;
+
+		Ok(deleted == 1)
+	}
+
+	/// Reaction totals per story and kind.
+	pub fn counts(
+		conn: &mut PgConnection,
+		story_ids: &[Uuid],
+	) -> QueryResult<Vec<(Uuid, ReactionKind, i64)>> {
+		story_reactions::table
+			.filter(story_reactions::story_id.eq_any(story_ids))
+			.group_by((story_reactions::story_id, story_reactions::kind))
+			.select((
+				story_reactions::story_id,
+				story_reactions::kind,
+				count_star(),
+			))
+			.order_by((story_reactions::story_id, story_reactions::kind))
+			.load(conn)
+	}
+
+	pub fn kinds_by_user(
+		conn: &mut PgConnection,
+		story_id: Uuid,
+		user_id: Uuid,
+	) -> QueryResult<Vec<ReactionKind>> {
+		story_reactions::table
+			.filter(story_reactions::story_id.eq(story_id))
+			.filter(story_reactions::user_id.eq(user_id))
+			.select(story_reactions::kind)
+			.order_by(story_reactions::kind)
+			.load(conn)
+	}
+}
+
+impl StoryRating {
+	/// Rates the story, replacing the user's previous rating.
+	pub fn upsert(
+		conn: &mut PgConnection,
+		story_id: Uuid,
+		user_id: Uuid,
+		stars: i16,
+	) -> QueryResult<Self> {
+		let now = Utc::now().naive_utc();
+
+		diesel::insert_into(story_ratings::table)
+			.values((
+				story_ratings::story_id.eq(story_id),
+				story_ratings::user_id.eq(user_id),
+				story_ratings::stars.eq(stars),
+			))
+			.on_conflict((story_ratings::story_id, story_ratings::user_id))
+			.do_update()
+			.set((
+				story_ratings::stars.eq(stars),
+				story_ratings::updated_at.eq(now),
+			))
+			.get_result(conn)
+	}
+
+	pub fn remove(
+		conn: &mut PgConnection,
+		story_id: Uuid,
+		user_id: Uuid,
+	) -> QueryResult<bool> {
+		let deleted = diesel::delete(story_ratings::table.find((story_id, user_id)))
+			.execute(conn)?;
+
+		Ok(deleted == 1)
+	}
+
+	pub fn get_by_user(
+		conn: &mut PgConnection,
+		story_id: Uuid,
+		user_id: Uuid,
+	) -> QueryResult<Option<Self>> {
+		story_ratings::table
+			.find((story_id, user_id))
+			.first(conn)
+			.optional()
+	}
+
+	pub fn summary(
+		conn: &mut PgConnection,
+		story_id: Uuid,
+	) -> QueryResult<RatingSummary> {
+		let (count, average) = story_ratings::table
+			.filter(story_ratings::story_id.eq(story_id))
+			.select((
+				sql::<BigInt>("count(*)"),
+				sql::<Nullable<Double>>("avg(stars)::float8"),
+			))
+			.get_result::<(i64, Option<f64>)>(conn)?;
+
+		Ok(RatingSummary { count, average })
+	}
+}
+
+impl Story {
+	/// Public stories, best first. The score grows with reactions and
+	/// ratings above 2.5 stars, and decays with age (hours + 2) ^ 1.5,
+	/// so fresh stories get a chance before older favourites.
+	pub fn feed(
+		conn: &mut PgConnection,
+		limit: i64,
+		offset: i64,
+	) -> QueryResult<Vec<FeedStory>> {
+		diesel::sql_query(
+			"SELECT s.id, s.session_id, se.theme, s.content, s.created_at,
+				COALESCE(r.reaction_count, 0) AS reaction_count,
+				COALESCE(rt.rating_count, 0) AS rating_count,
+				rt.rating_avg
+			FROM stories s
+			JOIN sessions se ON se.id = s.session_id
+			LEFT JOIN (
+				SELECT story_id, count(*) AS reaction_count
+				FROM story_reactions GROUP BY story_id
+			) r ON r.story_id = s.id
+			LEFT JOIN (
+				SELECT story_id, count(*) AS rating_count,
+					avg(stars)::float8 AS rating_avg
+				FROM story_ratings GROUP BY story_id
+			) rt ON rt.story_id = s.id
+			WHERE s.is_public
+			ORDER BY
+				GREATEST(
+					1 + COALESCE(r.reaction_count, 0)
+						+ COALESCE(rt.rating_count, 0)
+						* (COALESCE(rt.rating_avg, 2.5) - 2.5),
+					0.1
+				) / power(
+					extract(epoch FROM (now() AT TIME ZONE 'UTC') - s.created_at)
+						/ 3600 + 2,
+					1.5
+				) DESC,
+				s.created_at DESC
+			LIMIT $1 OFFSET $2",
+		)
+		.bind::<BigInt, _>(limit)
+		.bind::<BigInt, _>(offset)
+		.load(conn)
+	}
+}
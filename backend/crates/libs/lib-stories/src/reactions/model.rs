@@ -0,0 +1,54 @@
+use chrono::NaiveDateTime;
+use diesel::{
+	QueryableByName,
+	prelude::{Insertable, Queryable},
+	sql_types::{BigInt, Double, Nullable, Text, Timestamp, Uuid as SqlUuid},
+};
+use lib_core::model::{schema::story_reactions, schema_enums::ReactionKind};
+use uuid::Uuid;
+
+#[derive(Debug, Queryable, Insertable)]
+#[diesel(table_name = story_reactions)]
+pub struct StoryReaction {
+	pub story_id: Uuid,
+	pub user_id: Uuid,
+	pub kind: ReactionKind,
+	pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Queryable)]
+pub struct StoryRating {
+	pub story_id: Uuid,
+	pub user_id: Uuid,
+	pub stars: i16,
+	pub created_at: NaiveDateTime,
+	pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RatingSummary {
+	pub count: i64,
+	/// `None` while the story has no ratings.
+	pub average: Option<f64>,
+}
+
+/// Public story with its theme and vote totals, as ranked in the feed.
+#[derive(Debug, QueryableByName)]
+pub struct FeedStory {
+	#[diesel(sql_type = SqlUuid)]
+	pub id: Uuid,
+	#[diesel(sql_type = SqlUuid)]
+	pub session_id: Uuid,
+	#[diesel(sql_type = Text)]
+	pub theme: String,
+	#[diesel(sql_type = Text)]
+	pub content: String,
+	#[diesel(sql_type = Timestamp)]
+	pub created_at: NaiveDateTime,
+	#[diesel(sql_type = BigInt)]
+	pub reaction_count: i64,
+	#[diesel(sql_type = BigInt)]
+	pub rating_count: i64,
+	#[diesel(sql_type = Nullable<Double>)]
+	pub rating_avg: Option<f64>,
+}
@@ -0,0 +1,74 @@
+use chrono::Utc;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use lib_core::model::schema::story_shares;
+use uuid::Uuid;
+
+use crate::shares::model::{NewStoryShare, StoryShare};
+
+impl StoryShare {
+	pub fn create(
+		conn: &mut PgConnection,
+		item: NewStoryShare,
+	) -> QueryResult<Self> {
+		diesel::insert_into(story_shares::table)
+			.values(item)
+			.get_result(conn)
+	}
+
+	pub fn list_by_story(
+		conn: &mut PgConnection,
+		story_id: Uuid,
+		created_by: Uuid,
+	) -> QueryResult<Vec<Self>> {
+		story_shares::table
+			.filter(story_shares::story_id.eq(story_id))
+			.filter(story_shares::created_by.eq(created_by))
+			.order_by(story_shares::created_at.desc())
+			.load(conn)
+	}
+
+	/// Counts a view of an active (not revoked, not expired) share.
+	/// `None` when the token is unknown or no longer active.
+	pub fn record_view(
+		conn: &mut PgConnection,
+		token: &str,
+	) -> QueryResult<Option<Self>> {
+		let now = Utc::now().naive_utc();
+
+		diesel::update(
+			story_shares::table
+				.filter(story_shares::token.eq(token))
+				.filter(story_shares::revoked_at.is_null())
+				.filter(
+					story_shares::expires_at
+						.is_null()
+						.or(story_shares::expires_at.gt(now)),
+				),
+		)
+		.set(story_shares::view_count.eq(story_shares::view_count + 1))
+		.get_result(conn)
+		.optional()
+	}
+
+	/// Revokes a share the user made of the story,
+	/// returns false if there was none to revoke.
+	pub fn revoke(
+		conn: &mut PgConnection,
+		story_id: Uuid,
+		id: Uuid,
+		created_by: Uuid,
+	) -> QueryResult<bool> {
+		let updated = diesel::update(
+			story_shares::table
+				.find(id)
+				.filter(story_shares::story_id.eq(story_id))
+				.filter(story_shares::created_by.eq(created_by))
+				.filter(story_shares::revoked_at.is_null()),
+		)
+		.set(story_shares::revoked_at.eq(Utc::now().naive_utc()))
+		.execute(conn)?;
+
+		Ok(updated == 1)
+	}
+}
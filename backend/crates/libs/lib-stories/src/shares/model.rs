@@ -0,0 +1,28 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::{Insertable, Queryable};
+use lib_core::model::schema::story_shares;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Public link to a story, looked up by its unguessable `token`.
+#[derive(Debug, Queryable, Clone, Serialize)]
+#[diesel(table_name = story_shares)]
+pub struct StoryShare {
+	pub id: Uuid,
+	pub story_id: Uuid,
+	pub created_by: Uuid,
+	pub token: String,
+	pub expires_at: Option<NaiveDateTime>,
+	pub revoked_at: Option<NaiveDateTime>,
+	pub view_count: i64,
+	pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = story_shares)]
+pub struct NewStoryShare<'a> {
+	pub story_id: Uuid,
+	pub created_by: Uuid,
+	pub token: &'a str,
+	pub expires_at: Option<NaiveDateTime>,
+}
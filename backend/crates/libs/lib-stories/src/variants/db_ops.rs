@@ -0,0 +1,91 @@
+use chrono::Utc;
+use diesel::dsl::count_star;
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use lib_core::model::schema::{players, story_variant_votes, story_variants};
+use uuid::Uuid;
+
+use crate::variants::model::{NewStoryVariant, StoryVariant, StoryVariantVote};
+
+impl StoryVariant {
+	pub fn create(
+		conn: &mut PgConnection,
+		item: NewStoryVariant,
+	) -> QueryResult<Self> {
+		diesel::insert_into(story_variants::table)
+			.values(item)
+			.get_result(conn)
+	}
+
+	pub fn get(conn: &mut PgConnection, id: Uuid) -> QueryResult<Self> {
+		story_variants::table.find(id).first(conn)
+	}
+
+	pub fn list_by_session(
+		conn: &mut PgConnection,
+		session_id: Uuid,
+	) -> QueryResult<Vec<Self>> {
+		story_variants::table
+			.filter(story_variants::session_id.eq(session_id))
+			.order_by(story_variants::variant_index.asc())
+			.load(conn)
+	}
+}
+
+impl StoryVariantVote {
+	/// Votes for the variant, replacing the user's previous vote.
+	pub fn cast(
+		conn: &mut PgConnection,
+		session_id: Uuid,
+		user_id: Uuid,
+		variant_id: Uuid,
+	) -> QueryResult<Self> {
+		diesel::insert_into(story_variant_votes::table)
+			.values((
+				story_variant_votes::session_id.eq(session_id),
+				story_variant_votes::user_id.eq(user_id),
+				story_variant_votes::variant_id.eq(variant_id),
+			))
+			.on_conflict((
+				story_variant_votes::session_id,
+				story_variant_votes::user_id,
+			))
+			.do_update()
+			.set((
+				story_variant_votes::variant_id.eq(variant_id),
+				story_variant_votes::created_at.eq(Utc::now().naive_utc()),
+			))
+			.get_result(conn)
+	}
+
+	/// Votes per variant. Only players still in the session count.
+	pub fn tally(
+		conn: &mut PgConnection,
+		session_id: Uuid,
+	) -> QueryResult<Vec<(Uuid, i64)>> {
+		Self::current_players_votes(session_id)
+			.group_by(story_variant_votes::variant_id)
+			.select((story_variant_votes::variant_id, count_star()))
+			.load(conn)
+	}
+
+	pub fn count_by_session(
+		conn: &mut PgConnection,
+		session_id: Uuid,
+	) -> QueryResult<i64> {
+		Self::current_players_votes(session_id)
+			.count()
+			.get_result(conn)
+	}
+
+	#[diesel::dsl::auto_type(no_type_alias)]
+	fn current_players_votes(session_id: Uuid) -> _ {
+		let voters = players::table
+			.filter(players::session_id.eq(session_id))
+			.select(players::user_id);
+
+		story_variant_votes::table
+			.filter(story_variant_votes::session_id.eq(session_id))
+			.filter(story_variant_votes::user_id.eq_any(voters))
+	}
+}
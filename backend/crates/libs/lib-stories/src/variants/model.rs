@@ -0,0 +1,47 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::{Insertable, Queryable};
+use lib_core::{dto::story::StoryVariantDto, model::schema::story_variants};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// One of several stories generated for a session, before the vote.
+#[derive(Debug, Queryable, Clone, Serialize)]
+#[diesel(table_name = story_variants)]
+pub struct StoryVariant {
+	pub id: Uuid,
+	pub session_id: Uuid,
+	pub variant_index: i32,
+	pub style: String,
+	pub temperature: f32,
+	pub content: String,
+	pub created_at: NaiveDateTime,
+}
+
+impl From<StoryVariant> for StoryVariantDto {
+	fn from(variant: StoryVariant) -> Self {
+		Self {
+			variant_id: variant.id,
+			variant_index: variant.variant_index,
+			style: variant.style,
+			content: variant.content,
+		}
+	}
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = story_variants)]
+pub struct NewStoryVariant<'a> {
+	pub session_id: Uuid,
+	pub variant_index: i32,
+	pub style: &'a str,
+	pub temperature: f32,
+	pub content: &'a str,
+}
+
+#[derive(Debug, Queryable)]
+pub struct StoryVariantVote {
+	pub session_id: Uuid,
+	pub user_id: Uuid,
+	pub variant_id: Uuid,
+	pub created_at: NaiveDateTime,
+}
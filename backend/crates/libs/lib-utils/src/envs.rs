@@ -12,6 +12,26 @@ pub fn get_env_parse<T: FromStr>(name: &'static str) -> Result<T> {
 	val.parse::<T>().map_err(|_| Error::WrongFormat(name))
 }
 
+/// Returns `None` when the variable is missing or empty.
+pub fn get_env_opt(name: &'static str) -> Option<String> {
+	get_env(name).ok().filter(|val| !val.is_empty())
+}
+
+/// Parses the variable if it is set, falls back to `default` otherwise.
+pub fn get_env_parse_or<T: FromStr>(name: &'static str, default: T) -> Result<T> {
+	match get_env_opt(name) {
+		Some(val) => val.parse::<T>().map_err(|_| Error::WrongFormat(name)),
+		None => Ok(default),
+	}
+}
+
+/// Parses the variable if it is set, `None` otherwise.
+pub fn get_env_parse_opt<T: FromStr>(name: &'static str) -> Result<Option<T>> {
+	get_env_opt(name)
+		.map(|val| val.parse::<T>().map_err(|_| Error::WrongFormat(name)))
+		.transpose()
+}
+
 // region:    --- Error
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -1,17 +1,24 @@
 use axum::extract::ws::{Message, WebSocket};
 use futures_util::{SinkExt, StreamExt, stream::SplitSink};
-use lib_game_events::{event::game::GameEvent, manager::GameEventsManager};
+use lib_game_events::{
+	event::{VersionedEvent, game::GameEvent},
+	manager::GameEventsManager,
+};
 use std::sync::Arc;
 use uuid::Uuid;
 
 use tokio::select;
 
+use crate::socket_gauge::ActiveSocketGuard;
+
 pub async fn handle_game_events_socket(
 	socket: WebSocket,
 	session_id: Uuid,
 	game_events_manager: Arc<GameEventsManager>,
 	user_id: Uuid,
 ) {
+	let _active_socket = ActiveSocketGuard::new("game");
+
 	let mut receiver = game_events_manager
 		.subscribe_user_to_observe_game_events(session_id, user_id);
 
@@ -51,7 +58,7 @@ pub async fn handle_game_events_socket(
 
 async fn send_msg(
 	socket: &mut SplitSink<WebSocket, axum::extract::ws::Message>,
-	msg: GameEvent,
+	msg: VersionedEvent<GameEvent>,
 ) -> Result<(), axum::Error> {
 	if let Ok(json) = serde_json::to_string(&msg) {
 		socket.send(Message::Text(json.into())).await?;
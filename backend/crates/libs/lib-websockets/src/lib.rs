@@ -1,3 +1,5 @@
 pub mod game_events;
 pub mod router;
 pub mod sessions_events;
+pub mod socket_gauge;
+pub mod stories_events;
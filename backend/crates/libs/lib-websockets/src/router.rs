@@ -3,6 +3,7 @@ use std::sync::Arc;
 use crate::{
 	game_events::handle_game_events_socket,
 	sessions_events::handle_sessions_events_socket,
+	stories_events::handle_stories_events_socket,
 };
 use axum::{
 	Extension, Router,
@@ -40,6 +41,16 @@ pub async fn ws_observe_sessions_events_handler(
 	})
 }
 
+pub async fn ws_observe_stories_events_handler(
+	_ctx: Ctx,
+	Extension(game_events_manager): Extension<Arc<GameEventsManager>>,
+	ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+	ws.on_upgrade(move |socket| {
+		handle_stories_events_socket(socket, game_events_manager)
+	})
+}
+
 pub fn websocket_router(game_events_manager: Arc<GameEventsManager>) -> Router {
 	Router::new()
 		.route(
@@ -47,5 +58,6 @@ pub fn websocket_router(game_events_manager: Arc<GameEventsManager>) -> Router {
 			get(ws_observe_game_events_handler),
 		)
 		.route("/observe/sessions", get(ws_observe_sessions_events_handler))
+		.route("/observe/stories", get(ws_observe_stories_events_handler))
 		.layer(Extension(game_events_manager))
 }
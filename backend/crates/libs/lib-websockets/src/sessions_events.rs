@@ -1,16 +1,23 @@
 use axum::extract::ws::{Message, WebSocket};
 use futures_util::{SinkExt, StreamExt, stream::SplitSink};
-use lib_game_events::{event::session::SessionEvent, manager::GameEventsManager};
+use lib_game_events::{
+	event::{VersionedEvent, session::SessionEvent},
+	manager::GameEventsManager,
+};
 use std::sync::Arc;
 use uuid::Uuid;
 
 use tokio::select;
 
+use crate::socket_gauge::ActiveSocketGuard;
+
 pub async fn handle_sessions_events_socket(
 	socket: WebSocket,
 	user_id: Uuid,
 	game_events_manager: Arc<GameEventsManager>,
 ) {
+	let _active_socket = ActiveSocketGuard::new("sessions");
+
 	let mut receiver =
 		game_events_manager.subscribe_user_to_observe_sessions_list(user_id);
 
@@ -50,7 +57,7 @@ pub async fn handle_sessions_events_socket(
 
 async fn send_msg(
 	socket: &mut SplitSink<WebSocket, axum::extract::ws::Message>,
-	msg: SessionEvent,
+	msg: VersionedEvent<SessionEvent>,
 ) -> Result<(), axum::Error> {
 	if let Ok(json) = serde_json::to_string(&msg) {
 		socket.send(Message::Text(json.into())).await?;
@@ -0,0 +1,18 @@
+/// Keeps the `ws_active_connections` gauge in sync with open sockets:
+/// incremented on creation, decremented when dropped.
+pub struct ActiveSocketGuard {
+	kind: &'static str,
+}
+
+impl ActiveSocketGuard {
+	pub fn new(kind: &'static str) -> Self {
+		metrics::gauge!("ws_active_connections", "kind" => kind).increment(1.0);
+		Self { kind }
+	}
+}
+
+impl Drop for ActiveSocketGuard {
+	fn drop(&mut self) {
+		metrics::gauge!("ws_active_connections", "kind" => self.kind).decrement(1.0);
+	}
+}
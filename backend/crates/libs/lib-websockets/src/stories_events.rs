@@ -0,0 +1,67 @@
+use axum::extract::ws::{Message, WebSocket};
+use futures_util::{SinkExt, StreamExt, stream::SplitSink};
+use lib_game_events::{event::story::StoryEvent, manager::GameEventsManager};
+use std::sync::Arc;
+
+use tokio::{select, sync::broadcast::error::RecvError};
+
+use crate::socket_gauge::ActiveSocketGuard;
+
+pub async fn handle_stories_events_socket(
+	socket: WebSocket,
+	game_events_manager: Arc<GameEventsManager>,
+) {
+	let _active_socket = ActiveSocketGuard::new("stories");
+
+	let mut receiver = game_events_manager.subscribe_to_story_events();
+
+	let (mut ws_sender, mut ws_receiver) = socket.split();
+
+	let mut write_task = tokio::spawn(async move {
+		loop {
+			match receiver.recv().await {
+				Ok(msg) => {
+					if let Err(e) = send_msg(&mut ws_sender, msg).await {
+						eprintln!("Failed to send message to websocket: {:?}", e);
+						break;
+					}
+				}
+				// Feed updates are only hints, slow observers just miss some
+				Err(RecvError::Lagged(_)) => continue,
+				Err(RecvError::Closed) => break,
+			}
+		}
+	});
+
+	loop {
+		select! {
+			ws_msg = ws_receiver.next() => {
+				match ws_msg {
+					Some(Ok(msg)) => {
+						if msg == Message::Close(None) {
+							break;
+						}
+					}
+					_ => {
+						break;
+					}
+				}
+			}
+			_ = &mut write_task => {
+				break;
+			}
+		}
+	}
+
+	write_task.abort();
+}
+
+async fn send_msg(
+	socket: &mut SplitSink<WebSocket, axum::extract::ws::Message>,
+	msg: StoryEvent,
+) -> Result<(), axum::Error> {
+	if let Ok(json) = serde_json::to_string(&msg) {
+		socket.send(Message::Text(json.into())).await?;
+	}
+	Ok(())
+}
@@ -0,0 +1,101 @@
+use std::{sync::Arc, time::Instant};
+
+use axum::{
+	Extension, Router,
+	extract::{MatchedPath, Request},
+	http::StatusCode,
+	middleware::Next,
+	response::{IntoResponse, Response},
+	routing::get,
+};
+use lib_core::model::{ModelManager, schema_enums::SessionStatus};
+use lib_sessions::model::Session;
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use tracing::error;
+
+const LATENCY_BUCKETS: &[f64] = &[
+	0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+const STORY_GENERATION_BUCKETS: &[f64] =
+	&[1.0, 2.5, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0, 300.0];
+
+/// Installs the global Prometheus recorder used by the `metrics` macros.
+pub fn install_recorder() -> PrometheusHandle {
+	PrometheusBuilder::new()
+		.set_buckets_for_metric(
+			Matcher::Full("http_request_duration_seconds".to_string()),
+			LATENCY_BUCKETS,
+		)
+		.and_then(|builder| {
+			builder.set_buckets_for_metric(
+				Matcher::Full("story_generation_duration_seconds".to_string()),
+				STORY_GENERATION_BUCKETS,
+			)
+		})
+		.and_then(|builder| builder.install_recorder())
+		.expect("Failed to install Prometheus recorder")
+}
+
+pub fn metrics_router(handle: PrometheusHandle, mm: Arc<ModelManager>) -> Router {
+	Router::new()
+		.route("/metrics", get(metrics_handler))
+		.layer(Extension(handle))
+		.layer(Extension(mm))
+}
+
+async fn metrics_handler(
+	Extension(handle): Extension<PrometheusHandle>,
+	Extension(mm): Extension<Arc<ModelManager>>,
+) -> impl IntoResponse {
+	// Session counts live in the db, sample them on scrape
+	let counts = mm
+		.run_blocking_read(|conn| {
+			Session::count_by_status(conn)
+				.map_err(lib_core::model::error::Error::from)
+		})
+		.await;
+
+	match counts {
+		Ok(counts) => {
+			for status in SessionStatus::ALL
+				.iter()
+				.filter(|s| **s != SessionStatus::Finished)
+			{
+				let count = counts
+					.iter()
+					.find(|(s, _)| s == status)
+					.map(|(_, count)| *count)
+					.unwrap_or(0);
+
+				metrics::gauge!("sessions_active", "status" => status.as_str())
+					.set(count as f64);
+			}
+		}
+		Err(err) => error!("METRICS - failed to count sessions: {err}"),
+	}
+
+	(StatusCode::OK, handle.render())
+}
+
+/// Records `http_request_duration_seconds` per matched route.
+pub async fn mw_track_metrics(req: Request, next: Next) -> Response {
+	let started_at = Instant::now();
+	let method = req.method().to_string();
+	let route = req
+		.extensions()
+		.get::<MatchedPath>()
+		.map(|path| path.as_str().to_string())
+		.unwrap_or_else(|| "unmatched".to_string());
+
+	let res = next.run(req).await;
+
+	metrics::histogram!(
+		"http_request_duration_seconds",
+		"method" => method,
+		"route" => route,
+		"status" => res.status().as_u16().to_string(),
+	)
+	.record(started_at.elapsed().as_secs_f64());
+
+	res
+}
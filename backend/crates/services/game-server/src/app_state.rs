@@ -4,6 +4,7 @@ use lib_ai::client::AiClient;
 use lib_core::{config::core_config, model::ModelManager};
 use lib_game_events::manager::GameEventsManager;
 use lib_game_logic::engine::GameEngine;
+use tracing::error;
 
 #[derive(Clone)]
 pub struct AppState {
@@ -23,15 +24,21 @@ impl AppState {
 		));
 
 		let model_manager = Arc::new(ModelManager::new().await.unwrap());
+		let game_engine = Arc::new(GameEngine::new(
+			model_manager.clone(),
+			game_events_manager.clone(),
+			ai_client.clone(),
+		));
+
+		// Games interrupted by a restart pick up where they stopped
+		if let Err(e) = game_engine.run(|engine| engine.resume_interrupted()).await {
+			error!("Failed to resume interrupted games: {:?}", e);
+		}
 
 		Self {
-			model_manager: model_manager.clone(),
-			game_events_manager: game_events_manager.clone(),
-			game_engine: Arc::new(GameEngine::new(
-				model_manager.clone(),
-				game_events_manager.clone(),
-				ai_client.clone(),
-			)),
+			model_manager,
+			game_events_manager,
+			game_engine,
 		}
 	}
 }
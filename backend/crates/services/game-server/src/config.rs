@@ -0,0 +1,65 @@
+use std::{str::FromStr, sync::OnceLock};
+
+use lib_utils::envs::{get_env_opt, get_env_parse_or};
+
+pub fn server_config() -> &'static ServerConfig {
+	static INSTANCE: OnceLock<ServerConfig> = OnceLock::new();
+
+	INSTANCE.get_or_init(|| {
+		ServerConfig::load_from_env().unwrap_or_else(|ex| {
+			panic!("FATAL - WHILE LOADING SERVER CONF - Cause: {ex:?}")
+		})
+	})
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+	Pretty,
+	Json,
+}
+
+impl FromStr for LogFormat {
+	type Err = ();
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_ascii_lowercase().as_str() {
+			"pretty" | "text" => Ok(LogFormat::Pretty),
+			"json" => Ok(LogFormat::Json),
+			_ => Err(()),
+		}
+	}
+}
+
+#[allow(non_snake_case)]
+pub struct ServerConfig {
+	// -- Logging
+	/// `EnvFilter` directives, e.g. `info,game_server=debug`.
+	pub LOG_LEVEL: String,
+	pub LOG_FORMAT: LogFormat,
+
+	// -- Tracing export
+	/// OTLP collector endpoint, only used with the `otlp` feature.
+	pub OTLP_ENDPOINT: Option<String>,
+	#[cfg_attr(not(feature = "otlp"), allow(dead_code))]
+	pub OTEL_SERVICE_NAME: String,
+}
+
+impl ServerConfig {
+	fn load_from_env() -> lib_utils::envs::Result<ServerConfig> {
+		let log_level = get_env_opt("LOG_LEVEL")
+			.unwrap_or_else(|| "info,game_server=debug".to_string());
+		let log_format = get_env_parse_or("LOG_FORMAT", LogFormat::Pretty)?;
+		let otlp_endpoint = get_env_opt("OTEL_EXPORTER_OTLP_ENDPOINT");
+		let otel_service_name = get_env_opt("OTEL_SERVICE_NAME")
+			.unwrap_or_else(|| "game-server".to_string());
+
+		Ok(ServerConfig {
+			// -- Logging
+			LOG_LEVEL: log_level,
+			LOG_FORMAT: log_format,
+			// -- Tracing export
+			OTLP_ENDPOINT: otlp_endpoint,
+			OTEL_SERVICE_NAME: otel_service_name,
+		})
+	}
+}
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use axum::{
+	Extension, Json, Router, http::StatusCode, response::IntoResponse, routing::get,
+};
+use diesel::RunQueryDsl;
+use lib_ai::client::AiClient;
+use lib_core::model::ModelManager;
+use serde_json::json;
+
+pub fn health_router(mm: Arc<ModelManager>, ai_client: Arc<AiClient>) -> Router {
+	Router::new()
+		.route("/healthz", get(liveness_handler))
+		.route("/readyz", get(readiness_handler))
+		.route("/health/db", get(db_health_handler))
+		.layer(Extension(mm))
+		.layer(Extension(ai_client))
+}
+
+/// Liveness: the process is up and serving requests.
+async fn liveness_handler() -> impl IntoResponse {
+	(StatusCode::OK, Json(json!({ "status": "ok" })))
+}
+
+/// Readiness: the database answers and the AI provider is configured.
+async fn readiness_handler(
+	Extension(mm): Extension<Arc<ModelManager>>,
+	Extension(ai_client): Extension<Arc<AiClient>>,
+) -> impl IntoResponse {
+	let db_check = mm
+		.run_blocking(|conn| {
+			diesel::sql_query("SELECT 1")
+				.execute(conn)
+				.map_err(lib_core::model::error::Error::from)
+		})
+		.await
+		.map(|_| ())
+		.map_err(|err| err.to_string());
+
+	let ai_check = ai_client.check_config().map_err(|err| err.to_string());
+
+	let ready = db_check.is_ok() && ai_check.is_ok();
+	let status = if ready {
+		StatusCode::OK
+	} else {
+		StatusCode::SERVICE_UNAVAILABLE
+	};
+
+	(
+		status,
+		Json(json!({
+			"status": if ready { "ready" } else { "not_ready" },
+			"checks": {
+				"database": check_json(&db_check),
+				"ai_provider": check_json(&ai_check),
+			},
+			"pools": mm.stats(),
+		})),
+	)
+}
+
+fn check_json(check: &Result<(), String>) -> serde_json::Value {
+	match check {
+		Ok(()) => json!({ "ok": true }),
+		Err(err) => json!({ "ok": false, "error": err }),
+	}
+}
+
+/// Reports connection usage of the primary and replica pools.
+async fn db_health_handler(
+	Extension(mm): Extension<Arc<ModelManager>>,
+) -> impl IntoResponse {
+	(StatusCode::OK, Json(mm.stats()))
+}
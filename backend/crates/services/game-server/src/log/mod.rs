@@ -1,23 +1,36 @@
-use axum::http::{Method, Uri};
+pub mod tracing_init;
+
+use std::time::Duration;
+
+use axum::http::{Method, StatusCode, Uri};
 use lib_core::{client_error::ClientError, ctx::CtxExtResult};
-use tracing::debug;
+use tracing::info;
 use uuid::Uuid;
 
 pub async fn log_request(
 	uuid: Uuid,
 	req_method: Method,
 	uri: Uri,
+	status: StatusCode,
+	duration: Duration,
 	ctx: Option<CtxExtResult>,
 	client_error: Option<&ClientError>,
 ) -> Result<(), ()> {
-	let ctx_str = match ctx.as_ref() {
-		Some(Ok(c)) => format!("user_id={}, username={}", c.user_id, c.username),
-		_ => "no_ctx".to_string(),
+	let user_id = match ctx.as_ref() {
+		Some(Ok(c)) => Some(c.user_id.to_string()),
+		_ => None,
 	};
+	let client_error = client_error.and_then(|err| serde_json::to_string(err).ok());
 
-	debug!(
-		"REQ_LOG | uuid={} | method={} | uri={} | ctx={} | client_error={:?}",
-		uuid, req_method, uri, ctx_str, client_error
+	info!(
+		req_uuid = %uuid,
+		method = %req_method,
+		uri = %uri,
+		status = status.as_u16(),
+		duration_ms = duration.as_millis() as u64,
+		user_id = user_id.as_deref(),
+		client_error = client_error.as_deref(),
+		"REQ_LOG"
 	);
 
 	Ok(())
@@ -0,0 +1,122 @@
+use tracing_subscriber::{
+	EnvFilter, Layer, Registry, layer::SubscriberExt, util::SubscriberInitExt,
+};
+
+use crate::config::{LogFormat, server_config};
+
+/// Keeps the tracing exporters alive, flushing them on drop.
+pub struct TracingGuard {
+	#[cfg(feature = "otlp")]
+	tracer_provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+impl Drop for TracingGuard {
+	fn drop(&mut self) {
+		#[cfg(feature = "otlp")]
+		if let Some(provider) = self.tracer_provider.take()
+			&& let Err(err) = provider.shutdown()
+		{
+			eprintln!("Failed to shut down OTLP tracer provider: {err}");
+		}
+	}
+}
+
+/// Installs the global subscriber from `LOG_LEVEL` / `LOG_FORMAT`,
+/// plus the OTLP exporter when built with the `otlp` feature.
+pub fn init_tracing() -> TracingGuard {
+	let config = server_config();
+
+	let filter = EnvFilter::try_new(&config.LOG_LEVEL).unwrap_or_else(|err| {
+		eprintln!("Invalid LOG_LEVEL '{}': {err}", config.LOG_LEVEL);
+		EnvFilter::new("info")
+	});
+
+	let fmt_layer: Box<dyn Layer<Registry> + Send + Sync> = match config.LOG_FORMAT {
+		LogFormat::Pretty => tracing_subscriber::fmt::layer()
+			.without_time()
+			.with_target(false)
+			.boxed(),
+		LogFormat::Json => tracing_subscriber::fmt::layer()
+			.json()
+			.with_current_span(true)
+			.with_span_list(false)
+			.boxed(),
+	};
+
+	#[cfg(feature = "otlp")]
+	{
+		let (otel_layer, tracer_provider) = match otlp::layer() {
+			Some((layer, provider)) => (Some(layer), Some(provider)),
+			None => (None, None),
+		};
+
+		tracing_subscriber::registry()
+			.with(fmt_layer)
+			.with(otel_layer)
+			.with(filter)
+			.init();
+
+		TracingGuard { tracer_provider }
+	}
+
+	#[cfg(not(feature = "otlp"))]
+	{
+		tracing_subscriber::registry()
+			.with(fmt_layer)
+			.with(filter)
+			.init();
+
+		if config.OTLP_ENDPOINT.is_some() {
+			tracing::warn!(
+				"OTEL_EXPORTER_OTLP_ENDPOINT is set but the server was built without the `otlp` feature"
+			);
+		}
+
+		TracingGuard {}
+	}
+}
+
+#[cfg(feature = "otlp")]
+mod otlp {
+	use opentelemetry::trace::TracerProvider;
+	use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+	use opentelemetry_sdk::{Resource, trace::SdkTracerProvider};
+	use tracing_opentelemetry::OpenTelemetryLayer;
+	use tracing_subscriber::Registry;
+
+	use crate::config::server_config;
+
+	type OtelLayer = OpenTelemetryLayer<
+		tracing_subscriber::layer::Layered<
+			Box<dyn tracing_subscriber::Layer<Registry> + Send + Sync>,
+			Registry,
+		>,
+		opentelemetry_sdk::trace::Tracer,
+	>;
+
+	/// Builds the OTLP/HTTP span exporter layer, `None` when no endpoint is set.
+	pub fn layer() -> Option<(OtelLayer, SdkTracerProvider)> {
+		let config = server_config();
+		let endpoint = config.OTLP_ENDPOINT.as_ref()?;
+
+		let exporter = SpanExporter::builder()
+			.with_http()
+			.with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')))
+			.build()
+			.inspect_err(|err| eprintln!("Failed to build OTLP exporter: {err}"))
+			.ok()?;
+
+		let provider = SdkTracerProvider::builder()
+			.with_batch_exporter(exporter)
+			.with_resource(
+				Resource::builder()
+					.with_service_name(config.OTEL_SERVICE_NAME.clone())
+					.build(),
+			)
+			.build();
+
+		let tracer = provider.tracer("game-server");
+
+		Some((tracing_opentelemetry::layer().with_tracer(tracer), provider))
+	}
+}
@@ -1,10 +1,13 @@
+mod app_metrics;
 mod app_state;
+mod config;
 mod error;
+mod health;
 mod log;
 mod mw_auth;
 mod mw_res_map;
 
-use std::time::Duration;
+use std::{net::SocketAddr, time::Duration};
 
 use axum::{
 	http::{
@@ -12,23 +15,26 @@ use axum::{
 		HeaderValue, Method,
 	},
 	middleware::{self, from_fn},
-	Router,
+	Extension, Router,
 };
-use lib_auth::router::auth_router;
+use lib_auth::router::{account_router, auth_router};
 use lib_core::config::core_config;
-use lib_rest::router::rest_router;
+use lib_rate_limit::client_ip::TrustProxy;
+use lib_rest::router::{avatar_router, public_share_router, rest_router};
 use lib_websockets::router::websocket_router;
 use tower_http::cors::CorsLayer;
 
-use crate::app_state::AppState;
+use crate::{
+	app_metrics::{install_recorder, metrics_router},
+	app_state::AppState,
+	health::health_router,
+};
 
 #[tokio::main]
 pub async fn main() {
-	tracing_subscriber::fmt()
-		.without_time()
-		.with_target(false)
-		.with_env_filter("game_server=debug")
-		.init();
+	let _tracing_guard = log::tracing_init::init_tracing();
+
+	let metrics_handle = install_recorder();
 
 	let app_state = AppState::new().await;
 
@@ -40,16 +46,40 @@ pub async fn main() {
 
 	let cors = CorsLayer::new()
 		.allow_origin(allowed_origins)
-		.allow_methods([Method::GET, Method::POST, Method::DELETE])
+		.allow_methods([
+			Method::GET,
+			Method::POST,
+			Method::PUT,
+			Method::PATCH,
+			Method::DELETE,
+		])
 		.allow_headers([CONTENT_TYPE, AUTHORIZATION])
+		.expose_headers([mw_res_map::X_REQUEST_ID])
 		.allow_credentials(true)
 		.max_age(Duration::from_secs(3600));
 
 	let app = Router::new()
+		.merge(health_router(
+			app_state.model_manager.clone(),
+			app_state.game_engine.ai_client.clone(),
+		))
+		.merge(metrics_router(
+			metrics_handle,
+			app_state.model_manager.clone(),
+		))
+		.merge(avatar_router())
+		.merge(public_share_router(app_state.model_manager.clone()))
 		.merge(auth_router(
 			app_state.model_manager.clone(),
 			core_config().JWT_SECRET.clone(),
 		))
+		.merge(
+			account_router(
+				app_state.model_manager.clone(),
+				core_config().JWT_SECRET.clone(),
+			)
+			.route_layer(from_fn(mw_auth::mw_required_auth)),
+		)
 		.merge(
 			websocket_router(app_state.game_events_manager.clone())
 				.route_layer(from_fn(mw_auth::mw_required_auth)),
@@ -62,15 +92,22 @@ pub async fn main() {
 			.route_layer(from_fn(mw_auth::mw_required_auth)),
 		)
 		.route_layer(from_fn(mw_res_map::mw_response_map))
+		.route_layer(from_fn(app_metrics::mw_track_metrics))
 		.layer(middleware::from_fn_with_state(
 			app_state.clone(),
 			mw_auth::mw_ctx_resolver,
 		))
+		.layer(Extension(TrustProxy(core_config().RATE_LIMIT_TRUST_PROXY)))
 		.layer(cors);
 
 	println!("Listening on http://localhost:3000");
 
 	// run our app with hyper, listening globally on port 3000
 	let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
-	axum::serve(listener, app).await.unwrap();
+	axum::serve(
+		listener,
+		app.into_make_service_with_connect_info::<SocketAddr>(),
+	)
+	.await
+	.unwrap();
 }
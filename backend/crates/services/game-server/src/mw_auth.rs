@@ -10,8 +10,9 @@ use axum::{
 	middleware::Next,
 	response::Response,
 };
-use jsonwebtoken::{DecodingKey, Validation, decode};
-use lib_auth::{auth::jwt::Claims, users::model::User};
+use lib_auth::{
+	auth::jwt::decode_jwt, tokens::model::RevokedToken, users::model::User,
+};
 use lib_core::{
 	config::core_config,
 	ctx::{Ctx, CtxExtError, CtxExtResult, Result as CoreResult},
@@ -26,8 +27,10 @@ pub async fn mw_required_auth(
 ) -> Result<Response> {
 	debug!("{:<12} - mw_required_auth", "MIDDLEWARE");
 
-	// TODO: add another errors handling
-	ctx.map_err(|_| AppError::CtxExt(CtxExtError::CtxNotInRequestExt))?;
+	ctx.map_err(|ex| match ex {
+		lib_core::ctx::Error::CtxExt(err) => AppError::CtxExt(err),
+		_ => AppError::CtxExt(CtxExtError::CtxNotInRequestExt),
+	})?;
 
 	Ok(next.run(request).await)
 }
@@ -52,22 +55,29 @@ async fn _ctx_resolve(
 	headers: &HeaderMap,
 	uri: &Uri,
 ) -> CtxExtResult {
-	let mut db = mm.db();
-
 	let token = extract_bearer_token(headers)
 		.or_else(|| extract_token_from_query(uri))
 		.ok_or(CtxExtError::TokenNotInBarier)?;
 
-	let token_data = decode::<Claims>(
-		&token,
-		&DecodingKey::from_secret(core_config().JWT_SECRET.as_bytes()),
-		&Validation::default(),
-	)
-	.map_err(|_| CtxExtError::FailValidate)?;
+	let claims = decode_jwt(&token, core_config().JWT_SECRET.as_bytes())
+		.map_err(|_| CtxExtError::FailValidate)?;
+
+	let user_id = claims.sub;
+	let jti = claims.jti;
+	let user = mm
+		.run_blocking(move |db| {
+			let revoked =
+				RevokedToken::is_revoked(db, jti).map_err(|disel_error| {
+					CtxExtError::DbError(disel_error.to_string())
+				})?;
+			if revoked {
+				return Err(CtxExtError::TokenRevoked);
+			}
 
-	let user_id = token_data.claims.sub;
-	let user = User::get(&mut db, user_id)
-		.map_err(|disel_error| CtxExtError::DbError(disel_error.to_string()))?;
+			User::get(db, user_id)
+				.map_err(|disel_error| CtxExtError::DbError(disel_error.to_string()))
+		})
+		.await?;
 
 	Ctx::new(user_id, user.username)
 		.map_err(|ex| CtxExtError::CtxCreateFail(ex.to_string()))
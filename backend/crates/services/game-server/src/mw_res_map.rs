@@ -1,22 +1,41 @@
+use std::time::Instant;
+
 use axum::{
 	Json,
 	body::Body,
-	http::Request,
+	http::{HeaderName, HeaderValue, Request, header::RETRY_AFTER},
 	middleware::Next,
 	response::{IntoResponse, Response},
 };
 use lib_auth::auth::error::AuthError;
 use lib_core::{client_error::ClientError, ctx::CtxExtResult};
 use serde_json::{json, to_value};
-use tracing::{debug, error};
+use tracing::{Instrument, debug, error, info_span};
 use uuid::Uuid;
 
 use crate::{error::AppError, log::log_request};
 
-pub async fn mw_response_map(req: Request<Body>, next: Next) -> Response {
-	debug!("{:<12} - mw_response_map", "RES_MAPPER");
+pub const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
 
+pub async fn mw_response_map(req: Request<Body>, next: Next) -> Response {
 	let uuid = Uuid::new_v4();
+	let span = info_span!(
+		"request",
+		req_uuid = %uuid,
+		method = %req.method(),
+		uri = %req.uri(),
+	);
+
+	async move {
+		debug!("{:<12} - mw_response_map", "RES_MAPPER");
+		map_response(uuid, req, next).await
+	}
+	.instrument(span)
+	.await
+}
+
+async fn map_response(uuid: Uuid, req: Request<Body>, next: Next) -> Response {
+	let started_at = Instant::now();
 	let uri = req.uri().clone();
 	let req_method = req.method().clone();
 	let ctx = req.extensions().get::<CtxExtResult>().cloned();
@@ -46,21 +65,37 @@ pub async fn mw_response_map(req: Request<Body>, next: Next) -> Response {
 				});
 
 				debug!("CLIENT ERROR BODY:\n{client_error_body}");
-				(*status_code, Json(client_error_body)).into_response()
+				let mut error_response =
+					(*status_code, Json(client_error_body)).into_response();
+
+				// Keep the hint of rate limited responses
+				if let Some(retry_after) = res.headers().get(RETRY_AFTER) {
+					error_response
+						.headers_mut()
+						.insert(RETRY_AFTER, retry_after.clone());
+				}
+
+				error_response
 			});
 
+	let mut res = error_response.unwrap_or(res);
+
 	let _ = log_request(
 		uuid,
 		req_method,
 		uri,
+		res.status(),
+		started_at.elapsed(),
 		ctx,
 		client_status_error.as_ref().map(|(_, err)| err),
 	)
 	.await;
 
-	debug!("\n");
+	if let Ok(value) = HeaderValue::from_str(&uuid.to_string()) {
+		res.headers_mut().insert(X_REQUEST_ID, value);
+	}
 
-	error_response.unwrap_or(res)
+	res
 }
 
 fn find_client_status_error(
@@ -78,6 +113,10 @@ fn find_client_status_error(
 		error!("REST ERROR: {err:?}");
 		return Some(err.client_status_and_error());
 	}
+	if let Some(err) = res.extensions().get::<lib_rate_limit::error::Error>() {
+		error!("RATE LIMIT ERROR: {err}");
+		return Some(err.client_status_and_error());
+	}
 
 	None
 }
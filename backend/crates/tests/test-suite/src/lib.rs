@@ -1,3 +1,11 @@
+mod test_engine;
+mod test_export;
 mod test_play_flow;
 mod test_players;
+mod test_rate_limit;
 mod test_sessions;
+mod test_stories;
+mod test_tokens;
+mod test_users;
+mod test_validation;
+mod test_visibility;
@@ -0,0 +1,450 @@
+#[cfg(test)]
+mod test_super {
+	use std::{sync::Arc, time::Duration};
+
+	use lib_ai::client::AiClient;
+	use lib_auth::users::model::{NewUser, User};
+	use lib_core::model::{
+		TestModelManager,
+		base::BasicDbOps,
+		schema_enums::{
+			NarratorMode, PlayMode, SessionStatus, TurnOrderMode, VisibilityMode,
+		},
+	};
+	use lib_game_events::manager::GameEventsManager;
+	use lib_game_logic::{
+		engine::{GameEngine, SessionSettings},
+		error::Error,
+	};
+	use lib_messages::model::Message;
+	use lib_players::model::Player;
+	use lib_profiles::achievements::model::UserAchievement;
+	use lib_sessions::model::Session;
+	use lib_stories::{
+		model::{NewStory, Story},
+		reactions::model::StoryRating,
+	};
+	use serial_test::serial;
+	use uuid::Uuid;
+
+	/// An engine on the test database, its AI client never gets called.
+	fn engine(mm: &TestModelManager) -> GameEngine {
+		GameEngine::new(
+			Arc::new(mm.model_manager()),
+			Arc::new(GameEventsManager::new()),
+			Arc::new(AiClient::new("test", "test", "test", "test")),
+		)
+	}
+
+	fn settings(turn_order: TurnOrderMode) -> SessionSettings {
+		SessionSettings {
+			max_rounds: 2,
+			story_variants: 1,
+			visibility: VisibilityMode::Previous,
+			visibility_words: 10,
+			play_mode: PlayMode::Linear,
+			turn_order,
+			bot_backfill: false,
+			narrator: NarratorMode::Off,
+			objectives: false,
+			undo_window_secs: 0,
+		}
+	}
+
+	fn create_users(mm: &TestModelManager, usernames: &[&str]) -> Vec<Uuid> {
+		let mut conn = mm.db();
+		usernames
+			.iter()
+			.map(|username| {
+				User::create(
+					&mut conn,
+					NewUser {
+						username,
+						password_hash: Some("hash"),
+						is_guest: false,
+					},
+				)
+				.expect("create user failed")
+				.id
+			})
+			.collect()
+	}
+
+	/// Seats the other users and starts the game, the host goes first.
+	fn start_with(engine: &GameEngine, session_id: Uuid, users: &[Uuid]) {
+		for user_id in &users[1..] {
+			engine
+				.join_session(session_id, *user_id, None)
+				.expect("join failed");
+		}
+		for user_id in users {
+			engine
+				.set_ready(session_id, *user_id, true, None)
+				.expect("ready failed");
+		}
+		engine
+			.start_game(session_id, users[0], None)
+			.expect("start failed");
+	}
+
+	/// Polls the session until background tasks brought it to `done`.
+	async fn wait_for(
+		mm: &TestModelManager,
+		session_id: Uuid,
+		done: impl Fn(&Session) -> bool,
+	) -> Session {
+		for _ in 0..50 {
+			let session =
+				Session::get(&mut mm.db(), session_id).expect("get failed");
+			if done(&session) {
+				return session;
+			}
+			tokio::time::sleep(Duration::from_millis(100)).await;
+		}
+		panic!("session {session_id} never got there");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_host_starts_game_from_any_seat() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let engine = engine(&mm);
+		let users = create_users(&mm, &["host", "guest"]);
+		let (host, guest) = (users[0], users[1]);
+
+		let session = engine
+			.create_session("seats", host, settings(TurnOrderMode::Manual))
+			.expect("create session failed");
+		engine
+			.join_session(session.id, guest, None)
+			.expect("join failed");
+		for user_id in [host, guest] {
+			engine
+				.set_ready(session.id, user_id, true, None)
+				.expect("ready failed");
+		}
+
+		// The guest takes the first seat
+		engine
+			.arrange_seats(session.id, host, vec![guest, host], None)
+			.expect("arrange failed");
+		let mut conn = mm.db();
+		let seated =
+			Player::list_by_session(&mut conn, session.id).expect("list failed");
+		assert_eq!(seated[0].user_id, guest);
+		let found = Player::get_host(&mut conn, session.id)
+			.expect("get host failed")
+			.expect("host not found");
+		assert_eq!(found.user_id, host);
+
+		// Only the host starts, whatever seat they're in
+		assert!(matches!(
+			engine.start_game(session.id, guest, None),
+			Err(Error::NotHost)
+		));
+		let started = engine
+			.start_game(session.id, host, None)
+			.expect("start failed");
+		assert_eq!(started.status, SessionStatus::Started);
+		assert_eq!(started.current_user_id_turn, Some(guest));
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_rotating_rounds_list_lines_in_write_order() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let engine = engine(&mm);
+		let users = create_users(&mm, &["ann", "ben", "cid"]);
+		let (ann, ben, cid) = (users[0], users[1], users[2]);
+
+		let session = engine
+			.create_session(
+				"rotating",
+				ann,
+				SessionSettings {
+					max_rounds: 3,
+					..settings(TurnOrderMode::RotatingStarter)
+				},
+			)
+			.expect("create session failed");
+		start_with(&engine, session.id, &users);
+
+		// Ben starts round 2, Ann writes last
+		let turns = [ann, ben, cid, ben, cid, ann];
+		for (line, user_id) in turns.iter().enumerate() {
+			engine
+				.submit_message(session.id, *user_id, &format!("Line {line}."), None)
+				.expect("submit failed");
+		}
+
+		let mut conn = mm.db();
+		let authors = Message::list_by_session(&mut conn, session.id)
+			.expect("list failed")
+			.into_iter()
+			.filter_map(|message| message.user_id)
+			.collect::<Vec<_>>();
+		assert_eq!(authors, turns);
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_rotating_starter_survives_a_leave() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let engine = engine(&mm);
+		let users = create_users(&mm, &["ann", "ben", "cid", "dee"]);
+		let (ann, ben, cid, dee) = (users[0], users[1], users[2], users[3]);
+
+		let session = engine
+			.create_session(
+				"rotating",
+				ann,
+				SessionSettings {
+					max_rounds: 4,
+					..settings(TurnOrderMode::RotatingStarter)
+				},
+			)
+			.expect("create session failed");
+		start_with(&engine, session.id, &users);
+
+		for user_id in [ann, ben, cid, dee, ben] {
+			engine
+				.submit_message(session.id, user_id, "A line.", None)
+				.expect("submit failed");
+		}
+		// Ben started round 2 and leaves, the others finish it
+		engine
+			.leave_session(session.id, ben, None)
+			.expect("leave failed");
+		for user_id in [cid, dee, ann] {
+			engine
+				.submit_message(session.id, user_id, "A line.", None)
+				.expect("submit failed");
+		}
+
+		// Cid's seat comes next, not the third player left
+		let session = Session::get(&mut mm.db(), session.id).expect("get failed");
+		assert_eq!(session.current_round, 3);
+		assert_eq!(session.current_user_id_turn, Some(cid));
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_interrupted_narration_hands_the_turn_on() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let engine = engine(&mm);
+		let users = create_users(&mm, &["host", "guest"]);
+
+		let session = engine
+			.create_session(
+				"narrated",
+				users[0],
+				SessionSettings {
+					narrator: NarratorMode::Twists,
+					..settings(TurnOrderMode::JoinOrder)
+				},
+			)
+			.expect("create session failed");
+		start_with(&engine, session.id, &users);
+
+		// Narrating into round 2 when the server went down, deadline passed
+		let mut conn = mm.db();
+		let session = Session::get(&mut conn, session.id).expect("get failed");
+		Session::update_versioned(
+			&mut conn,
+			&Session {
+				current_round: 2,
+				current_user_id_turn: None,
+				narration_ends_at: Some(
+					chrono::Utc::now().naive_utc() - chrono::Duration::minutes(1),
+				),
+				..session
+			},
+		)
+		.expect("update failed");
+
+		engine.resume_interrupted().expect("resume failed");
+		let session =
+			wait_for(&mm, session.id, |s| s.current_user_id_turn.is_some()).await;
+		assert_eq!(session.current_user_id_turn, Some(users[0]));
+		assert_eq!(session.narration_ends_at, None);
+		assert!(
+			Message::get_narration(&mut conn, session.id, 1)
+				.expect("get failed")
+				.is_none()
+		);
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_no_suggestions_for_a_pending_line() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let engine = engine(&mm);
+		let users = create_users(&mm, &["host", "guest"]);
+
+		let session = engine
+			.create_session(
+				"undo",
+				users[0],
+				SessionSettings {
+					undo_window_secs: 30,
+					..settings(TurnOrderMode::JoinOrder)
+				},
+			)
+			.expect("create session failed");
+		start_with(&engine, session.id, &users);
+
+		engine
+			.submit_message(session.id, users[0], "The door creaked.", None)
+			.expect("submit failed");
+
+		// The turn is over, it only waits for the undo window
+		assert!(matches!(
+			engine.request_suggestions(session.id, users[0], None),
+			Err(Error::TurnPending)
+		));
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_rating_unlocks_critic() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let engine = engine(&mm);
+		let users = create_users(&mm, &["host", "reader"]);
+		let (host, reader) = (users[0], users[1]);
+
+		let session = engine
+			.create_session("rated", host, settings(TurnOrderMode::JoinOrder))
+			.expect("create session failed");
+		let mut conn = mm.db();
+		for content in 0..10 {
+			let story = Story::create(
+				&mut conn,
+				NewStory {
+					session_id: session.id,
+					content: &content.to_string(),
+					thread_id: None,
+				},
+			)
+			.expect("story create failed");
+			StoryRating::upsert(&mut conn, story.id, reader, 4)
+				.expect("rate failed");
+		}
+
+		engine
+			.check_achievements_of(session.id, reader)
+			.expect("check failed");
+		let unlocked = UserAchievement::list_by_user(&mut conn, reader)
+			.expect("list failed")
+			.into_iter()
+			.map(|achievement| achievement.achievement_id)
+			.collect::<Vec<_>>();
+		assert_eq!(unlocked, vec!["critic"]);
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_overdue_undo_window_releases_the_turn() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let engine = engine(&mm);
+		let users = create_users(&mm, &["host", "guest"]);
+		let (host, guest) = (users[0], users[1]);
+
+		let session = engine
+			.create_session(
+				"undo",
+				host,
+				SessionSettings {
+					undo_window_secs: 30,
+					..settings(TurnOrderMode::JoinOrder)
+				},
+			)
+			.expect("create session failed");
+		start_with(&engine, session.id, &users);
+
+		engine
+			.submit_message(session.id, host, "The door creaked.", None)
+			.expect("submit failed");
+
+		// The server went down during the window, which closed meanwhile
+		let mut conn = mm.db();
+		let overdue = |conn: &mut _| {
+			let session = Session::get(conn, session.id).expect("get failed");
+			assert!(session.pending_message_id.is_some());
+			Session::update_versioned(
+				conn,
+				&Session {
+					pending_until: Some(
+						chrono::Utc::now().naive_utc()
+							- chrono::Duration::seconds(1),
+					),
+					..session
+				},
+			)
+			.expect("update failed")
+		};
+		let seen = overdue(&mut conn);
+
+		// Too late to take the line back, the next player writes right away,
+		// against the version they saw before the release
+		assert!(matches!(
+			engine.retract_message(session.id, host, None),
+			Err(Error::NothingToUndo)
+		));
+		engine
+			.submit_message(
+				session.id,
+				guest,
+				"A cat walked in.",
+				Some(seen.version),
+			)
+			.expect("submit failed");
+		let held = Session::get(&mut conn, session.id).expect("get failed");
+		assert_eq!(held.current_user_id_turn, Some(guest));
+		assert!(held.pending_until.is_some());
+
+		// Pending lines are released on startup too
+		overdue(&mut conn);
+		engine.resume_interrupted().expect("resume failed");
+		let session =
+			wait_for(&mm, session.id, |s| s.current_user_id_turn == Some(host))
+				.await;
+		assert_eq!(session.current_round, 2);
+		assert_eq!(session.pending_message_id, None);
+		assert_eq!(session.pending_until, None);
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+}
@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod test_super {
+	use lib_export::{document::StoryDocument, format::ExportFormat, render};
+	use uuid::Uuid;
+
+	fn sample_document() -> StoryDocument {
+		StoryDocument {
+			story_id: Uuid::new_v4(),
+			theme: "The <Lost> Key!".to_string(),
+			contributors: vec![
+				"Ann".to_string(),
+				"Bob".to_string(),
+				"Cid".to_string(),
+			],
+			content: "Once upon a time.\n\nThe end.".to_string(),
+			created_at: chrono::Utc::now().naive_utc(),
+		}
+	}
+
+	#[test]
+	fn test_render_all_formats() {
+		let document = sample_document();
+		assert_eq!(document.byline(), "Ann, Bob and Cid");
+		assert_eq!(document.file_name("pdf"), "the-lost-key.pdf");
+
+		let markdown =
+			String::from_utf8(render(&document, ExportFormat::Markdown).unwrap())
+				.unwrap();
+		assert!(markdown.starts_with("# The \\<Lost\\> Key!"));
+
+		let html = String::from_utf8(render(&document, ExportFormat::Html).unwrap())
+			.unwrap();
+		assert!(html.contains("<h1>The &lt;Lost&gt; Key!</h1>"));
+		assert!(html.contains("<p>The end.</p>"));
+
+		// EPUB readers require the stored mimetype entry first
+		let epub = render(&document, ExportFormat::Epub).unwrap();
+		assert!(epub.starts_with(b"PK"));
+		assert_eq!(&epub[30..38], b"mimetype");
+
+		let pdf = render(&document, ExportFormat::Pdf).unwrap();
+		assert!(pdf.starts_with(b"%PDF"));
+
+		assert!("docx".parse::<ExportFormat>().is_err());
+	}
+}
@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod test_super {
+	use std::time::Duration;
+
+	use lib_rate_limit::{limiter::RateLimiter, throttle::LoginThrottle};
+
+	#[test]
+	fn test_limiter_burst() {
+		let limiter = RateLimiter::per_minute(3);
+
+		for _ in 0..3 {
+			limiter.check("ip:127.0.0.1").expect("within burst");
+		}
+		let err = limiter.check("ip:127.0.0.1").expect_err("over the limit");
+		assert!(err.retry_after_secs() > 0);
+
+		// Other keys have their own bucket
+		limiter.check("ip:127.0.0.2").expect("other key");
+	}
+
+	#[test]
+	fn test_login_lockout_grows() {
+		let throttle = LoginThrottle::new(
+			2,
+			Duration::from_secs(10),
+			Duration::from_secs(25),
+			Duration::from_secs(900),
+		);
+		let keys = ["ip:127.0.0.1", "user:alice"];
+
+		// Free attempts
+		throttle.record_failure(&keys);
+		throttle.record_failure(&keys);
+		throttle.check(&keys).expect("not locked yet");
+
+		// base, then doubled, then capped
+		throttle.record_failure(&keys);
+		let first = throttle.check(&keys).expect_err("locked");
+		throttle.record_failure(&keys);
+		let second = throttle.check(&keys).expect_err("locked");
+		throttle.record_failure(&keys);
+		let capped = throttle.check(&keys).expect_err("locked");
+
+		assert!(first.retry_after_secs() <= 10);
+		assert!(second.retry_after_secs() > 10);
+		assert!(capped.retry_after_secs() <= 25);
+
+		// Success clears the username, the ip stays locked
+		throttle.record_success(&["user:alice"]);
+		throttle.check(&["user:alice"]).expect("username unlocked");
+		throttle
+			.check(&["ip:127.0.0.1"])
+			.expect_err("ip still locked");
+	}
+
+	#[test]
+	fn test_login_lockout_is_per_client() {
+		let throttle = LoginThrottle::new(
+			1,
+			Duration::from_secs(10),
+			Duration::from_secs(25),
+			Duration::from_secs(900),
+		);
+		let attacker = ["ip:10.0.0.1", "ip:10.0.0.1:user:alice"];
+		let owner = ["ip:10.0.0.2", "ip:10.0.0.2:user:alice"];
+
+		throttle.record_failure(&attacker);
+		throttle.record_failure(&attacker);
+		throttle.check(&attacker).expect_err("attacker locked");
+
+		// The same username from another client is not locked
+		throttle.check(&owner).expect("owner can still log in");
+	}
+}
@@ -1,6 +1,28 @@
 #[cfg(test)]
 mod test_super {
-	use lib_core::model::{TestModelManager, base::BasicDbOps};
+	use lib_auth::users::model::{NewUser, User};
+	use lib_core::model::{
+		TestModelManager,
+		base::BasicDbOps,
+		schema_enums::{
+			BotDifficulty, NarratorMode, PlayMode, SessionStatus, TurnOrderMode,
+			VisibilityMode,
+		},
+	};
+	use lib_messages::{
+		model::{Message, NARRATION_TURN_ORDER, NewMessage},
+		suggestions::model::{NewSuggestion, Suggestion},
+		threads::model::Thread,
+	};
+	use lib_players::{
+		bots::model::Bot,
+		model::{NewPlayer, Player, PlayerId},
+		objectives::model::PlayerObjective,
+	};
+	use lib_profiles::{
+		achievements::model::UserAchievement,
+		stats::model::{LeaderboardEntry, UserStats},
+	};
 	use lib_sessions::model::{NewSession, Session};
 	use serial_test::serial;
 
@@ -8,6 +30,15 @@ mod test_super {
 		NewSession {
 			theme: "dark",
 			max_rounds: 3,
+			story_variants: 1,
+			visibility: VisibilityMode::Previous,
+			visibility_words: 10,
+			play_mode: PlayMode::Linear,
+			turn_order: TurnOrderMode::JoinOrder,
+			bot_backfill: false,
+			narrator: NarratorMode::Off,
+			objectives: false,
+			undo_window_secs: 0,
 		}
 	}
 
@@ -91,4 +122,679 @@ mod test_super {
 		// Drop test DB
 		mm.drop_db().await.expect("Failed to drop test DB");
 	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_update_versioned() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let mut conn = mm.db();
+
+		// Create
+		let created =
+			Session::create(&mut conn, sample_session()).expect("create failed");
+		assert_eq!(created.version, 1);
+
+		// Versioned update bumps the version
+		let mut to_update = created.clone();
+		to_update.theme = "light".to_string();
+		let updated = Session::update_versioned(&mut conn, &to_update)
+			.expect("versioned update failed");
+		assert_eq!(updated.version, 2);
+
+		// Updating from the stale snapshot is rejected
+		let stale = Session::update_versioned(&mut conn, &created);
+		assert!(matches!(stale, Err(diesel::result::Error::NotFound)));
+
+		// Bumping from the current version works, from a stale one does not
+		let bumped = Session::bump_version(&mut conn, created.id, updated.version)
+			.expect("bump failed");
+		assert_eq!(bumped, 3);
+		assert!(
+			Session::bump_version(&mut conn, created.id, updated.version).is_err()
+		);
+
+		// Cleanup
+		Session::delete(&mut conn, created.id).expect("delete failed");
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_threads_collect_messages_per_round() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let mut conn = mm.db();
+
+		let session = Session::create(
+			&mut conn,
+			NewSession {
+				play_mode: PlayMode::Rotating,
+				..sample_session()
+			},
+		)
+		.expect("create failed");
+		let threads = Thread::create_for_session(&mut conn, session.id, 2)
+			.expect("threads create failed");
+		assert_eq!(threads.len(), 2);
+
+		let mut users = Vec::new();
+		for username in ["lena", "mia"] {
+			let user = User::create(
+				&mut conn,
+				NewUser {
+					username,
+					password_hash: Some("hash"),
+					is_guest: false,
+				},
+			)
+			.expect("create user failed");
+			users.push(user.id);
+		}
+
+		// Round 1 each writes on their own thread, round 2 they swap
+		for (round, offset) in [(1, 0), (2, 1)] {
+			for (position, user_id) in users.iter().enumerate() {
+				let thread = &threads[(position + offset) % threads.len()];
+				Message::create(
+					&mut conn,
+					NewMessage {
+						session_id: session.id,
+						user_id: Some(*user_id),
+						content: &format!("round {round}"),
+						round,
+						turn_order: position as i32,
+						thread_id: Some(thread.id),
+						used_suggestion: false,
+					},
+				)
+				.expect("message create failed");
+			}
+		}
+
+		let first =
+			Message::list_by_thread(&mut conn, threads[0].id).expect("list failed");
+		assert_eq!(
+			first.iter().filter_map(|m| m.user_id).collect::<Vec<_>>(),
+			vec![users[0], users[1]]
+		);
+		let authors = Message::authors_in_round(&mut conn, session.id, 2)
+			.expect("authors failed");
+		assert_eq!(authors.len(), 2);
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_seats_order_players_and_can_be_swapped() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let mut conn = mm.db();
+
+		let session =
+			Session::create(&mut conn, sample_session()).expect("create failed");
+
+		let mut users = Vec::new();
+		for username in ["nina", "otto", "pia"] {
+			let user = User::create(
+				&mut conn,
+				NewUser {
+					username,
+					password_hash: Some("hash"),
+					is_guest: false,
+				},
+			)
+			.expect("create user failed");
+			let seat =
+				Player::next_seat(&mut conn, session.id).expect("next seat failed");
+			Player::create(
+				&mut conn,
+				NewPlayer {
+					session_id: session.id,
+					user_id: user.id,
+					is_ready: false,
+					is_host: users.is_empty(),
+					seat,
+				},
+			)
+			.expect("player create failed");
+			users.push(user.id);
+		}
+
+		// Seats are unique, so swapping them relies on the deferred check
+		let arranged = vec![users[2], users[0], users[1]];
+		conn.build_transaction()
+			.run(|conn| Player::set_seats(conn, session.id, &arranged))
+			.expect("set seats failed");
+
+		let seated = Player::list_by_session(&mut conn, session.id)
+			.expect("list failed")
+			.into_iter()
+			.map(|p| (p.user_id, p.seat))
+			.collect::<Vec<_>>();
+		assert_eq!(seated, vec![(users[2], 0), (users[0], 1), (users[1], 2)]);
+		assert_eq!(Player::next_seat(&mut conn, session.id).expect("next"), 3);
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_bot_takes_over_seat_of_leaver() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let mut conn = mm.db();
+
+		let session =
+			Session::create(&mut conn, sample_session()).expect("create failed");
+
+		let mut users = Vec::new();
+		for (seat, username) in ["quin", "rosa"].into_iter().enumerate() {
+			let user = User::create(
+				&mut conn,
+				NewUser {
+					username,
+					password_hash: Some("hash"),
+					is_guest: false,
+				},
+			)
+			.expect("create user failed");
+			Player::create(
+				&mut conn,
+				NewPlayer {
+					session_id: session.id,
+					user_id: user.id,
+					is_ready: true,
+					is_host: seat == 0,
+					seat: seat as i32,
+				},
+			)
+			.expect("player create failed");
+			users.push(user.id);
+		}
+
+		Message::create(
+			&mut conn,
+			NewMessage {
+				session_id: session.id,
+				user_id: Some(users[1]),
+				content: "before leaving",
+				round: 1,
+				turn_order: 1,
+				thread_id: None,
+				used_suggestion: false,
+			},
+		)
+		.expect("message create failed");
+
+		// Rosa leaves, a bot is seated where she sat
+		Player::delete(
+			&mut conn,
+			PlayerId {
+				session_id: session.id,
+				user_id: users[1],
+			},
+		)
+		.expect("player delete failed");
+		let bot = Bot::create(&mut conn, "a gloomy poet", BotDifficulty::Hard)
+			.expect("bot create failed");
+		Player::create(
+			&mut conn,
+			NewPlayer {
+				session_id: session.id,
+				user_id: bot.user_id,
+				is_ready: true,
+				is_host: false,
+				seat: 1,
+			},
+		)
+		.expect("bot seat failed");
+
+		let seated = Session::list_users_in_session(&mut conn, session.id)
+			.expect("list failed")
+			.into_iter()
+			.map(|u| (u.user_id, u.seat, u.is_bot))
+			.collect::<Vec<_>>();
+		assert_eq!(seated, vec![(users[0], 0, false), (bot.user_id, 1, true)]);
+		assert_eq!(
+			Bot::ids_in_session(&mut conn, session.id).expect("bots failed"),
+			vec![bot.user_id]
+		);
+
+		// The seat already wrote this round, so the bot doesn't again
+		let seats =
+			Message::seats_in_round(&mut conn, session.id, 1).expect("seats failed");
+		assert_eq!(seats, vec![1]);
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_narration_follows_its_round() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let mut conn = mm.db();
+
+		let session =
+			Session::create(&mut conn, sample_session()).expect("create failed");
+		let user = User::create(
+			&mut conn,
+			NewUser {
+				username: "sven",
+				password_hash: Some("hash"),
+				is_guest: false,
+			},
+		)
+		.expect("create user failed");
+
+		for (user_id, content, turn_order) in [
+			(Some(user.id), "the ship left port", 0),
+			(
+				None,
+				"Meanwhile, a storm was gathering.",
+				NARRATION_TURN_ORDER,
+			),
+		] {
+			Message::create(
+				&mut conn,
+				NewMessage {
+					session_id: session.id,
+					user_id,
+					content,
+					round: 1,
+					turn_order,
+					thread_id: None,
+					used_suggestion: false,
+				},
+			)
+			.expect("message create failed");
+		}
+		Message::create(
+			&mut conn,
+			NewMessage {
+				session_id: session.id,
+				user_id: Some(user.id),
+				content: "the mast cracked",
+				round: 2,
+				turn_order: 0,
+				thread_id: None,
+				used_suggestion: false,
+			},
+		)
+		.expect("message create failed");
+
+		// Narration sits between the rounds, written by no one
+		let contents = Message::list_by_session(&mut conn, session.id)
+			.expect("list failed")
+			.into_iter()
+			.map(|m| m.content)
+			.collect::<Vec<_>>();
+		assert_eq!(
+			contents,
+			vec![
+				"the ship left port",
+				"Meanwhile, a storm was gathering.",
+				"the mast cracked"
+			]
+		);
+		let narration = Message::get_narration(&mut conn, session.id, 1)
+			.expect("narration failed")
+			.expect("narration not found");
+		assert_eq!(narration.user_id, None);
+
+		// Player queries leave it out
+		let round =
+			Message::list_by_round(&mut conn, session.id, 1).expect("round failed");
+		assert_eq!(round.len(), 1);
+		assert_eq!(
+			Message::seats_in_round(&mut conn, session.id, 1).expect("seats"),
+			vec![0]
+		);
+		assert_eq!(
+			Message::get_last_by_session(&mut conn, session.id)
+				.expect("last failed")
+				.content,
+			"the mast cracked"
+		);
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_suggestions_are_counted_per_turn() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let mut conn = mm.db();
+
+		let session =
+			Session::create(&mut conn, sample_session()).expect("create failed");
+		let user = User::create(
+			&mut conn,
+			NewUser {
+				username: "tove",
+				password_hash: Some("hash"),
+				is_guest: false,
+			},
+		)
+		.expect("create user failed");
+
+		for round in [1, 1, 2] {
+			Suggestion::create(
+				&mut conn,
+				NewSuggestion {
+					session_id: session.id,
+					user_id: user.id,
+					round,
+				},
+			)
+			.expect("suggestion create failed");
+		}
+
+		let count = |conn: &mut _, round| {
+			Suggestion::count_for_turn(conn, session.id, user.id, round)
+				.expect("count failed")
+		};
+		assert_eq!(count(&mut conn, 1), 2);
+		assert_eq!(count(&mut conn, 2), 1);
+		assert_eq!(count(&mut conn, 3), 0);
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_objectives_are_judged() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let mut conn = mm.db();
+
+		let session = Session::create(
+			&mut conn,
+			NewSession {
+				objectives: true,
+				..sample_session()
+			},
+		)
+		.expect("create failed");
+		let users = ["una", "vik"].map(|username| {
+			User::create(
+				&mut conn,
+				NewUser {
+					username,
+					password_hash: Some("hash"),
+					is_guest: false,
+				},
+			)
+			.expect("create user failed")
+		});
+
+		PlayerObjective::create_for_session(
+			&mut conn,
+			session.id,
+			&[
+				(users[0].id, "Get the word 'lighthouse' into the story."),
+				(users[1].id, "Make a character betray someone."),
+			],
+		)
+		.expect("objectives create failed");
+
+		let unjudged = PlayerObjective::list_by_session(&mut conn, session.id)
+			.expect("list failed");
+		assert_eq!(unjudged.len(), 2);
+		assert!(unjudged.iter().all(|o| o.met.is_none() && o.points == 0));
+
+		let judged =
+			PlayerObjective::judge(&mut conn, session.id, users[1].id, true, 10)
+				.expect("judge failed");
+		assert_eq!(judged.met, Some(true));
+		assert_eq!(judged.points, 10);
+
+		let objectives = PlayerObjective::list_by_session(&mut conn, session.id)
+			.expect("list failed");
+		let una = objectives
+			.iter()
+			.find(|o| o.user_id == users[0].id)
+			.unwrap();
+		assert_eq!(una.met, None);
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_stats_are_read_from_games() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let mut conn = mm.db();
+
+		let session = Session::create(
+			&mut conn,
+			NewSession {
+				objectives: true,
+				..sample_session()
+			},
+		)
+		.expect("create failed");
+		let users = ["wim", "xia"].map(|username| {
+			User::create(
+				&mut conn,
+				NewUser {
+					username,
+					password_hash: Some("hash"),
+					is_guest: false,
+				},
+			)
+			.expect("create user failed")
+		});
+
+		let lines = [
+			(0, 1, "The tide came in"),
+			(1, 1, "slowly"),
+			(0, 2, "At last"),
+		];
+		for (turn_order, (user, round, content)) in lines.into_iter().enumerate() {
+			Message::create(
+				&mut conn,
+				NewMessage {
+					session_id: session.id,
+					user_id: Some(users[user].id),
+					content,
+					round,
+					turn_order: turn_order as i32,
+					thread_id: None,
+					used_suggestion: false,
+				},
+			)
+			.expect("message create failed");
+		}
+
+		PlayerObjective::create_for_session(
+			&mut conn,
+			session.id,
+			&[
+				(users[0].id, "Have it start raining."),
+				(users[1].id, "Introduce a talking animal."),
+			],
+		)
+		.expect("objectives create failed");
+		PlayerObjective::judge(&mut conn, session.id, users[0].id, true, 10)
+			.expect("judge failed");
+		PlayerObjective::judge(&mut conn, session.id, users[1].id, false, 0)
+			.expect("judge failed");
+
+		let stats =
+			UserStats::for_user(&mut conn, users[0].id).expect("stats failed");
+		assert_eq!(stats.games_played, 1);
+		assert_eq!(stats.games_finished, 0);
+		assert_eq!(stats.words_contributed, 6);
+		assert!(stats.avg_turn_secs.is_some());
+		assert_eq!(stats.stories_rated, 0);
+		assert_eq!(stats.win_points, 10);
+
+		// Only users who scored make the board
+		let board = LeaderboardEntry::top(&mut conn, None, 10).expect("top failed");
+		assert_eq!(board.len(), 1);
+		assert_eq!(board[0].user_id, users[0].id);
+		assert_eq!(board[0].rank, 1);
+		assert_eq!(board[0].display_name, "wim");
+
+		let since = chrono::Utc::now().naive_utc() + chrono::Duration::days(1);
+		let board =
+			LeaderboardEntry::top(&mut conn, Some(since), 10).expect("top failed");
+		assert!(board.is_empty());
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_achievements_unlock_once() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let mut conn = mm.db();
+
+		let mut session =
+			Session::create(&mut conn, sample_session()).expect("create failed");
+		let user = User::create(
+			&mut conn,
+			NewUser {
+				username: "yara",
+				password_hash: Some("hash"),
+				is_guest: false,
+			},
+		)
+		.expect("create user failed");
+		Message::create(
+			&mut conn,
+			NewMessage {
+				session_id: session.id,
+				user_id: Some(user.id),
+				content: "It was a dark night",
+				round: 1,
+				turn_order: 0,
+				thread_id: None,
+				used_suggestion: false,
+			},
+		)
+		.expect("message create failed");
+
+		// Nothing earned while the game is still going
+		let unlocked = UserAchievement::unlock_earned(&mut conn, user.id)
+			.expect("unlock failed");
+		assert!(unlocked.is_empty());
+
+		session.status = SessionStatus::Finished;
+		Session::update_versioned(&mut conn, &session).expect("update failed");
+
+		let unlocked = UserAchievement::unlock_earned(&mut conn, user.id)
+			.expect("unlock failed");
+		let ids = unlocked.iter().map(|a| a.id).collect::<Vec<_>>();
+		assert_eq!(ids, ["first_story"]);
+
+		// Announced once only
+		let unlocked = UserAchievement::unlock_earned(&mut conn, user.id)
+			.expect("unlock failed");
+		assert!(unlocked.is_empty());
+
+		let stored =
+			UserAchievement::list_by_user(&mut conn, user.id).expect("list failed");
+		assert_eq!(stored.len(), 1);
+		assert_eq!(stored[0].achievement_id, "first_story");
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_passes_and_pending_lines() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let mut conn = mm.db();
+
+		let session = Session::create(
+			&mut conn,
+			NewSession {
+				undo_window_secs: 10,
+				..sample_session()
+			},
+		)
+		.expect("create failed");
+		let user = User::create(
+			&mut conn,
+			NewUser {
+				username: "zeno",
+				password_hash: Some("hash"),
+				is_guest: false,
+			},
+		)
+		.expect("create user failed");
+
+		let mut lines = Vec::new();
+		for (content, round) in [("The bell rang", 1), ("", 2)] {
+			lines.push(
+				Message::create(
+					&mut conn,
+					NewMessage {
+						session_id: session.id,
+						user_id: Some(user.id),
+						content,
+						round,
+						turn_order: 0,
+						thread_id: None,
+						used_suggestion: false,
+					},
+				)
+				.expect("message create failed"),
+			);
+		}
+		let (line, pass) = (&lines[0], &lines[1]);
+		assert!(pass.content.is_empty());
+
+		// The next player sees the last line written, not the pass
+		let last = Message::get_last_by_session(&mut conn, session.id)
+			.expect("get last failed");
+		assert_eq!(last.id, line.id);
+
+		let held = Session::update_versioned(
+			&mut conn,
+			&Session {
+				pending_message_id: Some(line.id),
+				..session.clone()
+			},
+		)
+		.expect("update failed");
+		assert_eq!(held.pending_message_id, Some(line.id));
+		assert_eq!(held.undo_window_secs, 10);
+
+		// Taking the line back leaves nothing pending
+		Message::delete(&mut conn, line.id).expect("delete failed");
+		let session = Session::get(&mut conn, held.id).expect("get failed");
+		assert_eq!(session.pending_message_id, None);
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
 }
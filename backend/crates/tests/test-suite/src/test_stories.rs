@@ -0,0 +1,486 @@
+#[cfg(test)]
+mod test_super {
+	use lib_auth::users::model::{NewUser, User};
+	use lib_core::model::{
+		TestModelManager,
+		base::BasicDbOps,
+		schema_enums::{
+			NarratorMode, PlayMode, ReactionKind, SessionStatus, TurnOrderMode,
+			VisibilityMode,
+		},
+	};
+	use lib_messages::{
+		model::{Message, NewMessage},
+		threads::model::Thread,
+	};
+	use lib_players::model::{NewPlayer, Player, PlayerId};
+	use lib_sessions::model::{NewSession, Session};
+	use lib_stories::{
+		model::{NewStory, Story},
+		reactions::model::{StoryRating, StoryReaction},
+		shares::model::{NewStoryShare, StoryShare},
+		variants::model::{NewStoryVariant, StoryVariant, StoryVariantVote},
+	};
+	use serial_test::serial;
+	use uuid::Uuid;
+
+	#[tokio::test]
+	#[serial]
+	async fn test_list_stories_by_participant() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let mut conn = mm.db();
+
+		let mut users = Vec::new();
+		for username in ["erin", "frank", "grace"] {
+			let user = User::create(
+				&mut conn,
+				NewUser {
+					username,
+					password_hash: Some("hash"),
+					is_guest: false,
+				},
+			)
+			.expect("create user failed");
+			users.push(user.id);
+		}
+		let (erin, frank, grace) = (users[0], users[1], users[2]);
+
+		let session = Session::create(
+			&mut conn,
+			NewSession {
+				theme: "test-theme",
+				max_rounds: 1,
+				story_variants: 1,
+				visibility: VisibilityMode::Previous,
+				visibility_words: 10,
+				play_mode: PlayMode::Linear,
+				turn_order: TurnOrderMode::JoinOrder,
+				bot_backfill: false,
+				narrator: NarratorMode::Off,
+				objectives: false,
+				undo_window_secs: 0,
+			},
+		)
+		.expect("session create failed");
+
+		// Erin is still seated, Frank wrote a message and left
+		Player::create(
+			&mut conn,
+			NewPlayer {
+				session_id: session.id,
+				user_id: erin,
+				is_ready: true,
+				is_host: true,
+				seat: 0,
+			},
+		)
+		.expect("player create failed");
+		for (user_id, turn_order) in [(frank, 1), (erin, 0)] {
+			Message::create(
+				&mut conn,
+				NewMessage {
+					session_id: session.id,
+					user_id: Some(user_id),
+					content: "once upon a time",
+					round: 1,
+					turn_order,
+					thread_id: None,
+					used_suggestion: false,
+				},
+			)
+			.expect("message create failed");
+		}
+
+		let story = Story::create(
+			&mut conn,
+			NewStory {
+				session_id: session.id,
+				content: "The end.",
+				thread_id: None,
+			},
+		)
+		.expect("story create failed");
+
+		for user_id in [erin, frank] {
+			let stories = Story::list_by_participant(&mut conn, user_id, 10, 0)
+				.expect("list failed");
+			assert_eq!(stories.len(), 1);
+			assert_eq!(stories[0].id, story.id);
+			assert_eq!(
+				Story::count_by_participant(&mut conn, user_id)
+					.expect("count failed"),
+				1
+			);
+		}
+		assert!(
+			Story::list_by_participant(&mut conn, grace, 10, 0)
+				.expect("list failed")
+				.is_empty()
+		);
+
+		// Messages come back in turn order
+		let messages =
+			Message::list_by_session(&mut conn, session.id).expect("list failed");
+		let authors: Vec<_> = messages.iter().filter_map(|m| m.user_id).collect();
+		assert_eq!(authors, vec![erin, frank]);
+
+		let found = Story::get_by_session(&mut conn, session.id)
+			.expect("get failed")
+			.expect("story not found");
+		assert_eq!(found.id, story.id);
+
+		// Unpublished stories are private to their participants
+		for user_id in [erin, frank] {
+			assert!(
+				Story::get_visible(&mut conn, story.id, user_id)
+					.expect("get failed")
+					.is_some()
+			);
+		}
+		assert!(
+			Story::get_visible(&mut conn, story.id, grace)
+				.expect("get failed")
+				.is_none()
+		);
+		assert!(!found.is_visible_to(&mut conn, grace).expect("check failed"));
+
+		// Published ones are readable by anyone
+		let published =
+			Story::set_public(&mut conn, story.id, true).expect("publish failed");
+		assert!(
+			published
+				.is_visible_to(&mut conn, grace)
+				.expect("check failed")
+		);
+		assert!(
+			Story::get_visible(&mut conn, story.id, grace)
+				.expect("get failed")
+				.is_some()
+		);
+
+		// Thread stories are listed in thread order, whenever they were told
+		let threads =
+			Thread::create_for_session(&mut conn, session.id, 2).expect("threads");
+		let mut thread_stories = Vec::new();
+		for thread in threads.iter().rev() {
+			let told = Story::create(
+				&mut conn,
+				NewStory {
+					session_id: session.id,
+					content: "A thread.",
+					thread_id: Some(thread.id),
+				},
+			)
+			.expect("story create failed");
+			thread_stories.insert(0, told.id);
+		}
+		let listed: Vec<_> = Story::list_by_session(&mut conn, session.id)
+			.expect("list failed")
+			.into_iter()
+			.map(|story| story.id)
+			.collect();
+		assert_eq!(listed[..2], thread_stories[..]);
+		assert_eq!(listed.len(), 3);
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_share_views_expiry_and_revoke() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let mut conn = mm.db();
+
+		let user = User::create(
+			&mut conn,
+			NewUser {
+				username: "heidi",
+				password_hash: Some("hash"),
+				is_guest: false,
+			},
+		)
+		.expect("create user failed");
+		let session = Session::create(
+			&mut conn,
+			NewSession {
+				theme: "test-theme",
+				max_rounds: 1,
+				story_variants: 1,
+				visibility: VisibilityMode::Previous,
+				visibility_words: 10,
+				play_mode: PlayMode::Linear,
+				turn_order: TurnOrderMode::JoinOrder,
+				bot_backfill: false,
+				narrator: NarratorMode::Off,
+				objectives: false,
+				undo_window_secs: 0,
+			},
+		)
+		.expect("session create failed");
+		let story = Story::create(
+			&mut conn,
+			NewStory {
+				session_id: session.id,
+				content: "The end.",
+				thread_id: None,
+			},
+		)
+		.expect("story create failed");
+
+		let now = chrono::Utc::now().naive_utc();
+		let share = |conn: &mut _, token, expires_at| {
+			StoryShare::create(
+				conn,
+				NewStoryShare {
+					story_id: story.id,
+					created_by: user.id,
+					token,
+					expires_at,
+				},
+			)
+			.expect("share create failed")
+		};
+		let active = share(&mut conn, "active", None);
+		share(&mut conn, "expired", Some(now - chrono::Duration::hours(1)));
+
+		for expected in [1, 2] {
+			let viewed = StoryShare::record_view(&mut conn, "active")
+				.expect("view failed")
+				.expect("share not found");
+			assert_eq!(viewed.view_count, expected);
+		}
+		assert!(
+			StoryShare::record_view(&mut conn, "expired")
+				.expect("view failed")
+				.is_none()
+		);
+
+		// Shares are only revoked through their own story
+		assert!(
+			!StoryShare::revoke(&mut conn, Uuid::new_v4(), active.id, user.id)
+				.expect("revoke")
+		);
+
+		// Revoked links stop working, revoking twice is a no-op
+		assert!(
+			StoryShare::revoke(&mut conn, story.id, active.id, user.id)
+				.expect("revoke")
+		);
+		assert!(
+			!StoryShare::revoke(&mut conn, story.id, active.id, user.id)
+				.expect("revoke")
+		);
+		assert!(
+			StoryShare::record_view(&mut conn, "active")
+				.expect("view failed")
+				.is_none()
+		);
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_votes_are_unique_and_feed_lists_public_stories() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let mut conn = mm.db();
+
+		let user = User::create(
+			&mut conn,
+			NewUser {
+				username: "ivan",
+				password_hash: Some("hash"),
+				is_guest: false,
+			},
+		)
+		.expect("create user failed");
+		let session = Session::create(
+			&mut conn,
+			NewSession {
+				theme: "test-theme",
+				max_rounds: 1,
+				story_variants: 1,
+				visibility: VisibilityMode::Previous,
+				visibility_words: 10,
+				play_mode: PlayMode::Linear,
+				turn_order: TurnOrderMode::JoinOrder,
+				bot_backfill: false,
+				narrator: NarratorMode::Off,
+				objectives: false,
+				undo_window_secs: 0,
+			},
+		)
+		.expect("session create failed");
+		let mut stories = Vec::new();
+		for content in ["private", "public"] {
+			let story = Story::create(
+				&mut conn,
+				NewStory {
+					session_id: session.id,
+					content,
+					thread_id: None,
+				},
+			)
+			.expect("story create failed");
+			stories.push(story.id);
+		}
+		let (private, public) = (stories[0], stories[1]);
+		Story::set_public(&mut conn, public, true).expect("publish failed");
+
+		// One reaction of a kind per user
+		let like = ReactionKind::Like;
+		assert!(StoryReaction::add(&mut conn, public, user.id, like).expect("add"));
+		assert!(!StoryReaction::add(&mut conn, public, user.id, like).expect("add"));
+		StoryReaction::add(&mut conn, public, user.id, ReactionKind::Wow)
+			.expect("add failed");
+		let counts = StoryReaction::counts(&mut conn, &[public]).expect("counts");
+		assert_eq!(counts.len(), 2);
+		assert!(counts.iter().all(|(_, _, count)| *count == 1));
+
+		// Rating again replaces the previous one
+		StoryRating::upsert(&mut conn, public, user.id, 2).expect("rate failed");
+		StoryRating::upsert(&mut conn, public, user.id, 5).expect("rate failed");
+		let summary = StoryRating::summary(&mut conn, public).expect("summary");
+		assert_eq!(summary.count, 1);
+		assert_eq!(summary.average, Some(5.0));
+		assert!(StoryRating::upsert(&mut conn, public, user.id, 6).is_err());
+
+		let feed = Story::feed(&mut conn, 10, 0).expect("feed failed");
+		assert_eq!(feed.len(), 1);
+		assert_eq!(feed[0].id, public);
+		assert_eq!(feed[0].reaction_count, 2);
+		assert_eq!(feed[0].rating_avg, Some(5.0));
+		assert!(feed.iter().all(|story| story.id != private));
+		assert_eq!(Story::count_public(&mut conn).expect("count failed"), 1);
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_variant_votes_count_only_current_players() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let mut conn = mm.db();
+
+		let session = Session::create(
+			&mut conn,
+			NewSession {
+				theme: "test-theme",
+				max_rounds: 1,
+				story_variants: 2,
+				visibility: VisibilityMode::Previous,
+				visibility_words: 10,
+				play_mode: PlayMode::Linear,
+				turn_order: TurnOrderMode::JoinOrder,
+				bot_backfill: false,
+				narrator: NarratorMode::Off,
+				objectives: false,
+				undo_window_secs: 0,
+			},
+		)
+		.expect("session create failed");
+
+		let mut variants = Vec::new();
+		for (variant_index, style) in [(0, "classic"), (1, "dark")] {
+			let variant = StoryVariant::create(
+				&mut conn,
+				NewStoryVariant {
+					session_id: session.id,
+					variant_index,
+					style,
+					temperature: 0.7,
+					content: style,
+				},
+			)
+			.expect("variant create failed");
+			variants.push(variant.id);
+		}
+		let (classic, dark) = (variants[0], variants[1]);
+
+		let mut users = Vec::new();
+		for username in ["judy", "ken"] {
+			let user = User::create(
+				&mut conn,
+				NewUser {
+					username,
+					password_hash: Some("hash"),
+					is_guest: false,
+				},
+			)
+			.expect("create user failed");
+			Player::create(
+				&mut conn,
+				NewPlayer {
+					session_id: session.id,
+					user_id: user.id,
+					is_ready: true,
+					is_host: users.is_empty(),
+					seat: users.len() as i32,
+				},
+			)
+			.expect("player create failed");
+			users.push(user.id);
+		}
+		let (judy, ken) = (users[0], users[1]);
+
+		// The deadline stays with the session, to close the vote after a restart
+		let ends_at = chrono::NaiveDate::from_ymd_opt(2030, 1, 1)
+			.and_then(|day| day.and_hms_opt(12, 0, 0))
+			.expect("valid date");
+		Session::update_versioned(
+			&mut conn,
+			&Session {
+				status: SessionStatus::Voting,
+				voting_ends_at: Some(ends_at),
+				..session.clone()
+			},
+		)
+		.expect("update failed");
+		let voting = Session::list_by_status(&mut conn, SessionStatus::Voting)
+			.expect("list failed");
+		assert_eq!(voting.len(), 1);
+		assert_eq!(voting[0].id, session.id);
+		assert_eq!(voting[0].voting_ends_at, Some(ends_at));
+		assert!(
+			Session::list_by_status(&mut conn, SessionStatus::Started)
+				.expect("list failed")
+				.is_empty()
+		);
+
+		// Voting again moves the vote
+		StoryVariantVote::cast(&mut conn, session.id, judy, classic).expect("vote");
+		StoryVariantVote::cast(&mut conn, session.id, judy, dark).expect("vote");
+		StoryVariantVote::cast(&mut conn, session.id, ken, dark).expect("vote");
+		let tally = StoryVariantVote::tally(&mut conn, session.id).expect("tally");
+		assert_eq!(tally, vec![(dark, 2)]);
+
+		// Votes of players who left no longer count
+		Player::delete(
+			&mut conn,
+			PlayerId {
+				session_id: session.id,
+				user_id: ken,
+			},
+		)
+		.expect("player delete failed");
+		assert_eq!(
+			StoryVariantVote::count_by_session(&mut conn, session.id)
+				.expect("count failed"),
+			1
+		);
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+}
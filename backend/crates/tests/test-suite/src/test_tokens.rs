@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod test_super {
+	use chrono::{Duration, Utc};
+	use lib_auth::{
+		auth::jwt::{generate_refresh_token, hash_refresh_token},
+		tokens::model::{NewRefreshToken, RefreshToken, RevokedToken},
+		users::model::{NewUser, User},
+	};
+	use lib_core::model::{TestModelManager, base::BasicDbOps};
+	use serial_test::serial;
+	use uuid::Uuid;
+
+	#[tokio::test]
+	#[serial]
+	async fn test_refresh_token_revoke_once() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let mut conn = mm.db();
+
+		let user = User::create(
+			&mut conn,
+			NewUser {
+				username: "alice",
+				password_hash: Some("hash"),
+				is_guest: false,
+			},
+		)
+		.expect("create user failed");
+
+		// Only the hash is stored, lookup goes through it
+		let token = generate_refresh_token();
+		let token_hash = hash_refresh_token(&token);
+		let created = RefreshToken::create(
+			&mut conn,
+			NewRefreshToken {
+				user_id: user.id,
+				token_hash: &token_hash,
+				expires_at: Utc::now().naive_utc() + Duration::days(1),
+			},
+		)
+		.expect("create refresh token failed");
+		assert_ne!(created.token_hash, token);
+
+		let found = RefreshToken::find_by_hash(&mut conn, &token_hash)
+			.expect("find failed")
+			.expect("token not found");
+		assert_eq!(found.id, created.id);
+		assert!(found.revoked_at.is_none());
+
+		// Second revoke is a no-op
+		assert!(RefreshToken::revoke(&mut conn, created.id).expect("revoke failed"));
+		assert!(
+			!RefreshToken::revoke(&mut conn, created.id).expect("revoke failed")
+		);
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_revoked_jti() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let mut conn = mm.db();
+
+		let user = User::create(
+			&mut conn,
+			NewUser {
+				username: "bob",
+				password_hash: Some("hash"),
+				is_guest: false,
+			},
+		)
+		.expect("create user failed");
+
+		let jti = Uuid::new_v4();
+		assert!(!RevokedToken::is_revoked(&mut conn, jti).expect("check failed"));
+
+		let expires_at = Utc::now().naive_utc() + Duration::hours(1);
+		RevokedToken::revoke(&mut conn, jti, user.id, expires_at)
+			.expect("revoke failed");
+		// Revoking twice is fine
+		RevokedToken::revoke(&mut conn, jti, user.id, expires_at)
+			.expect("revoke failed");
+
+		assert!(RevokedToken::is_revoked(&mut conn, jti).expect("check failed"));
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+}
@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod test_super {
+	use lib_auth::users::model::{NewUser, User};
+	use lib_core::model::{TestModelManager, base::BasicDbOps};
+	use lib_profiles::model::Profile;
+	use serial_test::serial;
+
+	#[tokio::test]
+	#[serial]
+	async fn test_upgrade_guest_keeps_id() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let mut conn = mm.db();
+
+		let guest = User::create(
+			&mut conn,
+			NewUser {
+				username: "guest-0000abcd",
+				password_hash: None,
+				is_guest: true,
+			},
+		)
+		.expect("create guest failed");
+		assert!(guest.is_guest);
+
+		let upgraded = User::upgrade_guest(&mut conn, guest.id, "Carol", "hash")
+			.expect("upgrade failed");
+		assert_eq!(upgraded.id, guest.id);
+		assert_eq!(upgraded.username, "Carol");
+		assert_eq!(upgraded.password_hash.as_deref(), Some("hash"));
+		assert!(!upgraded.is_guest);
+
+		// Only guests can be upgraded, lookup ignores case
+		assert!(User::upgrade_guest(&mut conn, guest.id, "Carol2", "hash").is_err());
+		let found = User::find_by_username(&mut conn, "carol")
+			.expect("find failed")
+			.expect("user not found");
+		assert_eq!(found.id, guest.id);
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+
+	#[tokio::test]
+	#[serial]
+	async fn test_display_name_falls_back_to_username() {
+		let mm = TestModelManager::new()
+			.await
+			.expect("Failed to create ModelManager");
+		let mut conn = mm.db();
+
+		let user = User::create(
+			&mut conn,
+			NewUser {
+				username: "dave",
+				password_hash: Some("hash"),
+				is_guest: false,
+			},
+		)
+		.expect("create user failed");
+
+		// No profile yet
+		let name = Profile::display_name(&mut conn, user.id).expect("name failed");
+		assert_eq!(name, "dave");
+
+		let mut profile =
+			Profile::get_or_empty(&mut conn, user.id).expect("profile failed");
+		profile.display_name = Some("Dave the Bard".to_string());
+		Profile::upsert(&mut conn, &profile).expect("upsert failed");
+
+		let view =
+			Profile::get_user_profile(&mut conn, user.id).expect("view failed");
+		assert_eq!(view.username, "dave");
+		assert_eq!(view.display_name, "Dave the Bard");
+
+		// Drop test DB
+		mm.drop_db().await.expect("Failed to drop test DB");
+	}
+}
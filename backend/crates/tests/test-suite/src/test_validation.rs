@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod test_super {
+	use lib_auth::auth::{
+		error::AuthError,
+		validation::{PasswordPolicy, UsernamePolicy},
+	};
+
+	fn username_policy() -> UsernamePolicy {
+		UsernamePolicy {
+			min_len: 3,
+			max_len: 8,
+			extra_chars: "_-".to_string(),
+		}
+	}
+
+	fn is_invalid_username(result: Result<(), AuthError>) -> bool {
+		matches!(result, Err(AuthError::InvalidUsername(_)))
+	}
+
+	fn is_weak_password(result: Result<(), AuthError>) -> bool {
+		matches!(result, Err(AuthError::WeakPassword(_)))
+	}
+
+	#[test]
+	fn test_username_length_bounds() {
+		let policy = username_policy();
+
+		assert!(is_invalid_username(policy.check("ab")));
+		policy.check("abc").expect("min length");
+		policy.check("abcdefgh").expect("max length");
+		assert!(is_invalid_username(policy.check("abcdefghi")));
+
+		// Length counts characters, not bytes
+		let policy = UsernamePolicy {
+			extra_chars: "é".to_string(),
+			..policy
+		};
+		policy.check("éé1").expect("three characters");
+	}
+
+	#[test]
+	fn test_username_charset() {
+		let policy = username_policy();
+
+		policy.check("Ab_1-z").expect("letters, digits and extras");
+		assert!(is_invalid_username(policy.check("ab.c")));
+		assert!(is_invalid_username(policy.check("ab c")));
+		assert!(is_invalid_username(policy.check("abç")));
+
+		// Extra characters come from the policy
+		let policy = UsernamePolicy {
+			extra_chars: ".".to_string(),
+			..policy
+		};
+		policy.check("ab.c").expect("dot allowed");
+		assert!(is_invalid_username(policy.check("ab_c")));
+
+		let policy = UsernamePolicy {
+			extra_chars: String::new(),
+			..policy
+		};
+		policy.check("abc1").expect("letters and digits");
+		assert!(is_invalid_username(policy.check("ab.c")));
+	}
+
+	#[test]
+	fn test_password_length_bound() {
+		let policy = PasswordPolicy {
+			min_len: 8,
+			min_char_classes: 1,
+		};
+
+		assert!(is_weak_password(policy.check("abcdefg")));
+		policy.check("abcdefgh").expect("min length");
+		policy.check("").expect_err("empty");
+	}
+
+	#[test]
+	fn test_password_char_classes() {
+		let policy = PasswordPolicy {
+			min_len: 4,
+			min_char_classes: 3,
+		};
+
+		// One and two classes
+		assert!(is_weak_password(policy.check("abcdefgh")));
+		assert!(is_weak_password(policy.check("abcdEFGH")));
+		// Lowercase, uppercase and digit
+		policy.check("abcDEF12").expect("three classes");
+		// A symbol counts as a class
+		policy.check("abcdef1!").expect("symbol is a class");
+		policy.check("ABCD 123").expect("space is a symbol");
+		assert!(is_weak_password(policy.check("abcd!!!!")));
+
+		let policy = PasswordPolicy {
+			min_char_classes: 4,
+			..policy
+		};
+		assert!(is_weak_password(policy.check("abcDEF12")));
+		policy.check("abcDEF1!").expect("all four classes");
+	}
+}
@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod test_super {
+	use lib_core::model::schema_enums::VisibilityMode;
+	use lib_game_logic::visibility::excerpt;
+
+	#[test]
+	fn test_excerpt_per_mode() {
+		let contents = vec![
+			"The door creaked open.".to_string(),
+			"Nobody was there. Or so it seemed! A shadow moved".to_string(),
+		];
+
+		assert_eq!(excerpt(VisibilityMode::Previous, 3, &contents), contents[1]);
+		assert_eq!(
+			excerpt(VisibilityMode::LastWords, 3, &contents),
+			"A shadow moved"
+		);
+		assert_eq!(
+			excerpt(VisibilityMode::LastSentence, 3, &contents),
+			"A shadow moved"
+		);
+		assert_eq!(
+			excerpt(VisibilityMode::Open, 3, &contents),
+			format!("{}\n{}", contents[0], contents[1])
+		);
+		assert_eq!(excerpt(VisibilityMode::Blind, 3, &contents), "");
+
+		// Closing punctuation belongs to the last sentence
+		let closed = vec!["It rained. Then it stopped!".to_string()];
+		assert_eq!(
+			excerpt(VisibilityMode::LastSentence, 3, &closed),
+			"Then it stopped!"
+		);
+		assert_eq!(excerpt(VisibilityMode::LastWords, 10, &closed), closed[0]);
+		assert_eq!(excerpt(VisibilityMode::LastSentence, 3, &[]), "");
+	}
+}
@@ -0,0 +1,2 @@
+-- Usernames are unique regardless of case
+CREATE UNIQUE INDEX users_username_lower_idx ON users (lower(username));
@@ -0,0 +1,3 @@
+-- Guests have no password until they upgrade to a full account
+ALTER TABLE users ALTER COLUMN password_hash DROP NOT NULL;
+ALTER TABLE users ADD COLUMN is_guest BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,7 @@
+CREATE TABLE profiles (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    display_name TEXT NULL,
+    avatar_url TEXT NULL,
+    bio TEXT NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT now()
+);
@@ -0,0 +1 @@
+DROP TABLE story_shares;
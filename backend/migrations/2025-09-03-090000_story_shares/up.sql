@@ -0,0 +1,13 @@
+-- Public, revocable links to a story
+CREATE TABLE story_shares (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    story_id UUID NOT NULL REFERENCES stories(id) ON DELETE CASCADE,
+    created_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token TEXT NOT NULL UNIQUE,
+    expires_at TIMESTAMP NULL,
+    revoked_at TIMESTAMP NULL,
+    view_count BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE INDEX story_shares_story_id_idx ON story_shares(story_id);
@@ -0,0 +1,5 @@
+DROP TABLE story_ratings;
+DROP TABLE story_reactions;
+DROP TYPE reaction_kind;
+DROP INDEX stories_public_created_at_idx;
+ALTER TABLE stories DROP COLUMN is_public;
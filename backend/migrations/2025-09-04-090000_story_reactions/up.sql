@@ -0,0 +1,25 @@
+-- Stories are private to their players until published to the feed
+ALTER TABLE stories ADD COLUMN is_public BOOLEAN NOT NULL DEFAULT false;
+
+CREATE INDEX stories_public_created_at_idx ON stories(created_at DESC) WHERE is_public;
+
+CREATE TYPE reaction_kind AS ENUM ('like', 'love', 'laugh', 'wow', 'sad');
+
+-- One reaction of each kind per user and story
+CREATE TABLE story_reactions (
+    story_id UUID NOT NULL REFERENCES stories(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind reaction_kind NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    PRIMARY KEY (story_id, user_id, kind)
+);
+
+-- One rating per user and story, changed in place
+CREATE TABLE story_ratings (
+    story_id UUID NOT NULL REFERENCES stories(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    stars SMALLINT NOT NULL CHECK (stars BETWEEN 1 AND 5),
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP NOT NULL DEFAULT now(),
+    PRIMARY KEY (story_id, user_id)
+);
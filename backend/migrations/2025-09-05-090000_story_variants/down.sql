@@ -0,0 +1,18 @@
+DROP TABLE story_variant_votes;
+DROP TABLE story_variants;
+ALTER TABLE sessions DROP COLUMN story_variants;
+
+-- Enum values can't be dropped, recreate the type without 'voting'
+UPDATE sessions SET status = 'finished' WHERE status = 'voting';
+ALTER TYPE session_status RENAME TO session_status_old;
+CREATE TYPE session_status AS ENUM (
+    'waiting',
+    'started',
+    'waiting_for_story_generation',
+    'finished'
+);
+ALTER TABLE sessions
+    ALTER COLUMN status DROP DEFAULT,
+    ALTER COLUMN status TYPE session_status USING status::text::session_status,
+    ALTER COLUMN status SET DEFAULT 'waiting';
+DROP TYPE session_status_old;
@@ -0,0 +1,26 @@
+-- Players vote on the best of several generated stories
+ALTER TYPE session_status ADD VALUE 'voting' AFTER 'waiting_for_story_generation';
+
+ALTER TABLE sessions
+    ADD COLUMN story_variants INT NOT NULL DEFAULT 1
+    CHECK (story_variants BETWEEN 1 AND 5);
+
+CREATE TABLE story_variants (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    session_id UUID NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    variant_index INT NOT NULL,
+    style TEXT NOT NULL,
+    temperature REAL NOT NULL,
+    content TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    UNIQUE (session_id, variant_index)
+);
+
+-- One vote per player, changed in place until voting closes
+CREATE TABLE story_variant_votes (
+    session_id UUID NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    variant_id UUID NOT NULL REFERENCES story_variants(id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    PRIMARY KEY (session_id, user_id)
+);
@@ -0,0 +1,4 @@
+ALTER TABLE sessions
+    DROP COLUMN visibility_words,
+    DROP COLUMN visibility;
+DROP TYPE visibility_mode;
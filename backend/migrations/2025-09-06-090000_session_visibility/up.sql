@@ -0,0 +1,13 @@
+-- How much of the story so far the player on turn gets to see
+CREATE TYPE visibility_mode AS ENUM (
+    'previous',
+    'last_words',
+    'last_sentence',
+    'open',
+    'blind'
+);
+
+ALTER TABLE sessions
+    ADD COLUMN visibility visibility_mode NOT NULL DEFAULT 'previous',
+    ADD COLUMN visibility_words INT NOT NULL DEFAULT 10
+    CHECK (visibility_words BETWEEN 1 AND 100);
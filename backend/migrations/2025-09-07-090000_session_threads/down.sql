@@ -0,0 +1,6 @@
+ALTER TABLE stories DROP COLUMN thread_id;
+DROP INDEX messages_thread_id_idx;
+ALTER TABLE messages DROP COLUMN thread_id;
+DROP TABLE threads;
+ALTER TABLE sessions DROP COLUMN play_mode;
+DROP TYPE play_mode;
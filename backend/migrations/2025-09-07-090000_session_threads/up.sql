@@ -0,0 +1,19 @@
+-- 'rotating': every player writes each round, on a thread that rotates
+CREATE TYPE play_mode AS ENUM ('linear', 'rotating');
+
+ALTER TABLE sessions ADD COLUMN play_mode play_mode NOT NULL DEFAULT 'linear';
+
+CREATE TABLE threads (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    session_id UUID NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    thread_index INT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    UNIQUE (session_id, thread_index)
+);
+
+ALTER TABLE messages
+    ADD COLUMN thread_id UUID REFERENCES threads(id) ON DELETE CASCADE;
+CREATE INDEX messages_thread_id_idx ON messages (thread_id);
+
+ALTER TABLE stories
+    ADD COLUMN thread_id UUID REFERENCES threads(id) ON DELETE SET NULL;
@@ -0,0 +1,4 @@
+ALTER TABLE sessions DROP COLUMN turn_order;
+DROP TYPE turn_order_mode;
+ALTER TABLE players DROP CONSTRAINT players_session_id_seat_key;
+ALTER TABLE players DROP COLUMN seat;
@@ -0,0 +1,27 @@
+-- Explicit seats, so turn order survives players leaving
+ALTER TABLE players ADD COLUMN seat INT NOT NULL DEFAULT 0;
+
+UPDATE players p
+SET seat = s.seat
+FROM (
+    SELECT session_id, user_id,
+        (row_number() OVER (PARTITION BY session_id ORDER BY joined_at) - 1)::INT
+            AS seat
+    FROM players
+) s
+WHERE p.session_id = s.session_id AND p.user_id = s.user_id;
+
+-- Deferred, so seats can be swapped within a transaction
+ALTER TABLE players
+    ADD CONSTRAINT players_session_id_seat_key UNIQUE (session_id, seat)
+    DEFERRABLE INITIALLY DEFERRED;
+
+CREATE TYPE turn_order_mode AS ENUM (
+    'join_order',
+    'shuffled',
+    'rotating_starter',
+    'manual'
+);
+
+ALTER TABLE sessions
+    ADD COLUMN turn_order turn_order_mode NOT NULL DEFAULT 'join_order';
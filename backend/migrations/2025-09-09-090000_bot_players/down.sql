@@ -0,0 +1,3 @@
+ALTER TABLE sessions DROP COLUMN bot_backfill;
+DROP TABLE bots;
+DROP TYPE bot_difficulty;
@@ -0,0 +1,12 @@
+CREATE TYPE bot_difficulty AS ENUM ('easy', 'normal', 'hard');
+
+-- AI-controlled players, each bot is a user of its own
+CREATE TABLE bots (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    persona TEXT NOT NULL,
+    difficulty bot_difficulty NOT NULL DEFAULT 'normal',
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- Whether a bot takes over the seat of a player leaving mid-game
+ALTER TABLE sessions ADD COLUMN bot_backfill BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,5 @@
+DELETE FROM messages WHERE user_id IS NULL;
+ALTER TABLE messages ALTER COLUMN user_id SET NOT NULL;
+
+ALTER TABLE sessions DROP COLUMN narrator;
+DROP TYPE narrator_mode;
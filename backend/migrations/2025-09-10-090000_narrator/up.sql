@@ -0,0 +1,6 @@
+CREATE TYPE narrator_mode AS ENUM ('off', 'bridge', 'twists');
+
+ALTER TABLE sessions ADD COLUMN narrator narrator_mode NOT NULL DEFAULT 'off';
+
+-- Narration between rounds is written by no one
+ALTER TABLE messages ALTER COLUMN user_id DROP NOT NULL;
@@ -0,0 +1,2 @@
+ALTER TABLE messages DROP COLUMN used_suggestion;
+DROP TABLE suggestions;
@@ -0,0 +1,12 @@
+-- "Inspire me" requests, limited per turn
+CREATE TABLE suggestions (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    session_id UUID NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    round INT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX suggestions_turn_idx ON suggestions (session_id, user_id, round);
+
+ALTER TABLE messages ADD COLUMN used_suggestion BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,2 @@
+DROP TABLE player_objectives;
+ALTER TABLE sessions DROP COLUMN objectives;
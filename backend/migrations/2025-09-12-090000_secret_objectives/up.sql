@@ -0,0 +1,14 @@
+-- Competitive mode: every player gets a secret objective, judged at the end
+ALTER TABLE sessions ADD COLUMN objectives BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Kept when the player leaves, so their score stays on the scoreboard
+CREATE TABLE player_objectives (
+    session_id UUID NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    objective TEXT NOT NULL,
+    -- NULL until judged
+    met BOOLEAN,
+    points INT NOT NULL DEFAULT 0,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (session_id, user_id)
+);
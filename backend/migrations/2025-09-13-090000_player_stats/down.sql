@@ -0,0 +1,4 @@
+DROP INDEX player_objectives_created_at_idx;
+DROP INDEX player_objectives_user_id_idx;
+DROP INDEX story_ratings_user_id_idx;
+DROP INDEX messages_user_id_idx;
@@ -0,0 +1,5 @@
+-- Stats are read from the game tables, these keep the per-user lookups cheap
+CREATE INDEX messages_user_id_idx ON messages (user_id);
+CREATE INDEX story_ratings_user_id_idx ON story_ratings (user_id);
+CREATE INDEX player_objectives_user_id_idx ON player_objectives (user_id);
+CREATE INDEX player_objectives_created_at_idx ON player_objectives (created_at);
@@ -0,0 +1,2 @@
+ALTER TABLE sessions DROP COLUMN pending_message_id;
+ALTER TABLE sessions DROP COLUMN undo_window_secs;
@@ -0,0 +1,6 @@
+-- Seconds a player may take back or edit their line before the turn moves on,
+-- 0 hands the turn on right away
+ALTER TABLE sessions ADD COLUMN undo_window_secs INT NOT NULL DEFAULT 0;
+-- The line in its undo window, the next player waits until it's released
+ALTER TABLE sessions ADD COLUMN pending_message_id UUID
+    REFERENCES messages(id) ON DELETE SET NULL;
@@ -0,0 +1 @@
+ALTER TABLE sessions DROP COLUMN voting_ends_at;
@@ -0,0 +1,3 @@
+-- When an open vote closes, whoever has voted by then.
+-- Kept with the session so the vote still closes after a restart
+ALTER TABLE sessions ADD COLUMN voting_ends_at TIMESTAMP;
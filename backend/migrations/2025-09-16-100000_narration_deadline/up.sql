@@ -0,0 +1,3 @@
+-- Set while the narrator writes between rounds, nobody is on turn meanwhile.
+-- Past it the game goes on without the interlude, also after a restart
+ALTER TABLE sessions ADD COLUMN narration_ends_at TIMESTAMP;
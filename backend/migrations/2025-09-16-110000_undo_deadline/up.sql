@@ -0,0 +1,3 @@
+-- When the pending line's undo window closes. Kept with the session so the
+-- turn still moves on after a restart
+ALTER TABLE sessions ADD COLUMN pending_until TIMESTAMP;